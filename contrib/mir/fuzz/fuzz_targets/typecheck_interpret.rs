@@ -0,0 +1,31 @@
+/******************************************************************************/
+/*                                                                            */
+/* SPDX-License-Identifier: MIT                                               */
+/* Copyright (c) [2023] Serokell <hi@serokell.io>                             */
+/*                                                                            */
+/******************************************************************************/
+
+//! Typecheck arbitrary code on an empty stack and, if it typechecks, interpret
+//! it. Neither is expected to panic.
+
+#![no_main]
+
+use arbitrary::Unstructured;
+use libfuzzer_sys::fuzz_target;
+use mir::ast::Micheline;
+use mir::context::Ctx;
+use mir::gas::Gas;
+use mir::stack::stk;
+use typed_arena::Arena;
+
+fuzz_target!(|data: &[u8]| {
+    let arena = Arena::new();
+    let Ok(code) = Micheline::arbitrary(&arena, &mut Unstructured::new(data)) else {
+        return;
+    };
+    let mut ctx = Ctx::default();
+    ctx.gas = Gas::new(1_000_000);
+    if let Ok(instr) = code.typecheck_instruction(&mut ctx, None, &[]) {
+        let _ = instr.interpret(&mut ctx, &arena, &mut stk![]);
+    }
+});
@@ -70,6 +70,8 @@ pub struct Ticket<'a> {
 /// The names of the variants correspond to the names of Michelson types, but
 /// snake_case is converted to PascalCase.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[allow(missing_docs)]
 pub enum Type {
     Nat,
@@ -529,7 +531,9 @@ pub enum Instruction<'a> {
     Add(overloads::Add),
     Mul(overloads::Mul),
     Neg(overloads::Neg),
+    Sub(overloads::Sub),
     SubMutez,
+    Ediv(overloads::Ediv),
     Dip(Option<u16>, Vec<Self>),
     Drop(Option<u16>),
     Dup(Option<u16>),
@@ -554,6 +558,10 @@ pub enum Instruction<'a> {
     Failwith(Type),
     Never,
     Unit,
+    /// `CAST`, only checks the type at typechecking time, no-op at runtime.
+    Cast,
+    /// `RENAME`, only changes annotations, no-op at runtime.
+    Rename,
     Car,
     Cdr,
     Pair,
@@ -565,6 +573,7 @@ pub enum Instruction<'a> {
     Amount,
     Nil,
     EmptySet,
+    EmptyMap,
     EmptyBigMap(Type, Type),
     Mem(overloads::Mem),
     Get(overloads::Get),
@@ -582,6 +591,8 @@ pub enum Instruction<'a> {
     Or(overloads::Or),
     Xor(overloads::Xor),
     Not(overloads::Not),
+    Lsl,
+    Lsr,
     IfCons(Vec<Self>, Vec<Self>),
     Iter(overloads::Iter, Vec<Self>),
     IfLeft(Vec<Self>, Vec<Self>),
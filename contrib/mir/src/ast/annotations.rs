@@ -60,6 +60,11 @@ pub const NO_ANNS: Annotations = Annotations::new();
 
 /// Errors that can happen when working with [Annotations].
 #[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", content = "details")
+)]
 pub enum AnnotationError {
     /// Expected at most one field annotation, but found multiple.
     #[error("unexpected second field annotation: {0}")]
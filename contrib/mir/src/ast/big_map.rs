@@ -101,6 +101,11 @@ impl<'a> BigMap<'a> {
 
 /// Errors that can happen when working with lazy storage.
 #[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", content = "details")
+)]
 pub enum LazyStorageError {
     /// Decoding from the internal representation failed.
     #[error("decode failed {0}")]
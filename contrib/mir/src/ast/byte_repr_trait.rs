@@ -13,6 +13,11 @@ use tezos_crypto_rs::hash::FromBytesError;
 
 /// Errors that can happen when working with [ByteReprTrait].
 #[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", content = "details")
+)]
 pub enum ByteReprError {
     /// Encountered an unknown prefix while trying to decode a value either from
     /// bytes or base58-check string.
@@ -7,6 +7,9 @@
 
 use super::TypedValue;
 
+// `Ord` is defined in terms of `partial_cmp`, as values of different types
+// don't compare.
+#[allow(clippy::incorrect_partial_ord_impl_on_ord_type)]
 impl PartialOrd for TypedValue<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         use TypedValue::*;
@@ -118,6 +121,14 @@ mod tests {
         assert_cmp!(String; "foo".to_owned(); "foo".to_owned(); Equal);
         assert_cmp!(String; "foo".to_owned(); "bar".to_owned(); Greater);
 
+        assert_cmp!(Bytes; vec![]; vec![]; Equal);
+        assert_cmp!(Bytes; vec![]; vec![0x00]; Less);
+        assert_cmp!(Bytes; vec![0x00]; vec![0x00, 0x00]; Less);
+        assert_cmp!(Bytes; vec![0x01]; vec![0x00]; Greater);
+        assert_cmp!(Bytes; vec![0x01]; vec![0x00, 0xff]; Greater);
+        assert_cmp!(Bytes; vec![0x00, 0x01]; vec![0x00, 0x02]; Less);
+        assert_cmp!(Bytes; vec![0xab, 0xcd]; vec![0xab, 0xcd]; Equal);
+
         assert_cmp!(V::new_option; None; None; Equal);
         assert_cmp!(V::new_option; None; Some(V::int(3)); Less);
         assert_cmp!(V::new_option; Some(V::int(3)); None; Greater);
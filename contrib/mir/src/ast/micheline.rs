@@ -7,6 +7,10 @@
 
 //! Definition of Micheline representation and utilities for working with it.
 
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+mod pretty;
+
 use num_bigint::{BigInt, BigUint};
 use typed_arena::Arena;
 
@@ -253,20 +257,13 @@ macro_rules! micheline_fields {
 /// supported. Useful for total match in the typechecker.
 macro_rules! micheline_unsupported_instructions {
     () => {
-        Prim::SUB
-            | Prim::EDIV
-            | Prim::LSL
-            | Prim::LSR
-            | Prim::EMPTY_MAP
-            | Prim::SAPLING_EMPTY_STATE
+        Prim::SAPLING_EMPTY_STATE
             | Prim::SAPLING_VERIFY_UPDATE
             | Prim::OPEN_CHEST
             | Prim::VIEW
             | Prim::CREATE_ACCOUNT
             | Prim::STEPS_TO_QUOTA
             | Prim::TICKET_DEPRECATED
-            | Prim::CAST
-            | Prim::RENAME
     };
 }
 
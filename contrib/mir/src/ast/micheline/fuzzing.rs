@@ -0,0 +1,123 @@
+/******************************************************************************/
+/*                                                                            */
+/* SPDX-License-Identifier: MIT                                               */
+/* Copyright (c) [2023] Serokell <hi@serokell.io>                             */
+/*                                                                            */
+/******************************************************************************/
+
+//! Generating [Micheline] from fuzzer input, see [Micheline::arbitrary].
+//!
+//! *N.B.* Only available when the `arbitrary` feature is enabled.
+
+use arbitrary::{Result, Unstructured};
+use typed_arena::Arena;
+
+use super::Micheline;
+use crate::ast::annotations::NO_ANNS;
+
+/// Maximum nesting depth of generated nodes.
+const MAX_DEPTH: usize = 8;
+
+/// Maximum number of arguments of a generated primitive application.
+const MAX_ARGS: usize = 3;
+
+/// Maximum number of elements of a generated sequence.
+const MAX_SEQ_LEN: usize = 8;
+
+impl<'a> Micheline<'a> {
+    /// Generate a node from unstructured fuzzer input, allocating its children
+    /// in the [Arena]. The result is a valid Micheline tree, but not
+    /// necessarily a well-typed script or value: primitives are applied to
+    /// arbitrary arguments, regardless of their arity. Nesting depth is
+    /// bounded, so that processing the node doesn't overflow the stack.
+    pub fn arbitrary(arena: &'a Arena<Micheline<'a>>, u: &mut Unstructured) -> Result<Self> {
+        arbitrary_node(arena, u, MAX_DEPTH)
+    }
+}
+
+fn arbitrary_node<'a>(
+    arena: &'a Arena<Micheline<'a>>,
+    u: &mut Unstructured,
+    depth: usize,
+) -> Result<Micheline<'a>> {
+    // leaves only at the maximum depth
+    let choices = if depth == 0 { 4 } else { 6 };
+    Ok(match u.choose_index(choices)? {
+        0 => Micheline::prim0(u.arbitrary()?),
+        1 => Micheline::Int(u.arbitrary::<i64>()?.into()),
+        2 => Micheline::String(u.arbitrary()?),
+        3 => Micheline::Bytes(u.arbitrary()?),
+        4 => Micheline::App(
+            u.arbitrary()?,
+            arbitrary_nodes(arena, u, depth - 1, MAX_ARGS)?,
+            NO_ANNS,
+        ),
+        _ => Micheline::Seq(arbitrary_nodes(arena, u, depth - 1, MAX_SEQ_LEN)?),
+    })
+}
+
+fn arbitrary_nodes<'a>(
+    arena: &'a Arena<Micheline<'a>>,
+    u: &mut Unstructured,
+    depth: usize,
+    max_len: usize,
+) -> Result<&'a [Micheline<'a>]> {
+    let len = u.int_in_range(0..=max_len)?;
+    let nodes = (0..len)
+        .map(|_| arbitrary_node(arena, u, depth))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Micheline::alloc_iter(arena, nodes.into_iter()))
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use typed_arena::Arena;
+
+    use crate::ast::{IntoMicheline, Micheline, Type};
+    use crate::context::Ctx;
+    use crate::gas::Gas;
+    use crate::stk;
+
+    /// Deterministic pseudo-random inputs, standing in for a fuzzing corpus.
+    fn seed_corpus() -> impl Iterator<Item = Vec<u8>> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..200).map(move |_| {
+            (0..512)
+                .map(|_| {
+                    // constants from Knuth's MMIX linear congruential generator
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    (state >> 56) as u8
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn typecheck_interpret_doesnt_panic() {
+        for input in seed_corpus() {
+            let arena = Arena::new();
+            let code = Micheline::arbitrary(&arena, &mut Unstructured::new(&input)).unwrap();
+            let mut ctx = Ctx::default();
+            ctx.gas = Gas::new(1_000_000);
+            if let Ok(instr) = code.typecheck_instruction(&mut ctx, None, &[]) {
+                let _ = instr.interpret(&mut ctx, &arena, &mut stk![]);
+            }
+        }
+    }
+
+    #[test]
+    fn type_roundtrip() {
+        for input in seed_corpus() {
+            let arena = Arena::new();
+            let ty = Type::arbitrary(&mut Unstructured::new(&input)).unwrap();
+            let untyped = (&ty).into_micheline_optimized_legacy(&arena);
+            // ill-formed types, e.g. maps with incomparable keys, are rejected
+            if let Ok(parsed) = untyped.parse_ty(&mut Ctx::default()) {
+                assert_eq!(parsed, ty);
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+/******************************************************************************/
+/*                                                                            */
+/* SPDX-License-Identifier: MIT                                               */
+/* Copyright (c) [2023] Serokell <hi@serokell.io>                             */
+/*                                                                            */
+/******************************************************************************/
+
+//! Rendering [Micheline] as Michelson source.
+
+use std::fmt::{Display, Formatter, Result, Write};
+
+use super::Micheline;
+
+/// Renders the node as Michelson source, which parses back to the same node.
+///
+/// By default, the node is rendered on a single line. With the alternate flag,
+/// i.e. `{:#}`, each element of a sequence is put on its own line, indented
+/// two spaces deeper than the enclosing sequence.
+impl Display for Micheline<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let indent = f.alternate().then_some(0);
+        write_node(f, self, indent, false)
+    }
+}
+
+/// Write the node. `indent` is the indentation of the enclosing sequence if
+/// rendering on multiple lines, [None] otherwise. `nested` is true for
+/// primitive arguments, which need parentheses unless they are atomic.
+fn write_node(
+    f: &mut Formatter<'_>,
+    node: &Micheline,
+    indent: Option<usize>,
+    nested: bool,
+) -> Result {
+    match node {
+        Micheline::Int(i) => write!(f, "{i}"),
+        Micheline::String(s) => write_string(f, s),
+        Micheline::Bytes(bs) => write!(f, "0x{}", hex::encode(bs)),
+        Micheline::App(prim, args, anns) => {
+            let parens = nested && !(args.is_empty() && anns.is_empty());
+            if parens {
+                f.write_char('(')?;
+            }
+            write!(f, "{prim}")?;
+            for ann in anns.iter() {
+                write!(f, " {ann}")?;
+            }
+            for arg in args.iter() {
+                f.write_char(' ')?;
+                write_node(f, arg, indent, true)?;
+            }
+            if parens {
+                f.write_char(')')?;
+            }
+            Ok(())
+        }
+        Micheline::Seq([]) => f.write_str("{}"),
+        Micheline::Seq(elts) => match indent {
+            None => {
+                f.write_str("{ ")?;
+                for (ix, elt) in elts.iter().enumerate() {
+                    if ix > 0 {
+                        f.write_str("; ")?;
+                    }
+                    write_node(f, elt, None, false)?;
+                }
+                f.write_str(" }")
+            }
+            Some(indent) => {
+                f.write_char('{')?;
+                for (ix, elt) in elts.iter().enumerate() {
+                    if ix > 0 {
+                        f.write_char(';')?;
+                    }
+                    write!(f, "\n{:1$}", "", indent + 2)?;
+                    write_node(f, elt, Some(indent + 2), false)?;
+                }
+                write!(f, "\n{:1$}}}", "", indent)
+            }
+        },
+    }
+}
+
+/// Write a string literal, escaping characters as the lexer expects.
+fn write_string(f: &mut Formatter<'_>, s: &str) -> Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::test_helpers::parse;
+
+    #[track_caller]
+    fn roundtrip(src: &str, expected: &str) {
+        let ast = parse(src).unwrap();
+        assert_eq!(ast.to_string(), expected);
+        assert_eq!(parse(expected).unwrap(), ast);
+        assert_eq!(parse(&format!("{ast:#}")).unwrap(), ast);
+    }
+
+    #[test]
+    fn atoms() {
+        roundtrip("1", "1");
+        roundtrip("-12", "-12");
+        roundtrip("0xdeadF00D", "0xdeadf00d");
+        roundtrip(r#""foo\"bar\\\n\r\t""#, r#""foo\"bar\\\n\r\t""#);
+        roundtrip("Unit", "Unit");
+    }
+
+    #[test]
+    fn apps() {
+        roundtrip("DUP 2", "DUP 2");
+        roundtrip("DIP 3 {DROP 2}", "DIP 3 { DROP 2 }");
+        roundtrip(
+            "PUSH (pair int (option nat)) (Pair 1 None)",
+            "PUSH (pair int (option nat)) (Pair 1 None)",
+        );
+        roundtrip("PUSH @var :ty %field int 1", "PUSH @var :ty %field int 1");
+        roundtrip("(option %a int)", "option %a int");
+        roundtrip("pair (int :a) nat", "pair (int :a) nat");
+    }
+
+    #[test]
+    fn seqs() {
+        roundtrip("{}", "{}");
+        roundtrip("{ {} ; { {} } }", "{ {}; { {} } }");
+        roundtrip("{UNIT;DROP;}", "{ UNIT; DROP }");
+        roundtrip(
+            "{ PUSH nat 1 ; IF_NONE { } { DROP } }",
+            "{ PUSH nat 1; IF_NONE {} { DROP } }",
+        );
+    }
+
+    #[test]
+    fn multiline() {
+        let ast = parse("{ PUSH bool True; IF { DIP { DROP } } {}; UNIT }").unwrap();
+        assert_eq!(
+            format!("{ast:#}"),
+            "{
+  PUSH bool True;
+  IF {
+    DIP {
+      DROP
+    }
+  } {};
+  UNIT
+}"
+        );
+    }
+}
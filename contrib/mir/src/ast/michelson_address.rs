@@ -123,6 +123,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_base58_bad_checksum() {
+        for addr in [
+            "tz1Nw5nr152qddEjKT2dKBH8XcBMDAg72iLx",
+            "KT1BRd2ka5q2cPRdXALtXD1QZ38CPam2j1yf",
+        ] {
+            assert_eq!(
+                Address::from_base58_check(addr),
+                Err(ByteReprError::WrongFormat("invalid checksum".to_owned())),
+            );
+        }
+    }
+
     #[test]
     fn test_bin_to_base58() {
         // explicit default entrypoint is apparently forbidden in binary encoding
@@ -19,6 +19,7 @@ use super::ByteReprError;
 /// words, the part after `%` in `KT1BRd2ka5q2cPRdXALtXD1QZ38CPam2j1ye%foo`.
 /// Tezos entrypoints are ASCII strings of at most 31 characters long.
 #[derive(Debug, Clone, Eq, PartialOrd, Ord, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Entrypoint(String);
 
 /// A structure mapping from entrypoints to their types. This is simply an alias
@@ -18,11 +18,34 @@ pub enum Add {
     IntNat,
     NatInt,
     MutezMutez,
+    TimestampInt,
+    IntTimestamp,
     Bls12381G1,
     Bls12381G2,
     Bls12381Fr,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Sub {
+    IntInt,
+    NatNat,
+    IntNat,
+    NatInt,
+    MutezMutez,
+    TimestampInt,
+    TimestampTimestamp,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Ediv {
+    NatNat,
+    NatInt,
+    IntNat,
+    IntInt,
+    MutezNat,
+    MutezMutez,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum And {
     Bool,
@@ -17,6 +17,32 @@ use num_bigint::{BigInt, BigUint};
 use std::collections::HashMap;
 use tezos_crypto_rs::hash::OperationListHash;
 
+/// Default value of [Ctx::max_interpret_depth].
+pub const DEFAULT_MAX_INTERPRET_DEPTH: usize = 1000;
+
+/// Native stack that must be left to enter a nested code block without
+/// growing the stack. A level takes a few KiB with an optimized build, but
+/// up to a couple hundred KiB with an unoptimized one.
+#[cfg(not(target_arch = "wasm32"))]
+const NESTING_RED_ZONE: usize = 1024 * 1024;
+
+/// Size of the stack segments allocated when the native stack runs low.
+#[cfg(not(target_arch = "wasm32"))]
+const NESTING_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Run `f`, which typechecks or interprets a nested code block, growing the
+/// native stack onto the heap first if little of it is left. Thus reaching
+/// [Ctx::max_interpret_depth] doesn't overflow the stack regardless of the
+/// stack size of the current thread.
+///
+/// On `wasm32` the stack can't be grown, and `f` is simply called.
+pub(crate) fn with_nesting_stack<R>(f: impl FnOnce() -> R) -> R {
+    #[cfg(not(target_arch = "wasm32"))]
+    return stacker::maybe_grow(NESTING_RED_ZONE, NESTING_STACK_SIZE, f);
+    #[cfg(target_arch = "wasm32")]
+    f()
+}
+
 /// [Ctx] includes "outer context" required for typechecking and interpreting
 /// Michelson.
 pub struct Ctx<'a> {
@@ -26,7 +52,8 @@ pub struct Ctx<'a> {
     pub amount: i64,
     /// Contract balance. Defaults to `0`.
     pub balance: i64,
-    /// Current blockchain level. Defaults to `0`.
+    /// Current blockchain level. The result of the `LEVEL` instruction, which
+    /// is thus constant within a single execution. Defaults to `0`.
     pub level: BigUint,
     /// Transfer sender, i.e. the contract that initiated the current internal
     /// transaction. The result of the `SENDER` instruction. Defaults to
@@ -61,7 +88,8 @@ pub struct Ctx<'a> {
     /// [Self::set_voting_powers]. Defaults to returning `0` for any address.
     pub voting_powers: Box<dyn Fn(&KeyHash) -> BigUint>,
     /// The minimal injection time for the current block, as a unix timestamp
-    /// (in seconds). Defaults to `0`.
+    /// (in seconds). The result of the `NOW` instruction, which is thus
+    /// constant within a single execution. Defaults to `0`.
     pub now: BigInt,
     /// Total voting power. Note that if you are setting this manually, you must
     /// also provide a consistent implementation for [Self::voting_powers]. See
@@ -79,8 +107,23 @@ pub struct Ctx<'a> {
     /// admit a custom implementation of [LazyStorage] trait. Defaults to a new,
     /// empty, [InMemoryLazyStorage].
     pub big_map_storage: Box<dyn LazyStorage<'a> + 'a>,
+    /// Maximum nesting depth of code blocks being typechecked or interpreted,
+    /// i.e. nested bodies of `DIP`, `IF`, `LOOP`, lambdas, etc. Exceeding it
+    /// fails with
+    /// [TcError::StackOverflow](crate::typechecker::TcError::StackOverflow) or
+    /// [InterpretError::StackOverflow](crate::interpreter::InterpretError::StackOverflow)
+    /// respectively. Defaults to [DEFAULT_MAX_INTERPRET_DEPTH].
+    pub max_interpret_depth: usize,
+    /// Nesting depth of code blocks currently being typechecked or
+    /// interpreted. Typechecking may happen during interpretation, e.g. for
+    /// `UNPACK`, so the two share the counter.
+    pub(crate) nesting_depth: usize,
     origination_counter: u32,
     operation_counter: u128,
+    /// Addresses of the instructions that failed to typecheck, innermost
+    /// first. Only recorded by
+    /// [Micheline::typecheck_with_spans](crate::ast::Micheline::typecheck_with_spans).
+    pub(crate) failed_instructions: Option<Vec<usize>>,
 }
 
 impl Ctx<'_> {
@@ -147,6 +190,8 @@ impl Default for Ctx<'_> {
             voting_powers: Box::new(|_| 0u32.into()),
             total_voting_power: 0u32.into(),
             big_map_storage: Box::new(InMemoryLazyStorage::new()),
+            max_interpret_depth: DEFAULT_MAX_INTERPRET_DEPTH,
+            nesting_depth: 0,
             operation_counter: 0,
             operation_group_hash: OperationListHash::from_base58_check(
                 "onvsLP3JFZia2mzZKWaFuFkWg2L5p3BDUhzh5Kr6CiDDN3rtQ1D",
@@ -157,6 +202,7 @@ impl Default for Ctx<'_> {
             .try_into()
             .unwrap(),
             origination_counter: 0,
+            failed_instructions: None,
         }
     }
 }
@@ -7,17 +7,143 @@
 
 //! Gas accounting and costs.
 
+use checked::Checked;
 use num_bigint::{BigInt, BigUint};
 
 /// Structure carrying the remaining gas amount.
 #[derive(Debug)]
 pub struct Gas {
     milligas_amount: Option<u32>,
+    milligas_limit: u32,
+    costs: Box<GasCosts>,
+}
+
+/// Table of the costs charged by the typechecker and the interpreter. The
+/// default table follows the Tezos protocol; override it with
+/// [Gas::with_costs] to model protocol versions with different costs.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GasCosts {
+    /// Typechecking costs.
+    pub tc: tc_cost::TcCosts,
+    /// Interpretation costs.
+    pub interpret: interpret_cost::InterpretCosts,
+}
+
+#[cfg(test)]
+impl GasCosts {
+    /// Apply `f` to every cost in the table.
+    pub(crate) fn map(self, f: impl Fn(u32) -> u32) -> Self {
+        GasCosts {
+            tc: self.tc.map(&f),
+            interpret: self.interpret.map(&f),
+        }
+    }
+}
+
+/// A cost proportional to some size `n`: of an operand in bytes, of a stack in
+/// elements, of a type in nodes, &c. The costs in the Tezos protocol are rarely
+/// a whole number of milligas per unit, and are approximated by sums of `n /
+/// 2^k`, each rounded down, hence the cost is `Σ coefficients[k] * (n >> k)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeCost {
+    /// Coefficient of `n >> k` at index `k`.
+    pub coefficients: [u32; SizeCost::MAX_SHIFT + 1],
+}
+
+impl SizeCost {
+    /// Largest `k` in `n >> k` a [SizeCost] can have.
+    pub const MAX_SHIFT: usize = 13;
+
+    /// `milligas` per unit.
+    pub const fn per_unit(milligas: u32) -> Self {
+        let mut coefficients = [0; SizeCost::MAX_SHIFT + 1];
+        coefficients[0] = milligas;
+        SizeCost { coefficients }
+    }
+
+    /// One milligas per `2^shift` units, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// If `shift` exceeds [SizeCost::MAX_SHIFT].
+    pub const fn fraction(shift: usize) -> Self {
+        SizeCost::per_unit(0).plus_fraction(shift)
+    }
+
+    /// Add one milligas per `2^shift` units, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// If `shift` exceeds [SizeCost::MAX_SHIFT].
+    pub const fn plus_fraction(mut self, shift: usize) -> Self {
+        self.coefficients[shift] += 1;
+        self
+    }
+
+    /// Cost of size `n`.
+    pub fn of(&self, n: Checked<u64>) -> Checked<u64> {
+        self.coefficients
+            .iter()
+            .zip(0u32..)
+            .fold(Checked::from(0), |acc, (c, k)| acc + (n >> k) * *c as u64)
+    }
+}
+
+#[cfg(test)]
+trait MapCost {
+    fn map(self, f: &impl Fn(u32) -> u32) -> Self;
+}
+
+#[cfg(test)]
+impl MapCost for u32 {
+    fn map(self, f: &impl Fn(u32) -> u32) -> Self {
+        f(self)
+    }
+}
+
+#[cfg(test)]
+impl MapCost for SizeCost {
+    fn map(self, f: &impl Fn(u32) -> u32) -> Self {
+        SizeCost {
+            coefficients: self.coefficients.map(f),
+        }
+    }
+}
+
+/// Define a table of costs, as a struct with a public field per cost, and its
+/// [Default] implementation, giving the default value of each cost.
+macro_rules! cost_table {
+    ($(#[$meta:meta])* $name:ident { $($field:ident: $ty:ty = $default:expr),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name {
+            $(pub $field: $ty),*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $($field: $default),*
+                }
+            }
+        }
+
+        #[cfg(test)]
+        impl $name {
+            pub(crate) fn map(self, f: &impl Fn(u32) -> u32) -> Self {
+                use super::MapCost;
+                $name {
+                    $($field: self.$field.map(f)),*
+                }
+            }
+        }
+    };
 }
 
 /// Out of gas error.
 #[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
 #[error("out of gas")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct OutOfGas;
 
 /// Default gas limit per transaction, according to
@@ -37,9 +163,24 @@ impl Gas {
     pub fn new(milligas_amount: u32) -> Gas {
         Gas {
             milligas_amount: Some(milligas_amount),
+            milligas_limit: milligas_amount,
+            costs: Box::default(),
         }
     }
 
+    /// Charge the costs in `costs` instead of the default ones.
+    pub fn with_costs(self, costs: GasCosts) -> Gas {
+        Gas {
+            costs: Box::new(costs),
+            ..self
+        }
+    }
+
+    /// The table of costs charged.
+    pub fn costs(&self) -> &GasCosts {
+        &self.costs
+    }
+
     /// Try to consume the specified milligas `cost`. If not enough gas left,
     /// return [OutOfGas], and mark gas as exhausted.
     ///
@@ -64,6 +205,22 @@ impl Gas {
         self.milligas_amount
             .expect("Access to gas after exhaustion")
     }
+
+    /// Get the remaining milligas amount, which is `0` if gas was exhausted.
+    pub fn remaining_milligas(&self) -> u32 {
+        self.milligas_amount.unwrap_or(0)
+    }
+
+    /// Return `milligas` previously consumed. The remaining amount never
+    /// exceeds the amount [Gas] was constructed with; any excess is ignored.
+    ///
+    /// # Panics
+    ///
+    /// If gas was previously exhausted.
+    pub fn refund(&mut self, milligas: u32) {
+        let remaining = self.milligas().saturating_add(milligas);
+        self.milligas_amount = Some(remaining.min(self.milligas_limit));
+    }
 }
 
 trait AsGasCost {
@@ -125,7 +282,7 @@ impl Log2i for u64 {
 pub mod tc_cost {
     use checked::Checked;
 
-    use super::{AsGasCost, Log2i, OutOfGas};
+    use super::{AsGasCost, GasCosts, Log2i, OutOfGas, SizeCost};
 
     // Due to the quirk of the Tezos protocol implementation, step gas is
     // charged twice as often as in MIR.
@@ -173,43 +330,72 @@ pub mod tc_cost {
     // corresponds to cost_DECODING_CHAIN_ID in the protocol
     pub const CHAIN_ID_OPTIMIZED: u32 = 50;
 
-    pub fn timestamp_decoding(l: usize) -> Result<u32, OutOfGas> {
-        use integer_sqrt::IntegerSquareRoot;
-        let v0: Checked<usize> = Checked::from(l.integer_sqrt()) * l;
-        (105 + ((v0 >> 5) + (v0 >> 6))).as_gas_cost()
+    cost_table! {
+        /// Typechecking costs. Fields without a suffix are base costs, the
+        /// `_per_*` ones are costs proportional to some size.
+        TcCosts {
+            instr_step: u32 = INSTR_STEP,
+            value_step: u32 = VALUE_STEP,
+            parse_type_step: u32 = PARSE_TYPE_STEP,
+            type_prop_step: u32 = TYPE_PROP_STEP,
+            key_hash_readable: u32 = KEY_HASH_READABLE,
+            key_hash_optimized: u32 = KEY_HASH_OPTIMIZED,
+            bls_fr: u32 = BLS_FR,
+            bls_g1: u32 = BLS_G1,
+            bls_g2: u32 = BLS_G2,
+            key_readable: u32 = KEY_READABLE,
+            key_optimized: u32 = KEY_OPTIMIZED,
+            chain_id_readable: u32 = CHAIN_ID_READABLE,
+            chain_id_optimized: u32 = CHAIN_ID_OPTIMIZED,
+            timestamp_decoding: u32 = 105,
+            timestamp_decoding_per_unit: SizeCost = SizeCost::fraction(5).plus_fraction(6),
+            // corresponds to Cost_of.Typechecking.proof_argument in the protocol
+            proof_argument_per_elt: SizeCost = SizeCost::per_unit(50),
+            ty_eq_per_node: SizeCost = SizeCost::per_unit(60),
+            construct_map_per_elt: SizeCost = SizeCost::per_unit(80),
+            construct_map_per_unit: SizeCost = SizeCost::per_unit(1),
+            construct_set_per_elt: SizeCost = SizeCost::per_unit(130),
+            construct_set_per_unit: SizeCost = SizeCost::per_unit(1),
+        }
     }
 
-    fn variadic(depth: u16) -> Result<u32, OutOfGas> {
-        let depth = Checked::from(depth as u32);
-        (depth * 50).as_gas_cost()
+    pub fn timestamp_decoding(costs: &GasCosts, l: usize) -> Result<u32, OutOfGas> {
+        use integer_sqrt::IntegerSquareRoot;
+        let c = &costs.tc;
+        let v0 = Checked::from(l.integer_sqrt() as u64) * l as u64;
+        (c.timestamp_decoding_per_unit.of(v0) + c.timestamp_decoding as u64).as_gas_cost()
     }
 
-    pub fn dig_n(depth: usize) -> Result<u32, OutOfGas> {
+    fn proof_argument(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
         // corresponds to Cost_of.Typechecking.proof_argument in the protocol
-        (Checked::from(depth) * 50).as_gas_cost()
+        let size = Checked::from(size as u64);
+        costs.tc.proof_argument_per_elt.of(size).as_gas_cost()
     }
 
-    pub fn dug_n(depth: usize) -> Result<u32, OutOfGas> {
-        // corresponds to Cost_of.Typechecking.proof_argument in the protocol
-        (Checked::from(depth) * 50).as_gas_cost()
+    pub fn dig_n(costs: &GasCosts, depth: usize) -> Result<u32, OutOfGas> {
+        proof_argument(costs, depth)
+    }
+
+    pub fn dug_n(costs: &GasCosts, depth: usize) -> Result<u32, OutOfGas> {
+        proof_argument(costs, depth)
     }
 
-    pub fn drop_n(depth: &Option<u16>) -> Result<u32, OutOfGas> {
-        depth.map_or(Ok(0), variadic)
+    pub fn drop_n(costs: &GasCosts, depth: &Option<u16>) -> Result<u32, OutOfGas> {
+        depth.map_or(Ok(0), |depth| proof_argument(costs, depth as usize))
     }
 
-    pub fn dip_n(depth: &Option<u16>) -> Result<u32, OutOfGas> {
-        depth.map_or(Ok(0), variadic)
+    pub fn dip_n(costs: &GasCosts, depth: &Option<u16>) -> Result<u32, OutOfGas> {
+        depth.map_or(Ok(0), |depth| proof_argument(costs, depth as usize))
     }
 
-    pub fn ty_eq(sz1: usize, sz2: usize) -> Result<u32, OutOfGas> {
+    pub fn ty_eq(costs: &GasCosts, sz1: usize, sz2: usize) -> Result<u32, OutOfGas> {
         // complexity of comparing types T and U is O(min(|T|, |U|)), as
         // comparison short-circuits at the first mismatch
-        let sz = Checked::from(std::cmp::min(sz1, sz2));
-        (sz * 60).as_gas_cost()
+        let sz = Checked::from(std::cmp::min(sz1, sz2) as u64);
+        costs.tc.ty_eq_per_node.of(sz).as_gas_cost()
     }
 
-    pub fn construct_map(key_size: usize, sz: usize) -> Result<u32, OutOfGas> {
+    pub fn construct_map(costs: &GasCosts, key_size: usize, sz: usize) -> Result<u32, OutOfGas> {
         // Tezos protocol constructs maps element by element, thus the cost ends
         // up Σ (80 + key_size*log2(i)) = 80 * n + key_size * Σ log2(i) = 80 * n
         // + key_size * log2(Π i) = 80 * n + key_size * log2(n!)
@@ -217,38 +403,36 @@ pub mod tc_cost {
         // ≈ 80 * n + key_size * n * log2(n)
         // which seems like a reasonable first-order approximation.
         // to avoid log2(0) it's more practical to compute log2(n + 1)
-        let n = Checked::from(sz);
-        let key_size = Checked::from(key_size);
-        let log2n = (n + 1).ok_or(OutOfGas)?.log2i() as usize;
-        (80 * n + key_size * n * log2n).as_gas_cost()
+        let c = &costs.tc;
+        let n = Checked::from(sz as u64);
+        let log2n = (n + 1).ok_or(OutOfGas)?.log2i() as u64;
+        let v0 = n * key_size as u64 * log2n;
+        (c.construct_map_per_elt.of(n) + c.construct_map_per_unit.of(v0)).as_gas_cost()
     }
 
-    pub fn construct_set(val_size: usize, sz: usize) -> Result<u32, OutOfGas> {
+    pub fn construct_set(costs: &GasCosts, val_size: usize, sz: usize) -> Result<u32, OutOfGas> {
         // Similar to `construct_map`, only the coefficient differs
-        let n = Checked::from(sz);
-        let key_size = Checked::from(val_size);
-        let log2n = (n + 1).ok_or(OutOfGas)?.log2i() as usize;
-        (130 * n + key_size * n * log2n).as_gas_cost()
+        let c = &costs.tc;
+        let n = Checked::from(sz as u64);
+        let log2n = (n + 1).ok_or(OutOfGas)?.log2i() as u64;
+        let v0 = n * val_size as u64 * log2n;
+        (c.construct_set_per_elt.of(n) + c.construct_set_per_unit.of(v0)).as_gas_cost()
     }
 
-    pub fn pair_n(size: usize) -> Result<u32, OutOfGas> {
-        // corresponds to Cost_of.Typechecking.proof_argument in the protocol
-        (Checked::from(size) * 50).as_gas_cost()
+    pub fn pair_n(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        proof_argument(costs, size)
     }
 
-    pub fn unpair_n(size: usize) -> Result<u32, OutOfGas> {
-        // corresponds to Cost_of.Typechecking.proof_argument in the protocol
-        (Checked::from(size) * 50).as_gas_cost()
+    pub fn unpair_n(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        proof_argument(costs, size)
     }
 
-    pub fn get_n(size: usize) -> Result<u32, OutOfGas> {
-        // corresponds to Cost_of.Typechecking.proof_argument in the protocol
-        (Checked::from(size) * 50).as_gas_cost()
+    pub fn get_n(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        proof_argument(costs, size)
     }
 
-    pub fn update_n(size: usize) -> Result<u32, OutOfGas> {
-        // corresponds to Cost_of.Typechecking.proof_argument in the protocol
-        (Checked::from(size) * 50).as_gas_cost()
+    pub fn update_n(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        proof_argument(costs, size)
     }
 }
 
@@ -282,7 +466,7 @@ pub mod interpret_cost {
     use num_bigint::{BigInt, BigUint};
     use num_traits::Zero;
 
-    use super::{AsGasCost, BigIntByteSize, Log2i, OutOfGas};
+    use super::{AsGasCost, BigIntByteSize, GasCosts, Log2i, OutOfGas, SizeCost};
     use crate::ast::{Key, KeyHash, Micheline, Or, Ticket, TypedValue};
 
     pub const DIP: u32 = 10;
@@ -319,6 +503,8 @@ pub mod interpret_cost {
     pub const NEG_G1: u32 = 50;
     pub const NEG_G2: u32 = 70;
     pub const SUB_MUTEZ: u32 = 15;
+    pub const EDIV_TEZ: u32 = 80;
+    pub const EDIV_TEZ_NAT: u32 = 70;
     pub const UNIT: u32 = 10;
     pub const AND_BOOL: u32 = 10;
     pub const OR_BOOL: u32 = 10;
@@ -334,6 +520,7 @@ pub mod interpret_cost {
     pub const NIL: u32 = 10;
     pub const CONS: u32 = 15;
     pub const EMPTY_SET: u32 = 300;
+    pub const EMPTY_MAP: u32 = 300;
     pub const SIZE_STRING: u32 = 15;
     pub const SIZE_BYTES: u32 = 10;
     pub const SIZE_LIST: u32 = 10;
@@ -387,118 +574,418 @@ pub mod interpret_cost {
     pub const LOOP_EXIT: u32 = 10;
     pub const CREATE_CONTRACT: u32 = 60;
 
-    pub fn join_tickets(t1: &Ticket, t2: &Ticket) -> Result<u32, OutOfGas> {
-        compare(&t1.content, &t2.content)?;
-        add_num(&t1.amount, &t2.amount)
+    cost_table! {
+        /// Interpretation costs. Fields without a suffix are base costs, the
+        /// `_per_*` ones are costs proportional to some size.
+        InterpretCosts {
+            dip: u32 = DIP,
+            drop: u32 = DROP,
+            dup: u32 = DUP,
+            gt: u32 = GT,
+            ge: u32 = GE,
+            eq: u32 = EQ,
+            neq: u32 = NEQ,
+            le: u32 = LE,
+            lt: u32 = LT,
+            if_: u32 = IF,
+            if_none: u32 = IF_NONE,
+            if_cons: u32 = IF_CONS,
+            if_left: u32 = IF_LEFT,
+            loop_: u32 = LOOP,
+            iter: u32 = ITER,
+            swap: u32 = SWAP,
+            int_nat: u32 = INT_NAT,
+            isnat: u32 = ISNAT,
+            int_bls_fr: u32 = INT_BLS_FR,
+            push: u32 = PUSH,
+            add_tez: u32 = ADD_TEZ,
+            add_bls_fr: u32 = ADD_BLS_FR,
+            add_bls_g1: u32 = ADD_BLS_G1,
+            add_bls_g2: u32 = ADD_BLS_G2,
+            mul_bls_g1: u32 = MUL_BLS_G1,
+            mul_bls_g2: u32 = MUL_BLS_G2,
+            mul_bls_fr: u32 = MUL_BLS_FR,
+            mul_tez_nat: u32 = MUL_TEZ_NAT,
+            mul_nat_tez: u32 = MUL_NAT_TEZ,
+            neg_fr: u32 = NEG_FR,
+            neg_g1: u32 = NEG_G1,
+            neg_g2: u32 = NEG_G2,
+            sub_mutez: u32 = SUB_MUTEZ,
+            ediv_tez: u32 = EDIV_TEZ,
+            ediv_tez_nat: u32 = EDIV_TEZ_NAT,
+            unit: u32 = UNIT,
+            and_bool: u32 = AND_BOOL,
+            or_bool: u32 = OR_BOOL,
+            xor_bool: u32 = XOR_BOOL,
+            not_bool: u32 = NOT_BOOL,
+            car: u32 = CAR,
+            cdr: u32 = CDR,
+            pair: u32 = PAIR,
+            unpair: u32 = UNPAIR,
+            some: u32 = SOME,
+            none: u32 = NONE,
+            amount: u32 = AMOUNT,
+            nil: u32 = NIL,
+            cons: u32 = CONS,
+            empty_set: u32 = EMPTY_SET,
+            empty_map: u32 = EMPTY_MAP,
+            size_string: u32 = SIZE_STRING,
+            size_bytes: u32 = SIZE_BYTES,
+            size_list: u32 = SIZE_LIST,
+            size_set: u32 = SIZE_SET,
+            size_map: u32 = SIZE_MAP,
+            empty_big_map: u32 = EMPTY_BIG_MAP,
+            chain_id: u32 = CHAIN_ID,
+            pack: u32 = PACK,
+            self_: u32 = SELF,
+            address: u32 = ADDRESS,
+            left: u32 = LEFT,
+            right: u32 = RIGHT,
+            map_option: u32 = MAP_OPTION,
+            map_list: u32 = MAP_LIST,
+            map_map: u32 = MAP_MAP,
+            transfer_tokens: u32 = TRANSFER_TOKENS,
+            set_delegate: u32 = SET_DELEGATE,
+            lambda: u32 = LAMBDA,
+            exec: u32 = EXEC,
+            hash_key: u32 = HASH_KEY,
+            apply: u32 = APPLY,
+            ticket: u32 = TICKET,
+            read_ticket: u32 = READ_TICKET,
+            balance: u32 = BALANCE,
+            contract: u32 = CONTRACT,
+            level: u32 = LEVEL,
+            min_block_time: u32 = MIN_BLOCK_TIME,
+            self_address: u32 = SELF_ADDRESS,
+            sender: u32 = SENDER,
+            source: u32 = SOURCE,
+            now: u32 = NOW,
+            implicit_account: u32 = IMPLICIT_ACCOUNT,
+            voting_power: u32 = VOTING_POWER,
+            total_voting_power: u32 = TOTAL_VOTING_POWER,
+            emit: u32 = EMIT,
+            interpret_ret: u32 = INTERPRET_RET,
+            loop_enter: u32 = LOOP_ENTER,
+            loop_left_enter: u32 = LOOP_LEFT_ENTER,
+            loop_exit: u32 = LOOP_EXIT,
+            create_contract: u32 = CREATE_CONTRACT,
+            split_ticket: u32 = 40,
+            split_ticket_per_byte: SizeCost = SizeCost::fraction(1),
+            // Approximates 30 + 2.713108*n, copied from the Tezos protocol
+            drop_n: u32 = 30,
+            drop_n_per_elt: SizeCost = SizeCost::per_unit(2).plus_fraction(1).plus_fraction(3),
+            // Approximates 15 + 4.05787663635*n, copied from the Tezos protocol
+            dip_n: u32 = 15,
+            dip_n_per_elt: SizeCost = SizeCost::per_unit(4),
+            // this is derived by observing gas costs as of Nairobi, as charged
+            // by the Tezos protocol. It seems undip cost is charged as
+            // cost_N_KUndip * n + cost_N_KCons,
+            // where cost_N_KUndip = cost_N_KCons = 10
+            undip: u32 = 10,
+            undip_per_elt: SizeCost = SizeCost::per_unit(10),
+            // Approximates 20 + 1.222263*n, copied from the Tezos protocol
+            dup_n: u32 = 20,
+            dup_n_per_elt: SizeCost = SizeCost::per_unit(1).plus_fraction(2),
+            dig: u32 = 30,
+            dig_per_elt: SizeCost = SizeCost::per_unit(6).plus_fraction(1).plus_fraction(2),
+            dug: u32 = 35,
+            dug_per_elt: SizeCost = SizeCost::per_unit(6).plus_fraction(1).plus_fraction(2),
+            add_num: u32 = 35,
+            add_num_per_byte: SizeCost = SizeCost::fraction(1),
+            ediv_num: u32 = 105,
+            ediv_num_per_unit: SizeCost =
+                SizeCost::fraction(10).plus_fraction(11).plus_fraction(13),
+            and_num: u32 = 35,
+            and_num_per_byte: SizeCost = SizeCost::fraction(1),
+            and_bytes: u32 = 35,
+            and_bytes_per_byte: SizeCost = SizeCost::fraction(1),
+            or_num: u32 = 35,
+            or_num_per_byte: SizeCost = SizeCost::fraction(1),
+            or_bytes: u32 = 35,
+            or_bytes_per_byte: SizeCost = SizeCost::fraction(1),
+            xor_nat: u32 = 35,
+            xor_nat_per_byte: SizeCost = SizeCost::fraction(1),
+            xor_bytes: u32 = 40,
+            xor_bytes_per_byte: SizeCost = SizeCost::fraction(1),
+            not_num: u32 = 25,
+            not_num_per_byte: SizeCost = SizeCost::fraction(1),
+            lsl_nat: u32 = 45,
+            lsl_nat_per_byte: SizeCost = SizeCost::fraction(1),
+            lsr_nat: u32 = 45,
+            lsr_nat_per_byte: SizeCost = SizeCost::fraction(1),
+            not_bytes: u32 = 30,
+            not_bytes_per_byte: SizeCost = SizeCost::fraction(1),
+            mul_int: u32 = 55,
+            mul_int_per_unit: SizeCost = SizeCost::fraction(1).plus_fraction(2).plus_fraction(4),
+            // Approximating 35 + 0.024413 x term
+            compare_bytes: u32 = 35,
+            compare_bytes_per_byte: SizeCost = SizeCost::fraction(6).plus_fraction(7),
+            compare_unit: u32 = 10,
+            compare_pair: u32 = 10,
+            compare_option: u32 = 10,
+            compare_or: u32 = 10,
+            compare_chain_id: u32 = 30,
+            // hard-coded in the protocol
+            compare_key: u32 = 92,
+            // hard-coded in the protocol
+            compare_signature: u32 = 92,
+            concat_list_precheck_per_elt: SizeCost = SizeCost::per_unit(10),
+            // Copied from the Tezos protocol
+            concat_string_list: u32 = 100,
+            concat_string_list_per_byte: SizeCost = SizeCost::fraction(1),
+            concat_bytes_list: u32 = 100,
+            concat_bytes_list_per_byte: SizeCost = SizeCost::fraction(1),
+            concat_string_pair: u32 = 45,
+            concat_string_pair_per_byte: SizeCost = SizeCost::fraction(1),
+            concat_bytes_pair: u32 = 45,
+            concat_bytes_pair_per_byte: SizeCost = SizeCost::fraction(1),
+            map_get: u32 = 80,
+            set_mem: u32 = 115,
+            map_update: u32 = 80,
+            set_update: u32 = 130,
+            map_get_and_update: u32 = 80,
+            micheline_encoding_per_node: SizeCost = SizeCost::per_unit(100),
+            micheline_encoding_per_zarith_byte: SizeCost = SizeCost::per_unit(25),
+            micheline_encoding_per_str_byte: SizeCost = SizeCost::per_unit(10),
+            check_signature_ed25519: u32 = 65800,
+            check_signature_ed25519_per_byte: SizeCost = SizeCost::per_unit(1).plus_fraction(3),
+            check_signature_secp256k1: u32 = 51600,
+            check_signature_secp256k1_per_byte: SizeCost = SizeCost::per_unit(1).plus_fraction(3),
+            check_signature_p256: u32 = 341000,
+            check_signature_p256_per_byte: SizeCost = SizeCost::per_unit(1).plus_fraction(3),
+            check_signature_bls: u32 = 1570000,
+            check_signature_bls_per_byte: SizeCost = SizeCost::per_unit(3),
+            slice: u32 = 25,
+            slice_per_byte: SizeCost = SizeCost::fraction(1),
+            // fun size -> (430. + (1.125 * size))
+            blake2b: u32 = 430,
+            blake2b_per_byte: SizeCost = SizeCost::per_unit(1).plus_fraction(3),
+            // fun size -> (1350. + (8.25 * size))
+            keccak: u32 = 1350,
+            keccak_per_byte: SizeCost = SizeCost::per_unit(8).plus_fraction(2),
+            // fun size -> (600. + (4.75 * size))
+            sha256: u32 = 600,
+            sha256_per_byte: SizeCost = SizeCost::per_unit(4).plus_fraction(1).plus_fraction(2),
+            // fun size -> (1350. + (8.25 * size))
+            sha3: u32 = 1350,
+            sha3_per_byte: SizeCost = SizeCost::per_unit(8).plus_fraction(2),
+            // fun size -> (680. + (3. * size))
+            sha512: u32 = 680,
+            sha512_per_byte: SizeCost = SizeCost::per_unit(3),
+            pairing_check: u32 = 450_000,
+            pairing_check_per_elt: SizeCost = SizeCost::per_unit(342_500),
+            // 265. + 1.0625 * size
+            mul_bls_fr_big_int: u32 = 265,
+            mul_bls_fr_big_int_per_byte: SizeCost = SizeCost::per_unit(1).plus_fraction(4),
+            neg_int: u32 = 25,
+            neg_int_per_byte: SizeCost = SizeCost::fraction(1),
+            abs: u32 = 20,
+            abs_per_byte: SizeCost = SizeCost::fraction(1),
+            int_bytes: u32 = 20,
+            int_bytes_per_byte: SizeCost = SizeCost::per_unit(2).plus_fraction(1),
+            nat_bytes: u32 = 45,
+            nat_bytes_per_byte: SizeCost = SizeCost::per_unit(2).plus_fraction(1),
+            bytes_int: u32 = 90,
+            bytes_int_per_byte: SizeCost = SizeCost::per_unit(3),
+            bytes_nat: u32 = 75,
+            bytes_nat_per_byte: SizeCost = SizeCost::per_unit(3),
+            unpack: u32 = 260,
+            unpack_per_byte: SizeCost = SizeCost::fraction(1),
+            pair_n: u32 = 40,
+            pair_n_per_elt: SizeCost = SizeCost::per_unit(3).plus_fraction(2),
+            unpair_n: u32 = 30,
+            unpair_n_per_elt: SizeCost = SizeCost::per_unit(4),
+            get_n: u32 = 20,
+            get_n_per_elt: SizeCost = SizeCost::fraction(1).plus_fraction(4),
+            update_n: u32 = 30,
+            update_n_per_elt: SizeCost = SizeCost::per_unit(1).plus_fraction(2).plus_fraction(5),
+        }
     }
 
-    pub fn split_ticket(amount1: &BigUint, amount2: &BigUint) -> Result<u32, OutOfGas> {
+    /// Base cost `base` and `per_unit` cost of size `n`.
+    fn linear(base: u32, per_unit: &SizeCost, n: Checked<u64>) -> Result<u32, OutOfGas> {
+        (per_unit.of(n) + base as u64).as_gas_cost()
+    }
+
+    /// Size of a byte or character sequence of length `len`.
+    fn len_size(len: usize) -> Checked<u64> {
+        Checked::from(len as u64)
+    }
+
+    pub fn join_tickets(costs: &GasCosts, t1: &Ticket, t2: &Ticket) -> Result<u32, OutOfGas> {
+        compare(costs, &t1.content, &t2.content)?;
+        add_num(costs, &t1.amount, &t2.amount)
+    }
+
+    pub fn split_ticket(
+        costs: &GasCosts,
+        amount1: &BigUint,
+        amount2: &BigUint,
+    ) -> Result<u32, OutOfGas> {
         use std::mem::size_of_val;
-        let sz = Checked::from(std::cmp::max(size_of_val(amount1), size_of_val(amount2)));
-        (40 + (sz >> 1)).as_gas_cost()
+        let c = &costs.interpret;
+        let sz = len_size(std::cmp::max(size_of_val(amount1), size_of_val(amount2)));
+        linear(c.split_ticket, &c.split_ticket_per_byte, sz)
     }
 
-    fn dropn(n: u16) -> Result<u32, OutOfGas> {
-        // Approximates 30 + 2.713108*n, copied from the Tezos protocol
-        let n = Checked::from(n as u32);
-        (30 + n * 2 + (n >> 1) + (n >> 3)).as_gas_cost()
+    fn dropn(costs: &GasCosts, n: u16) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.drop_n, &c.drop_n_per_elt, Checked::from(n as u64))
     }
 
-    pub fn drop(mb_n: Option<u16>) -> Result<u32, OutOfGas> {
-        mb_n.map_or(Ok(DROP), dropn)
+    pub fn drop(costs: &GasCosts, mb_n: Option<u16>) -> Result<u32, OutOfGas> {
+        mb_n.map_or(Ok(costs.interpret.drop), |n| dropn(costs, n))
     }
 
-    fn dipn(n: u16) -> Result<u32, OutOfGas> {
-        // Approximates 15 + 4.05787663635*n, copied from the Tezos protocol
-        let n = Checked::from(n as u32);
-        (15 + n * 4).as_gas_cost()
+    fn dipn(costs: &GasCosts, n: u16) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.dip_n, &c.dip_n_per_elt, Checked::from(n as u64))
     }
 
-    pub fn dip(mb_n: Option<u16>) -> Result<u32, OutOfGas> {
-        mb_n.map_or(Ok(DIP), dipn)
+    pub fn dip(costs: &GasCosts, mb_n: Option<u16>) -> Result<u32, OutOfGas> {
+        mb_n.map_or(Ok(costs.interpret.dip), |n| dipn(costs, n))
     }
 
-    pub fn undip(n: u16) -> Result<u32, OutOfGas> {
-        // this is derived by observing gas costs as of Nairobi, as charged by
-        // the Tezos protocol. It seems undip cost is charged as
-        // cost_N_KUndip * n + cost_N_KCons,
-        // where cost_N_KUndip = cost_N_KCons = 10
-        let n = Checked::from(n as u32);
-        ((n + 1) * 10).as_gas_cost()
+    pub fn undip(costs: &GasCosts, n: u16) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.undip, &c.undip_per_elt, Checked::from(n as u64))
     }
 
-    fn dupn(n: u16) -> Result<u32, OutOfGas> {
-        // Approximates 20 + 1.222263*n, copied from the Tezos protocol
-        let n = Checked::from(n as u32);
-        (20 + n + (n >> 2)).as_gas_cost()
+    fn dupn(costs: &GasCosts, n: u16) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.dup_n, &c.dup_n_per_elt, Checked::from(n as u64))
     }
 
-    pub fn dig(n: u16) -> Result<u32, OutOfGas> {
-        let n = Checked::from(n as u32);
-        (30 + 6 * n + (n >> 1) + (n >> 2)).as_gas_cost()
+    pub fn dig(costs: &GasCosts, n: u16) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.dig, &c.dig_per_elt, Checked::from(n as u64))
     }
 
-    pub fn dug(n: u16) -> Result<u32, OutOfGas> {
-        let n = Checked::from(n as u32);
-        (35 + 6 * n + (n >> 1) + (n >> 2)).as_gas_cost()
+    pub fn dug(costs: &GasCosts, n: u16) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.dug, &c.dug_per_elt, Checked::from(n as u64))
     }
 
-    pub fn dup(mb_n: Option<u16>) -> Result<u32, OutOfGas> {
-        mb_n.map_or(Ok(DUP), dupn)
+    pub fn dup(costs: &GasCosts, mb_n: Option<u16>) -> Result<u32, OutOfGas> {
+        mb_n.map_or(Ok(costs.interpret.dup), |n| dupn(costs, n))
     }
 
-    pub fn add_num(i1: &impl BigIntByteSize, i2: &impl BigIntByteSize) -> Result<u32, OutOfGas> {
+    pub fn add_num(
+        costs: &GasCosts,
+        i1: &impl BigIntByteSize,
+        i2: &impl BigIntByteSize,
+    ) -> Result<u32, OutOfGas> {
         // max is copied from the Tezos protocol, ostensibly adding two big ints depends on
         // the larger of the two due to result allocation
+        let c = &costs.interpret;
         let sz = Checked::from(std::cmp::max(i1.byte_size(), i2.byte_size()));
-        (35 + (sz >> 1)).as_gas_cost()
+        linear(c.add_num, &c.add_num_per_byte, sz)
+    }
+
+    pub fn sub_num(
+        costs: &GasCosts,
+        i1: &impl BigIntByteSize,
+        i2: &impl BigIntByteSize,
+    ) -> Result<u32, OutOfGas> {
+        // copied from the Tezos protocol, subtraction is charged the same as
+        // addition
+        add_num(costs, i1, i2)
+    }
+
+    pub fn ediv_num(
+        costs: &GasCosts,
+        i1: &impl BigIntByteSize,
+        i2: &impl BigIntByteSize,
+    ) -> Result<u32, OutOfGas> {
+        // copied from the Tezos protocol, the cost depends on the size of the
+        // quotient, approximated by the difference of the operand sizes
+        let c = &costs.interpret;
+        let (sz1, sz2) = (i1.byte_size(), i2.byte_size());
+        let q = Checked::from(sz1.saturating_sub(sz2));
+        let v0 = q * Checked::from(sz2);
+        linear(c.ediv_num, &c.ediv_num_per_unit, v0)
     }
 
     /// Cost for `AND` on numbers and bytearrays
-    pub fn and_num(i1: &impl BigIntByteSize, i2: &impl BigIntByteSize) -> Result<u32, OutOfGas> {
+    pub fn and_num(
+        costs: &GasCosts,
+        i1: &impl BigIntByteSize,
+        i2: &impl BigIntByteSize,
+    ) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
         let sz = Checked::from(Ord::min(i1.byte_size(), i2.byte_size()));
-        (35 + (sz >> 1)).as_gas_cost()
+        linear(c.and_num, &c.and_num_per_byte, sz)
     }
 
-    pub fn and_bytes(b1: &[u8], b2: &[u8]) -> Result<u32, OutOfGas> {
-        let sz = Checked::from(Ord::min(b1.len(), b2.len()));
-        (35 + (sz >> 1)).as_gas_cost()
+    pub fn and_bytes(costs: &GasCosts, b1: &[u8], b2: &[u8]) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        let sz = len_size(Ord::min(b1.len(), b2.len()));
+        linear(c.and_bytes, &c.and_bytes_per_byte, sz)
     }
 
-    pub fn or_num(i1: &impl BigIntByteSize, i2: &impl BigIntByteSize) -> Result<u32, OutOfGas> {
+    pub fn or_num(
+        costs: &GasCosts,
+        i1: &impl BigIntByteSize,
+        i2: &impl BigIntByteSize,
+    ) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
         let sz = Checked::from(Ord::min(i1.byte_size(), i2.byte_size()));
-        (35 + (sz >> 1)).as_gas_cost()
+        linear(c.or_num, &c.or_num_per_byte, sz)
     }
 
-    pub fn or_bytes(b1: &[u8], b2: &[u8]) -> Result<u32, OutOfGas> {
+    pub fn or_bytes(costs: &GasCosts, b1: &[u8], b2: &[u8]) -> Result<u32, OutOfGas> {
         // NB: Tezos takes maximum of the sizes, but in our implementation only
         // touches bytes in two vectors intersection. So taking the same formula
         // as in [and_bytes].
-        let sz = Checked::from(Ord::min(b1.len(), b2.len()));
-        (35 + (sz >> 1)).as_gas_cost()
+        let c = &costs.interpret;
+        let sz = len_size(Ord::min(b1.len(), b2.len()));
+        linear(c.or_bytes, &c.or_bytes_per_byte, sz)
     }
 
-    pub fn xor_nat(i1: &BigUint, i2: &BigUint) -> Result<u32, OutOfGas> {
+    pub fn xor_nat(costs: &GasCosts, i1: &BigUint, i2: &BigUint) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
         let sz = Checked::from(Ord::min(i1.byte_size(), i2.byte_size()));
-        (35 + (sz >> 1)).as_gas_cost()
+        linear(c.xor_nat, &c.xor_nat_per_byte, sz)
     }
 
-    pub fn xor_bytes(b1: &[u8], b2: &[u8]) -> Result<u32, OutOfGas> {
-        let sz = Checked::from(Ord::min(b1.len(), b2.len()));
-        (40 + (sz >> 1)).as_gas_cost()
+    pub fn xor_bytes(costs: &GasCosts, b1: &[u8], b2: &[u8]) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        let sz = len_size(Ord::min(b1.len(), b2.len()));
+        linear(c.xor_bytes, &c.xor_bytes_per_byte, sz)
     }
 
-    pub fn not_num<T: BigIntByteSize>(n: &T) -> Result<u32, OutOfGas> {
+    pub fn not_num<T: BigIntByteSize>(costs: &GasCosts, n: &T) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
         let sz = Checked::from(n.byte_size());
-        (25 + (sz >> 1)).as_gas_cost()
+        linear(c.not_num, &c.not_num_per_byte, sz)
+    }
+
+    pub fn lsl_nat(costs: &GasCosts, x: &impl BigIntByteSize, shift: u16) -> Result<u32, OutOfGas> {
+        // proportional to the size of the result
+        let c = &costs.interpret;
+        let sz = Checked::from(x.byte_size()) + u64::from(shift / 8);
+        linear(c.lsl_nat, &c.lsl_nat_per_byte, sz)
+    }
+
+    pub fn lsr_nat(costs: &GasCosts, x: &impl BigIntByteSize) -> Result<u32, OutOfGas> {
+        // the result is never larger than the argument
+        let c = &costs.interpret;
+        let sz = Checked::from(x.byte_size());
+        linear(c.lsr_nat, &c.lsr_nat_per_byte, sz)
     }
 
-    pub fn not_bytes(b: &Vec<u8>) -> Result<u32, OutOfGas> {
-        let sz = Checked::from(b.len());
-        (30 + (sz >> 1)).as_gas_cost()
+    pub fn not_bytes(costs: &GasCosts, b: &Vec<u8>) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        let sz = len_size(b.len());
+        linear(c.not_bytes, &c.not_bytes_per_byte, sz)
     }
 
-    pub fn mul_int(i1: &impl BigIntByteSize, i2: &impl BigIntByteSize) -> Result<u32, OutOfGas> {
+    pub fn mul_int(
+        costs: &GasCosts,
+        i1: &impl BigIntByteSize,
+        i2: &impl BigIntByteSize,
+    ) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
         let a = Checked::from(i1.byte_size()) + Checked::from(i2.byte_size());
         // log2 is ill-defined for zero, hence this check
         let v0 = if a.is_zero() {
@@ -506,26 +993,23 @@ pub mod interpret_cost {
         } else {
             a * (a.ok_or(OutOfGas)?.log2i() as u64)
         };
-        (55 + (v0 >> 1) + (v0 >> 2) + (v0 >> 4)).as_gas_cost()
+        linear(c.mul_int, &c.mul_int_per_unit, v0)
     }
 
-    pub fn compare(v1: &TypedValue, v2: &TypedValue) -> Result<u32, OutOfGas> {
+    pub fn compare(costs: &GasCosts, v1: &TypedValue, v2: &TypedValue) -> Result<u32, OutOfGas> {
         use TypedValue as V;
+        let c = &costs.interpret;
         let cmp_bytes = |s1: u64, s2: u64| {
-            // Approximating 35 + 0.024413 x term
             let v = Checked::from(std::cmp::min(s1, s2));
-            (35 + (v >> 6) + (v >> 7)).as_gas_cost()
+            linear(c.compare_bytes, &c.compare_bytes_per_byte, v)
         };
         let cmp_pair = |l: &(_, _), r: &(_, _)| {
-            let c = Checked::from(10u32);
-            (c + compare(&l.0, &r.0)? + compare(&l.1, &r.1)?).as_gas_cost()
+            let cmp = Checked::from(c.compare_pair);
+            (cmp + compare(costs, &l.0, &r.0)? + compare(costs, &l.1, &r.1)?).as_gas_cost()
         };
-        let cmp_option = Checked::from(10u32);
+        let cmp_option = Checked::from(c.compare_option);
         const ADDRESS_SIZE: u64 = 20 + 31; // hash size + max entrypoint size
-        const CMP_CHAIN_ID: u32 = 30;
-        const CMP_KEY: u32 = 92; // hard-coded in the protocol
-        const CMP_SIGNATURE: u32 = 92; // hard-coded in the protocol
-        let cmp_or = Checked::from(10u32);
+        let cmp_or = Checked::from(c.compare_or);
         #[track_caller]
         fn incomparable() -> ! {
             unreachable!("Comparison of incomparable values")
@@ -549,7 +1033,7 @@ pub mod interpret_cost {
             (V::String(l), V::String(r)) => cmp_bytes(l.len() as u64, r.len() as u64)?,
             (V::String(_), _) => incomparable(),
 
-            (V::Unit, V::Unit) => 10,
+            (V::Unit, V::Unit) => c.compare_unit,
             (V::Unit, _) => incomparable(),
 
             (V::Pair(l), V::Pair(r)) => cmp_pair(l.as_ref(), r.as_ref())?,
@@ -559,7 +1043,7 @@ pub mod interpret_cost {
                 (None, None) => cmp_option,
                 (None, Some(_)) => cmp_option,
                 (Some(_), None) => cmp_option,
-                (Some(l), Some(r)) => cmp_option + compare(l, r)?,
+                (Some(l), Some(r)) => cmp_option + compare(costs, l, r)?,
             }
             .as_gas_cost()?,
             (V::Option(_), _) => incomparable(),
@@ -567,16 +1051,16 @@ pub mod interpret_cost {
             (V::Address(..), V::Address(..)) => cmp_bytes(ADDRESS_SIZE, ADDRESS_SIZE)?,
             (V::Address(_), _) => incomparable(),
 
-            (V::ChainId(..), V::ChainId(..)) => CMP_CHAIN_ID,
+            (V::ChainId(..), V::ChainId(..)) => c.compare_chain_id,
             (V::ChainId(_), _) => incomparable(),
 
             (V::Bytes(l), V::Bytes(r)) => cmp_bytes(l.len() as u64, r.len() as u64)?,
             (V::Bytes(_), _) => incomparable(),
 
-            (V::Key(_), V::Key(_)) => CMP_KEY,
+            (V::Key(_), V::Key(_)) => c.compare_key,
             (V::Key(_), _) => incomparable(),
 
-            (V::Signature(_), V::Signature(_)) => CMP_SIGNATURE,
+            (V::Signature(_), V::Signature(_)) => c.compare_signature,
             (V::Signature(_), _) => incomparable(),
 
             (V::KeyHash(_), V::KeyHash(_)) => {
@@ -585,8 +1069,8 @@ pub mod interpret_cost {
             (V::KeyHash(_), _) => incomparable(),
 
             (V::Or(l), V::Or(r)) => match (l.as_ref(), r.as_ref()) {
-                (Or::Left(x), Or::Left(y)) => cmp_or + compare(x, y)?,
-                (Or::Right(x), Or::Right(y)) => cmp_or + compare(x, y)?,
+                (Or::Left(x), Or::Left(y)) => cmp_or + compare(costs, x, y)?,
+                (Or::Right(x), Or::Right(y)) => cmp_or + compare(costs, x, y)?,
                 (Or::Left(_), Or::Right(_)) => cmp_or,
                 (Or::Right(_), Or::Left(_)) => cmp_or,
             }
@@ -612,35 +1096,47 @@ pub mod interpret_cost {
 
     /// Cost charged for computing total entries size (needed for the subsequent
     /// gas calculation, so this is meta-gas).
-    pub fn concat_list_precheck(list_size: usize) -> Result<u32, OutOfGas> {
-        (10 * Checked::from(list_size)).as_gas_cost()
+    pub fn concat_list_precheck(costs: &GasCosts, list_size: usize) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(0, &c.concat_list_precheck_per_elt, len_size(list_size))
     }
 
-    pub fn concat_string_list(total_len: Checked<usize>) -> Result<u32, OutOfGas> {
+    pub fn concat_string_list(
+        costs: &GasCosts,
+        total_len: Checked<usize>,
+    ) -> Result<u32, OutOfGas> {
         // Copied from the Tezos protocol
-        (total_len / 2 + 100).as_gas_cost()
+        let c = &costs.interpret;
+        let sz = Checked::from(total_len.map(|len| len as u64));
+        linear(c.concat_string_list, &c.concat_string_list_per_byte, sz)
     }
 
-    pub fn concat_bytes_list(total_len: Checked<usize>) -> Result<u32, OutOfGas> {
+    pub fn concat_bytes_list(costs: &GasCosts, total_len: Checked<usize>) -> Result<u32, OutOfGas> {
         // Copied from the Tezos protocol
-        (total_len / 2 + 100).as_gas_cost()
+        let c = &costs.interpret;
+        let sz = Checked::from(total_len.map(|len| len as u64));
+        linear(c.concat_bytes_list, &c.concat_bytes_list_per_byte, sz)
     }
 
-    pub fn concat_string_pair(len1: usize, len2: usize) -> Result<u32, OutOfGas> {
+    pub fn concat_string_pair(costs: &GasCosts, len1: usize, len2: usize) -> Result<u32, OutOfGas> {
         // Copied from the Tezos protocol
-        ((Checked::from(len1) + Checked::from(len2)) / 2 + 45).as_gas_cost()
+        let c = &costs.interpret;
+        let sz = len_size(len1) + len_size(len2);
+        linear(c.concat_string_pair, &c.concat_string_pair_per_byte, sz)
     }
 
-    pub fn concat_bytes_pair(len1: usize, len2: usize) -> Result<u32, OutOfGas> {
+    pub fn concat_bytes_pair(costs: &GasCosts, len1: usize, len2: usize) -> Result<u32, OutOfGas> {
         // Copied from the Tezos protocol
-        ((Checked::from(len1) + Checked::from(len2)) / 2 + 45).as_gas_cost()
+        let c = &costs.interpret;
+        let sz = len_size(len1) + len_size(len2);
+        linear(c.concat_bytes_pair, &c.concat_bytes_pair_per_byte, sz)
     }
 
-    pub fn map_mem(k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
-        map_get(k, map_size)
+    pub fn map_mem(costs: &GasCosts, k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
+        map_get(costs, k, map_size)
     }
 
-    pub fn map_get(k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
+    pub fn map_get(costs: &GasCosts, k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
         // NB: this doesn't copy the tezos model exactly; tezos model uses
         //
         // 80 + sizeof(key)*log2(map.size)
@@ -653,44 +1149,48 @@ pub mod interpret_cost {
         // "+ 1" is from the observation that a lookup in a map of size 1 does
         // exactly one comparison.
         let map_size = Checked::from(map_size);
-        let compare_cost = compare(k, k)?;
+        let compare_cost = compare(costs, k, k)?;
         let size_log = (map_size + 1).ok_or(OutOfGas)?.log2i();
         let lookup_cost = Checked::from(compare_cost) * size_log;
-        (80 + lookup_cost).as_gas_cost()
+        (costs.interpret.map_get + lookup_cost).as_gas_cost()
     }
 
-    pub fn set_mem(k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
+    pub fn set_mem(costs: &GasCosts, k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
         // NB: same considerations as for map_get
-        let compare_cost = compare(k, k)?;
+        let compare_cost = compare(costs, k, k)?;
         let size_log = (Checked::from(map_size) + 1).ok_or(OutOfGas)?.log2i();
         let lookup_cost = Checked::from(compare_cost) * size_log;
-        (115 + lookup_cost).as_gas_cost()
+        (costs.interpret.set_mem + lookup_cost).as_gas_cost()
     }
 
-    pub fn map_update(k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
+    pub fn map_update(costs: &GasCosts, k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
         // NB: same considerations as for map_get
         let map_size = Checked::from(map_size);
-        let compare_cost = compare(k, k)?;
+        let compare_cost = compare(costs, k, k)?;
         let size_log = (map_size + 1).ok_or(OutOfGas)?.log2i();
         let lookup_cost = Checked::from(compare_cost) * size_log;
         // NB: 2 factor copied from Tezos protocol, in principle it should
         // reflect update vs get overhead.
-        (80 + 2 * lookup_cost).as_gas_cost()
+        (costs.interpret.map_update + 2 * lookup_cost).as_gas_cost()
     }
 
-    pub fn set_update(k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
+    pub fn set_update(costs: &GasCosts, k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
         // NB: same considerations as for map_update
-        let compare_cost = compare(k, k)?;
+        let compare_cost = compare(costs, k, k)?;
         let size_log = (Checked::from(map_size) + 1).ok_or(OutOfGas)?.log2i();
         let lookup_cost = Checked::from(compare_cost) * size_log;
         // coefficient larger than in case of Map looks suspicious, something
         // to benchmark later
-        (130 + 2 * lookup_cost).as_gas_cost()
+        (costs.interpret.set_update + 2 * lookup_cost).as_gas_cost()
     }
 
-    pub fn map_get_and_update(k: &TypedValue, map_size: usize) -> Result<u32, OutOfGas> {
+    pub fn map_get_and_update(
+        costs: &GasCosts,
+        k: &TypedValue,
+        map_size: usize,
+    ) -> Result<u32, OutOfGas> {
         // NB: same considerations as for map_get
-        let compare_cost = compare(k, k)?;
+        let compare_cost = compare(costs, k, k)?;
         let size_log = (Checked::from(map_size) + 1).ok_or(OutOfGas)?.log2i();
         let lookup_cost = Checked::from(compare_cost) * size_log;
         // NB: 3 factor copied from Tezos protocol, in principle it should
@@ -701,9 +1201,8 @@ pub mod interpret_cost {
         // However, note that this function is also reused for big_map version
         // of GET_AND_UPDATE, wherein it's more justified. That is to say, take
         // care when updating this.
-        (80 + 3 * lookup_cost).as_gas_cost()
+        (costs.interpret.map_get_and_update + 3 * lookup_cost).as_gas_cost()
     }
-
     /// Measures size of Michelson using several metrics.
     pub struct MichelineSize {
         /// Total number of nodes (including leaves).
@@ -726,14 +1225,21 @@ pub mod interpret_cost {
         }
     }
 
-    pub fn micheline_encoding<'a>(mich: &'a Micheline<'a>) -> Result<u32, OutOfGas> {
+    pub fn micheline_encoding<'a>(
+        costs: &GasCosts,
+        mich: &'a Micheline<'a>,
+    ) -> Result<u32, OutOfGas> {
         let mut size = MichelineSize::default();
         collect_micheline_size(mich, &mut size);
-        micheline_encoding_by_size(size)
+        micheline_encoding_by_size(costs, size)
     }
 
-    fn micheline_encoding_by_size(size: MichelineSize) -> Result<u32, OutOfGas> {
-        (size.nodes_num * 100 + size.zariths * 25 + size.str_byte * 10).as_gas_cost()
+    fn micheline_encoding_by_size(costs: &GasCosts, size: MichelineSize) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        (c.micheline_encoding_per_node.of(size.nodes_num)
+            + c.micheline_encoding_per_zarith_byte.of(size.zariths)
+            + c.micheline_encoding_per_str_byte.of(size.str_byte))
+        .as_gas_cost()
     }
 
     fn collect_micheline_size<'a>(mich: &'a Micheline<'a>, size: &mut MichelineSize) {
@@ -766,127 +1272,143 @@ pub mod interpret_cost {
         }
     }
 
-    pub fn check_signature(k: &Key, msg: &[u8]) -> Result<u32, OutOfGas> {
-        let len = Checked::from(msg.len());
+    pub fn check_signature(costs: &GasCosts, k: &Key, msg: &[u8]) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        let len = len_size(msg.len());
         match k {
-            Key::Ed25519(..) => 65800 + ((len >> 3) + len),
-            Key::Secp256k1(..) => 51600 + ((len >> 3) + len),
-            Key::P256(..) => 341000 + ((len >> 3) + len),
-            Key::Bls(..) => 1570000 + (len * 3),
+            Key::Ed25519(..) => linear(
+                c.check_signature_ed25519,
+                &c.check_signature_ed25519_per_byte,
+                len,
+            ),
+            Key::Secp256k1(..) => linear(
+                c.check_signature_secp256k1,
+                &c.check_signature_secp256k1_per_byte,
+                len,
+            ),
+            Key::P256(..) => linear(
+                c.check_signature_p256,
+                &c.check_signature_p256_per_byte,
+                len,
+            ),
+            Key::Bls(..) => linear(c.check_signature_bls, &c.check_signature_bls_per_byte, len),
         }
-        .as_gas_cost()
     }
 
-    pub fn slice(length: usize) -> Result<u32, OutOfGas> {
+    pub fn slice(costs: &GasCosts, length: usize) -> Result<u32, OutOfGas> {
         // In the protocol, the gas costs for slicing strings and bytes are defined
         // separately (see `cost_N_ISlice_bytes` and `cost_N_ISlice_string`).
         //
         // In practice, they both have the same cost.
-        ((Checked::from(length) >> 1) + 25).as_gas_cost()
+        let c = &costs.interpret;
+        linear(c.slice, &c.slice_per_byte, len_size(length))
     }
 
-    pub fn blake2b(msg: &[u8]) -> Result<u32, OutOfGas> {
-        /* fun size -> (430. + (1.125 * size)) */
-        let size = Checked::from(msg.len());
-        (Checked::from(430) + ((size >> 3) + size)).as_gas_cost()
+    pub fn blake2b(costs: &GasCosts, msg: &[u8]) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.blake2b, &c.blake2b_per_byte, len_size(msg.len()))
     }
 
-    pub fn keccak(msg: &[u8]) -> Result<u32, OutOfGas> {
-        /* fun size -> (1350. + (8.25 * size)) */
-        let size = Checked::from(msg.len());
-        (Checked::from(1350) + ((size >> 2) + (size * 8))).as_gas_cost()
+    pub fn keccak(costs: &GasCosts, msg: &[u8]) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.keccak, &c.keccak_per_byte, len_size(msg.len()))
     }
 
-    pub fn sha256(msg: &[u8]) -> Result<u32, OutOfGas> {
-        /* fun size -> (600. + (4.75 * size)) */
-        let size = Checked::from(msg.len());
-        (Checked::from(600) + ((size >> 2) + ((size >> 1) + (size * 4)))).as_gas_cost()
+    pub fn sha256(costs: &GasCosts, msg: &[u8]) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.sha256, &c.sha256_per_byte, len_size(msg.len()))
     }
 
-    pub fn sha3(msg: &[u8]) -> Result<u32, OutOfGas> {
-        /* fun size -> (1350. + (8.25 * size)) */
-        let size = Checked::from(msg.len());
-        (Checked::from(1350) + ((size >> 2) + (size * 8))).as_gas_cost()
+    pub fn sha3(costs: &GasCosts, msg: &[u8]) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.sha3, &c.sha3_per_byte, len_size(msg.len()))
     }
 
-    pub fn sha512(msg: &[u8]) -> Result<u32, OutOfGas> {
-        /* fun size -> (680. + (3. * size)) */
-        let size = Checked::from(msg.len());
-        (Checked::from(680) + (size * 3)).as_gas_cost()
+    pub fn sha512(costs: &GasCosts, msg: &[u8]) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.sha512, &c.sha512_per_byte, len_size(msg.len()))
     }
 
-    pub fn pairing_check(size: usize) -> Result<u32, OutOfGas> {
-        (450_000 + 342_500 * Checked::from(size)).as_gas_cost()
+    pub fn pairing_check(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.pairing_check, &c.pairing_check_per_elt, len_size(size))
     }
 
-    pub fn mul_bls_fr_big_int(int: &impl BigIntByteSize) -> Result<u32, OutOfGas> {
-        // 265. + 1.0625 * size
+    pub fn mul_bls_fr_big_int(
+        costs: &GasCosts,
+        int: &impl BigIntByteSize,
+    ) -> Result<u32, OutOfGas> {
         // NB: cost_N_IMul_bls12_381_fr_z and
         // cost_N_IMul_bls12_381_z_fr ar distinct in the protocol, but they're the
         // same exact operation, so we opted to use one function.
+        let c = &costs.interpret;
         let size = Checked::from(int.byte_size());
-        (265 + ((size >> 4) + size)).as_gas_cost()
+        linear(c.mul_bls_fr_big_int, &c.mul_bls_fr_big_int_per_byte, size)
     }
 
-    pub fn neg_int(int: &impl BigIntByteSize) -> Result<u32, OutOfGas> {
+    pub fn neg_int(costs: &GasCosts, int: &impl BigIntByteSize) -> Result<u32, OutOfGas> {
         // NB: taken from the protocol, this doesn't fit with MIR implementation.
+        let c = &costs.interpret;
         let size = Checked::from(int.byte_size());
-        (25 + (size >> 1)).as_gas_cost()
+        linear(c.neg_int, &c.neg_int_per_byte, size)
     }
 
-    pub fn abs(int: &impl BigIntByteSize) -> Result<u32, OutOfGas> {
+    pub fn abs(costs: &GasCosts, int: &impl BigIntByteSize) -> Result<u32, OutOfGas> {
         // NB: MIR implementation is constant-time and alloc free so could have
         // a constant gas cost but for consistency with the protocol we use the
         // same linear model.
+        let c = &costs.interpret;
         let size = Checked::from(int.byte_size());
-        (20 + (size >> 1)).as_gas_cost()
+        linear(c.abs, &c.abs_per_byte, size)
     }
 
-    pub fn int_bytes(size: usize) -> Result<u32, OutOfGas> {
-        let size = Checked::from(size);
-        (20 + ((size >> 1) + (size * 2))).as_gas_cost()
+    pub fn int_bytes(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.int_bytes, &c.int_bytes_per_byte, len_size(size))
     }
 
-    pub fn nat_bytes(size: usize) -> Result<u32, OutOfGas> {
-        let size = Checked::from(size);
-        (45 + ((size >> 1) + (size * 2))).as_gas_cost()
+    pub fn nat_bytes(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.nat_bytes, &c.nat_bytes_per_byte, len_size(size))
     }
 
-    pub fn bytes_int(int: &BigInt) -> Result<u32, OutOfGas> {
+    pub fn bytes_int(costs: &GasCosts, int: &BigInt) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
         let size = Checked::from(int.byte_size());
-        (90 + (size * 3)).as_gas_cost()
+        linear(c.bytes_int, &c.bytes_int_per_byte, size)
     }
 
-    pub fn bytes_nat(int: &BigUint) -> Result<u32, OutOfGas> {
+    pub fn bytes_nat(costs: &GasCosts, int: &BigUint) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
         let size = Checked::from(int.byte_size());
-        (75 + (size * 3)).as_gas_cost()
+        linear(c.bytes_nat, &c.bytes_nat_per_byte, size)
     }
 
-    pub fn unpack(bytes: &[u8]) -> Result<u32, OutOfGas> {
-        let size = Checked::from(bytes.len());
-        (260 + (size >> 1)).as_gas_cost()
+    pub fn unpack(costs: &GasCosts, bytes: &[u8]) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.unpack, &c.unpack_per_byte, len_size(bytes.len()))
     }
 
-    pub fn pair_n(size: usize) -> Result<u32, OutOfGas> {
-        let size = Checked::from(size);
-        let v0 = size - 2;
-        (40 + ((v0 >> 2) + (v0 * 3))).as_gas_cost()
+    pub fn pair_n(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        let v0 = len_size(size) - 2;
+        linear(c.pair_n, &c.pair_n_per_elt, v0)
     }
 
-    pub fn unpair_n(size: usize) -> Result<u32, OutOfGas> {
-        let size = Checked::from(size);
-        let v0 = size - 2;
-        (30 + (v0 * 4)).as_gas_cost()
+    pub fn unpair_n(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        let v0 = len_size(size) - 2;
+        linear(c.unpair_n, &c.unpair_n_per_elt, v0)
     }
 
-    pub fn get_n(size: usize) -> Result<u32, OutOfGas> {
-        let size = Checked::from(size);
-        (20 + ((size >> 1) + (size >> 4))).as_gas_cost()
+    pub fn get_n(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.get_n, &c.get_n_per_elt, len_size(size))
     }
 
-    pub fn update_n(size: usize) -> Result<u32, OutOfGas> {
-        let size = Checked::from(size);
-        (30 + ((size >> 5) + ((size >> 2) + size))).as_gas_cost()
+    pub fn update_n(costs: &GasCosts, size: usize) -> Result<u32, OutOfGas> {
+        let c = &costs.interpret;
+        linear(c.update_n, &c.update_n_per_elt, len_size(size))
     }
 }
 
@@ -901,6 +1423,39 @@ mod test {
         assert_eq!(gas.milligas(), 70)
     }
 
+    #[test]
+    fn gas_refund() {
+        let mut gas = Gas::new(100);
+        gas.consume(30).unwrap();
+        gas.refund(20);
+        assert_eq!(gas.remaining_milligas(), 90);
+        gas.refund(20);
+        assert_eq!(gas.remaining_milligas(), 100);
+        gas.consume(30).unwrap();
+        gas.refund(u32::MAX);
+        assert_eq!(gas.remaining_milligas(), 100);
+    }
+
+    #[test]
+    fn remaining_after_exhaustion() {
+        let mut gas = Gas::new(100);
+        assert_eq!(gas.consume(1000), Err(OutOfGas));
+        assert_eq!(gas.remaining_milligas(), 0);
+    }
+
+    #[test]
+    fn size_cost() {
+        let cost = SizeCost::per_unit(2).plus_fraction(1).plus_fraction(3);
+        for n in [0u64, 1, 2, 7, 8, 100] {
+            assert_eq!(
+                cost.of(Checked::from(n)),
+                Checked::from(2 * n + (n >> 1) + (n >> 3))
+            );
+        }
+        assert_eq!(SizeCost::fraction(0), SizeCost::per_unit(1));
+        assert_eq!(*cost.of(Checked::from(u64::MAX)), None);
+    }
+
     #[test]
     fn gas_exhaustion_error() {
         let mut gas = Gas::new(100);
@@ -919,7 +1474,10 @@ mod test {
     #[test]
     fn overflow_to_out_of_gas() {
         for n in [usize::MAX, usize::MAX / 2, usize::MAX / 4] {
-            assert_eq!(super::tc_cost::ty_eq(n, n), Err(OutOfGas));
+            assert_eq!(
+                super::tc_cost::ty_eq(&GasCosts::default(), n, n),
+                Err(OutOfGas)
+            );
         }
     }
 
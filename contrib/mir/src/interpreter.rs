@@ -19,7 +19,7 @@ use typed_arena::Arena;
 use crate::ast::big_map::{BigMap, LazyStorageError};
 use crate::ast::*;
 use crate::bls;
-use crate::context::Ctx;
+use crate::context::{with_nesting_stack, Ctx};
 use crate::gas::{interpret_cost, OutOfGas};
 use crate::irrefutable_match::irrefutable_match;
 use crate::stack::*;
@@ -34,12 +34,21 @@ pub enum InterpretError<'a> {
     /// When performing mutez arithmetic, an overflow occurred.
     #[error("mutez overflow")]
     MutezOverflow,
+    /// When performing mutez arithmetic, an underflow occurred.
+    #[error("mutez underflow")]
+    MutezUnderflow,
+    /// The shift amount of `LSL` or `LSR` is greater than 256.
+    #[error("shift overflow")]
+    ShiftOverflow,
     /// Interpreter reached a `FAILWITH` instruction.
     #[error("failed with: {1:?} of type {0:?}")]
     FailedWith(Type, TypedValue<'a>),
     /// An error occurred when working with `big_map` storage.
     #[error("lazy storage error: {0}")]
     LazyStorageError(#[from] LazyStorageError),
+    /// Code blocks are nested deeper than [Ctx::max_interpret_depth].
+    #[error("stack overflow: code blocks nested deeper than {0}")]
+    StackOverflow(usize),
 }
 
 /// Errors possible when interpreting a full contract script.
@@ -100,11 +109,14 @@ impl<'a> Instruction<'a> {
     /// # Panics
     ///
     /// When the instruction can't be executed on the provided stack.
+    ///
+    /// Usually, the stack is an [IStack], but any [StackOps] implementation
+    /// can be used.
     pub fn interpret(
         &self,
         ctx: &mut Ctx<'a>,
         arena: &'a Arena<Micheline<'a>>,
-        stack: &mut IStack<'a>,
+        stack: &mut impl StackOps<TypedValue<'a>>,
     ) -> Result<(), InterpretError<'a>> {
         interpret_one(self, ctx, arena, stack)
     }
@@ -114,15 +126,124 @@ fn interpret<'a>(
     ast: &[Instruction<'a>],
     ctx: &mut Ctx<'a>,
     arena: &'a Arena<Micheline<'a>>,
+    stack: &mut impl StackOps<TypedValue<'a>>,
+) -> Result<(), InterpretError<'a>> {
+    if ctx.nesting_depth >= ctx.max_interpret_depth {
+        return Err(InterpretError::StackOverflow(ctx.max_interpret_depth));
+    }
+    ctx.nesting_depth += 1;
+    let res = with_nesting_stack(|| {
+        ast.iter()
+            .try_for_each(|i| i.interpret(ctx, arena, stack))
+            .and_then(|()| Ok(ctx.gas.consume(ctx.gas.costs().interpret.interpret_ret)?))
+    });
+    ctx.nesting_depth -= 1;
+    res
+}
+
+/// Execute a single instruction, see [Instruction::interpret]. Together with
+/// [trace], this is meant for tooling, e.g. debuggers.
+///
+/// # Panics
+///
+/// When the instruction can't be executed on the provided stack.
+pub fn step<'a>(
+    instr: &Instruction<'a>,
+    ctx: &mut Ctx<'a>,
+    arena: &'a Arena<Micheline<'a>>,
     stack: &mut IStack<'a>,
 ) -> Result<(), InterpretError<'a>> {
-    for i in ast {
-        i.interpret(ctx, arena, stack)?;
+    interpret_one(instr, ctx, arena, stack)
+}
+
+/// A single step of a [Trace].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<'a, 'i> {
+    /// The instruction executed.
+    pub instruction: &'i Instruction<'a>,
+    /// The stack after executing the instruction.
+    pub stack: IStack<'a>,
+}
+
+/// Iterator over the steps of interpreting a sequence, see [trace].
+pub struct Trace<'a, 'i, 'c> {
+    instrs: std::slice::Iter<'i, Instruction<'a>>,
+    ctx: &'c mut Ctx<'a>,
+    arena: &'a Arena<Micheline<'a>>,
+    stack: &'c mut IStack<'a>,
+    is_seq: bool,
+    done: bool,
+}
+
+/// Interpret the instruction step by step, yielding the instruction executed
+/// and the stack after each step.
+///
+/// If the instruction is a sequence, its elements are executed one per step;
+/// otherwise, it takes a single step. Instructions with nested code blocks,
+/// like `IF` or `LOOP`, are executed in one step.
+///
+/// If interpretation fails, e.g. runs out of gas, the error is the last item.
+/// Once the iterator is exhausted, `stack` holds the result of interpretation,
+/// same as after [Instruction::interpret].
+///
+/// # Panics
+///
+/// When the instruction can't be executed on the provided stack.
+pub fn trace<'a, 'i, 'c>(
+    instr: &'i Instruction<'a>,
+    ctx: &'c mut Ctx<'a>,
+    arena: &'a Arena<Micheline<'a>>,
+    stack: &'c mut IStack<'a>,
+) -> Trace<'a, 'i, 'c> {
+    let (instrs, is_seq) = match instr {
+        Instruction::Seq(instrs) => (instrs.as_slice(), true),
+        instr => (std::slice::from_ref(instr), false),
+    };
+    Trace {
+        instrs: instrs.iter(),
+        ctx,
+        arena,
+        stack,
+        is_seq,
+        done: false,
+    }
+}
+
+impl<'a, 'i, 'c> Iterator for Trace<'a, 'i, 'c> {
+    type Item = Result<Step<'a, 'i>, InterpretError<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let Some(instruction) = self.instrs.next() else {
+            self.done = true;
+            if !self.is_seq {
+                return None;
+            }
+            // charged at the end of a sequence, same as in `interpret`
+            return self
+                .ctx
+                .gas
+                .consume(self.ctx.gas.costs().interpret.interpret_ret)
+                .err()
+                .map(|err| Err(err.into()));
+        };
+        match step(instruction, self.ctx, self.arena, self.stack) {
+            Ok(()) => Some(Ok(Step {
+                instruction,
+                stack: self.stack.clone(),
+            })),
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
     }
-    ctx.gas.consume(interpret_cost::INTERPRET_RET)?;
-    Ok(())
 }
 
+impl std::iter::FusedIterator for Trace<'_, '_, '_> {}
+
 #[track_caller]
 fn unreachable_state() -> ! {
     // If the typechecking of the program being interpreted was successful and if this is reached
@@ -134,7 +255,7 @@ fn interpret_one<'a>(
     i: &Instruction<'a>,
     ctx: &mut Ctx<'a>,
     arena: &'a Arena<Micheline<'a>>,
-    stack: &mut IStack<'a>,
+    stack: &mut impl StackOps<TypedValue<'a>>,
 ) -> Result<(), InterpretError<'a>> {
     use Instruction as I;
     use TypedValue as V;
@@ -166,54 +287,74 @@ fn interpret_one<'a>(
             overloads::Add::IntInt => {
                 let o1 = pop!(V::Int);
                 let o2 = pop!(V::Int);
-                ctx.gas.consume(interpret_cost::add_num(&o1, &o2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::add_num(ctx.gas.costs(), &o1, &o2)?)?;
                 let sum = o1 + o2;
                 stack.push(V::Int(sum));
             }
             overloads::Add::NatNat => {
                 let o1 = pop!(V::Nat);
                 let o2 = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::add_num(&o1, &o2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::add_num(ctx.gas.costs(), &o1, &o2)?)?;
                 let sum = o1 + o2;
                 stack.push(V::Nat(sum));
             }
             overloads::Add::IntNat => {
                 let o1 = pop!(V::Int);
                 let o2 = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::add_num(&o1, &o2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::add_num(ctx.gas.costs(), &o1, &o2)?)?;
                 let sum = o1 + BigInt::from(o2);
                 stack.push(V::Int(sum));
             }
             overloads::Add::NatInt => {
                 let o1 = pop!(V::Nat);
                 let o2 = pop!(V::Int);
-                ctx.gas.consume(interpret_cost::add_num(&o1, &o2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::add_num(ctx.gas.costs(), &o1, &o2)?)?;
                 let sum = BigInt::from(o1) + o2;
                 stack.push(V::Int(sum));
             }
             overloads::Add::MutezMutez => {
                 let o1 = pop!(V::Mutez);
                 let o2 = pop!(V::Mutez);
-                ctx.gas.consume(interpret_cost::ADD_TEZ)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.add_tez)?;
                 let sum = o1.checked_add(o2).ok_or(InterpretError::MutezOverflow)?;
                 stack.push(V::Mutez(sum));
             }
+            overloads::Add::TimestampInt => {
+                let o1 = pop!(V::Timestamp);
+                let o2 = pop!(V::Int);
+                ctx.gas
+                    .consume(interpret_cost::add_num(ctx.gas.costs(), &o1, &o2)?)?;
+                let sum = o1 + o2;
+                stack.push(V::Timestamp(sum));
+            }
+            overloads::Add::IntTimestamp => {
+                let o1 = pop!(V::Int);
+                let o2 = pop!(V::Timestamp);
+                ctx.gas
+                    .consume(interpret_cost::add_num(ctx.gas.costs(), &o1, &o2)?)?;
+                let sum = o1 + o2;
+                stack.push(V::Timestamp(sum));
+            }
             overloads::Add::Bls12381Fr => {
                 let o1 = pop!(V::Bls12381Fr);
                 let o2 = pop!(V::Bls12381Fr);
-                ctx.gas.consume(interpret_cost::ADD_BLS_FR)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.add_bls_fr)?;
                 stack.push(V::Bls12381Fr(o1 + o2));
             }
             overloads::Add::Bls12381G1 => {
                 let o1 = pop!(V::Bls12381G1);
                 let o2 = pop!(V::Bls12381G1);
-                ctx.gas.consume(interpret_cost::ADD_BLS_G1)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.add_bls_g1)?;
                 stack.push(V::new_bls12381_g1(o1.as_ref() + o2.as_ref()));
             }
             overloads::Add::Bls12381G2 => {
                 let o1 = pop!(V::Bls12381G2);
                 let o2 = pop!(V::Bls12381G2);
-                ctx.gas.consume(interpret_cost::ADD_BLS_G2)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.add_bls_g2)?;
                 stack.push(V::new_bls12381_g2(o1.as_ref() + o2.as_ref()));
             }
         },
@@ -221,73 +362,79 @@ fn interpret_one<'a>(
             overloads::Mul::NatNat => {
                 let x1 = pop!(V::Nat);
                 let x2 = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::mul_int(&x1, &x2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::mul_int(ctx.gas.costs(), &x1, &x2)?)?;
                 let res = x1 * x2;
                 stack.push(V::Nat(res));
             }
             overloads::Mul::NatInt => {
                 let x1 = pop!(V::Nat);
                 let x2 = pop!(V::Int);
-                ctx.gas.consume(interpret_cost::mul_int(&x1, &x2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::mul_int(ctx.gas.costs(), &x1, &x2)?)?;
                 let res = BigInt::from(x1) * x2;
                 stack.push(V::Int(res));
             }
             overloads::Mul::IntNat => {
                 let x1 = pop!(V::Int);
                 let x2 = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::mul_int(&x1, &x2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::mul_int(ctx.gas.costs(), &x1, &x2)?)?;
                 let res = x1 * BigInt::from(x2);
                 stack.push(V::Int(res));
             }
             overloads::Mul::IntInt => {
                 let x1 = pop!(V::Int);
                 let x2 = pop!(V::Int);
-                ctx.gas.consume(interpret_cost::mul_int(&x1, &x2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::mul_int(ctx.gas.costs(), &x1, &x2)?)?;
                 let res = x1 * x2;
                 stack.push(V::Int(res));
             }
             overloads::Mul::MutezNat => {
-                ctx.gas.consume(interpret_cost::MUL_TEZ_NAT)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.mul_tez_nat)?;
                 let x1 = pop!(V::Mutez);
                 let x2 = i64::try_from(pop!(V::Nat)).map_err(|_| InterpretError::MutezOverflow)?;
                 let res = x1.checked_mul(x2).ok_or(InterpretError::MutezOverflow)?;
                 stack.push(V::Mutez(res));
             }
             overloads::Mul::NatMutez => {
-                ctx.gas.consume(interpret_cost::MUL_NAT_TEZ)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.mul_nat_tez)?;
                 let x1 = i64::try_from(pop!(V::Nat)).map_err(|_| InterpretError::MutezOverflow)?;
                 let x2 = pop!(V::Mutez);
                 let res = x1.checked_mul(x2).ok_or(InterpretError::MutezOverflow)?;
                 stack.push(V::Mutez(res));
             }
             overloads::Mul::Bls12381G1Bls12381Fr => {
-                ctx.gas.consume(interpret_cost::MUL_BLS_G1)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.mul_bls_g1)?;
                 let x1 = pop!(V::Bls12381G1);
                 let x2 = pop!(V::Bls12381Fr);
                 stack.push(V::new_bls12381_g1(x1.as_ref() * x2));
             }
             overloads::Mul::Bls12381G2Bls12381Fr => {
-                ctx.gas.consume(interpret_cost::MUL_BLS_G2)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.mul_bls_g2)?;
                 let x1 = pop!(V::Bls12381G2);
                 let x2 = pop!(V::Bls12381Fr);
                 stack.push(V::new_bls12381_g2(x1.as_ref() * x2));
             }
             overloads::Mul::Bls12381FrBls12381Fr => {
-                ctx.gas.consume(interpret_cost::MUL_BLS_FR)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.mul_bls_fr)?;
                 let x1 = pop!(V::Bls12381Fr);
                 let x2 = pop!(V::Bls12381Fr);
                 stack.push(V::Bls12381Fr(x1 * x2));
             }
             overloads::Mul::NatBls12381Fr => {
                 let nat = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::mul_bls_fr_big_int(&nat)?)?;
+                ctx.gas
+                    .consume(interpret_cost::mul_bls_fr_big_int(ctx.gas.costs(), &nat)?)?;
                 let x1 = bls::Fr::from_big_int(&nat.into());
                 let x2 = pop!(V::Bls12381Fr);
                 stack.push(V::Bls12381Fr(x1 * x2));
             }
             overloads::Mul::IntBls12381Fr => {
                 let int = pop!(V::Int);
-                ctx.gas.consume(interpret_cost::mul_bls_fr_big_int(&int)?)?;
+                ctx.gas
+                    .consume(interpret_cost::mul_bls_fr_big_int(ctx.gas.costs(), &int)?)?;
                 let x1 = bls::Fr::from_big_int(&int);
                 let x2 = pop!(V::Bls12381Fr);
                 stack.push(V::Bls12381Fr(x1 * x2));
@@ -295,14 +442,16 @@ fn interpret_one<'a>(
             overloads::Mul::Bls12381FrNat => {
                 let x1 = pop!(V::Bls12381Fr);
                 let nat = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::mul_bls_fr_big_int(&nat)?)?;
+                ctx.gas
+                    .consume(interpret_cost::mul_bls_fr_big_int(ctx.gas.costs(), &nat)?)?;
                 let x2 = bls::Fr::from_big_int(&nat.into());
                 stack.push(V::Bls12381Fr(x1 * x2));
             }
             overloads::Mul::Bls12381FrInt => {
                 let x1 = pop!(V::Bls12381Fr);
                 let int = pop!(V::Int);
-                ctx.gas.consume(interpret_cost::mul_bls_fr_big_int(&int)?)?;
+                ctx.gas
+                    .consume(interpret_cost::mul_bls_fr_big_int(ctx.gas.costs(), &int)?)?;
                 let x2 = bls::Fr::from_big_int(&int);
                 stack.push(V::Bls12381Fr(x1 * x2));
             }
@@ -310,32 +459,144 @@ fn interpret_one<'a>(
         I::Neg(overload) => match overload {
             overloads::Neg::Nat => {
                 let v = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::neg_int(&v)?)?;
+                ctx.gas
+                    .consume(interpret_cost::neg_int(ctx.gas.costs(), &v)?)?;
                 stack.push(V::Int(BigInt::from_biguint(Sign::Minus, v)));
             }
             overloads::Neg::Int => {
                 let v = pop!(V::Int);
-                ctx.gas.consume(interpret_cost::neg_int(&v)?)?;
+                ctx.gas
+                    .consume(interpret_cost::neg_int(ctx.gas.costs(), &v)?)?;
                 stack.push(V::Int(-v));
             }
             overloads::Neg::Bls12381G1 => {
-                ctx.gas.consume(interpret_cost::NEG_G1)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.neg_g1)?;
                 let v = irrefutable_match!(&mut stack[0]; V::Bls12381G1).as_mut();
                 *v = -(v as &bls::G1);
             }
             overloads::Neg::Bls12381G2 => {
-                ctx.gas.consume(interpret_cost::NEG_G2)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.neg_g2)?;
                 let v = irrefutable_match!(&mut stack[0]; V::Bls12381G2).as_mut();
                 *v = -(v as &bls::G2);
             }
             overloads::Neg::Bls12381Fr => {
-                ctx.gas.consume(interpret_cost::NEG_FR)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.neg_fr)?;
                 let v = irrefutable_match!(&mut stack[0]; V::Bls12381Fr);
                 *v = -(v as &bls::Fr);
             }
         },
+        I::Sub(overload) => match overload {
+            overloads::Sub::IntInt => {
+                let o1 = pop!(V::Int);
+                let o2 = pop!(V::Int);
+                ctx.gas
+                    .consume(interpret_cost::sub_num(ctx.gas.costs(), &o1, &o2)?)?;
+                let diff = o1 - o2;
+                stack.push(V::Int(diff));
+            }
+            overloads::Sub::NatNat => {
+                let o1 = pop!(V::Nat);
+                let o2 = pop!(V::Nat);
+                ctx.gas
+                    .consume(interpret_cost::sub_num(ctx.gas.costs(), &o1, &o2)?)?;
+                let diff = BigInt::from(o1) - BigInt::from(o2);
+                stack.push(V::Int(diff));
+            }
+            overloads::Sub::IntNat => {
+                let o1 = pop!(V::Int);
+                let o2 = pop!(V::Nat);
+                ctx.gas
+                    .consume(interpret_cost::sub_num(ctx.gas.costs(), &o1, &o2)?)?;
+                let diff = o1 - BigInt::from(o2);
+                stack.push(V::Int(diff));
+            }
+            overloads::Sub::NatInt => {
+                let o1 = pop!(V::Nat);
+                let o2 = pop!(V::Int);
+                ctx.gas
+                    .consume(interpret_cost::sub_num(ctx.gas.costs(), &o1, &o2)?)?;
+                let diff = BigInt::from(o1) - o2;
+                stack.push(V::Int(diff));
+            }
+            overloads::Sub::MutezMutez => {
+                let o1 = pop!(V::Mutez);
+                let o2 = pop!(V::Mutez);
+                ctx.gas.consume(ctx.gas.costs().interpret.sub_mutez)?;
+                let diff = o1
+                    .checked_sub(o2)
+                    .filter(|diff| *diff >= 0)
+                    .ok_or(InterpretError::MutezUnderflow)?;
+                stack.push(V::Mutez(diff));
+            }
+            overloads::Sub::TimestampInt => {
+                let o1 = pop!(V::Timestamp);
+                let o2 = pop!(V::Int);
+                ctx.gas
+                    .consume(interpret_cost::sub_num(ctx.gas.costs(), &o1, &o2)?)?;
+                let diff = o1 - o2;
+                stack.push(V::Timestamp(diff));
+            }
+            overloads::Sub::TimestampTimestamp => {
+                let o1 = pop!(V::Timestamp);
+                let o2 = pop!(V::Timestamp);
+                ctx.gas
+                    .consume(interpret_cost::sub_num(ctx.gas.costs(), &o1, &o2)?)?;
+                let diff = o1 - o2;
+                stack.push(V::Int(diff));
+            }
+        },
+        I::Ediv(overload) => {
+            let res = match overload {
+                overloads::Ediv::NatNat => {
+                    let x = pop!(V::Nat);
+                    let y = pop!(V::Nat);
+                    ctx.gas
+                        .consume(interpret_cost::ediv_num(ctx.gas.costs(), &x, &y)?)?;
+                    (!y.is_zero()).then(|| (V::Nat(&x / &y), V::Nat(x % y)))
+                }
+                overloads::Ediv::NatInt => {
+                    let x = pop!(V::Nat);
+                    let y = pop!(V::Int);
+                    ctx.gas
+                        .consume(interpret_cost::ediv_num(ctx.gas.costs(), &x, &y)?)?;
+                    ediv_int(x.into(), y).map(|(q, r)| (V::Int(q), V::Nat(r)))
+                }
+                overloads::Ediv::IntNat => {
+                    let x = pop!(V::Int);
+                    let y = pop!(V::Nat);
+                    ctx.gas
+                        .consume(interpret_cost::ediv_num(ctx.gas.costs(), &x, &y)?)?;
+                    ediv_int(x, y.into()).map(|(q, r)| (V::Int(q), V::Nat(r)))
+                }
+                overloads::Ediv::IntInt => {
+                    let x = pop!(V::Int);
+                    let y = pop!(V::Int);
+                    ctx.gas
+                        .consume(interpret_cost::ediv_num(ctx.gas.costs(), &x, &y)?)?;
+                    ediv_int(x, y).map(|(q, r)| (V::Int(q), V::Nat(r)))
+                }
+                overloads::Ediv::MutezNat => {
+                    ctx.gas.consume(ctx.gas.costs().interpret.ediv_tez_nat)?;
+                    let x = pop!(V::Mutez);
+                    let y = pop!(V::Nat);
+                    // a divisor not fitting into mutez is larger than any dividend
+                    match i64::try_from(y) {
+                        Ok(0) => None,
+                        Ok(y) => Some((V::Mutez(x / y), V::Mutez(x % y))),
+                        Err(_) => Some((V::Mutez(0), V::Mutez(x))),
+                    }
+                }
+                overloads::Ediv::MutezMutez => {
+                    ctx.gas.consume(ctx.gas.costs().interpret.ediv_tez)?;
+                    let x = pop!(V::Mutez);
+                    let y = pop!(V::Mutez);
+                    (y != 0).then(|| (V::Nat(BigUint::from(x as u64 / y as u64)), V::Mutez(x % y)))
+                }
+            };
+            stack.push(V::new_option(res.map(|(q, r)| V::new_pair(q, r))));
+        }
         I::SubMutez => {
-            ctx.gas.consume(interpret_cost::SUB_MUTEZ)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.sub_mutez)?;
             let v1 = pop!(V::Mutez);
             let v2 = pop!(V::Mutez);
             if v1 >= v2 {
@@ -348,19 +609,21 @@ fn interpret_one<'a>(
             overloads::And::Bool => {
                 let o1 = pop!(V::Bool);
                 let o2 = irrefutable_match!(&mut stack[0]; V::Bool);
-                ctx.gas.consume(interpret_cost::AND_BOOL)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.and_bool)?;
                 *o2 &= o1;
             }
             overloads::And::NatNat => {
                 let o1 = pop!(V::Nat);
                 let o2 = irrefutable_match!(&mut stack[0]; V::Nat);
-                ctx.gas.consume(interpret_cost::and_num(&o1, o2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::and_num(ctx.gas.costs(), &o1, o2)?)?;
                 *o2 &= o1;
             }
             overloads::And::IntNat => {
                 let o1 = pop!(V::Int);
                 let o2 = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::and_num(&o1, &o2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::and_num(ctx.gas.costs(), &o1, &o2)?)?;
                 let res = BigUint::try_from(o1 & BigInt::from(o2))
                     // safe, `neg` & `pos` = `pos`
                     .unwrap();
@@ -369,7 +632,8 @@ fn interpret_one<'a>(
             overloads::And::Bytes => {
                 let mut o1 = pop!(V::Bytes);
                 let o2 = irrefutable_match!(&mut stack[0]; V::Bytes);
-                ctx.gas.consume(interpret_cost::and_bytes(&o1, o2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::and_bytes(ctx.gas.costs(), &o1, o2)?)?;
 
                 // The resulting vector length is the smallest length among the
                 // operands, so to reuse memory we put the smallest vector to
@@ -386,19 +650,21 @@ fn interpret_one<'a>(
             overloads::Or::Bool => {
                 let o1 = pop!(V::Bool);
                 let o2 = irrefutable_match!(&mut stack[0]; V::Bool);
-                ctx.gas.consume(interpret_cost::OR_BOOL)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.or_bool)?;
                 *o2 |= o1;
             }
             overloads::Or::Nat => {
                 let o1 = pop!(V::Nat);
                 let o2 = irrefutable_match!(&mut stack[0]; V::Nat);
-                ctx.gas.consume(interpret_cost::or_num(&o1, o2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::or_num(ctx.gas.costs(), &o1, o2)?)?;
                 *o2 |= o1;
             }
             overloads::Or::Bytes => {
                 let mut o1 = pop!(V::Bytes);
                 let o2 = irrefutable_match!(&mut stack[0]; V::Bytes);
-                ctx.gas.consume(interpret_cost::or_bytes(&o1, o2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::or_bytes(ctx.gas.costs(), &o1, o2)?)?;
 
                 // The resulting vector length is the largest length among the
                 // operands, so to reuse memory we put the largest vector to
@@ -415,13 +681,14 @@ fn interpret_one<'a>(
             overloads::Xor::Bool => {
                 let o1 = pop!(V::Bool);
                 let o2 = irrefutable_match!(&mut stack[0]; V::Bool);
-                ctx.gas.consume(interpret_cost::XOR_BOOL)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.xor_bool)?;
                 *o2 ^= o1;
             }
             overloads::Xor::Nat => {
                 let o1 = pop!(V::Nat);
                 let o2 = irrefutable_match!(&mut stack[0]; V::Nat);
-                ctx.gas.consume(interpret_cost::xor_nat(&o1, o2)?)?;
+                ctx.gas
+                    .consume(interpret_cost::xor_nat(ctx.gas.costs(), &o1, o2)?)?;
                 *o2 ^= o1;
             }
             overloads::Xor::Bytes => {
@@ -442,91 +709,114 @@ fn interpret_one<'a>(
         I::Not(overload) => match overload {
             overloads::Not::Bool => {
                 let o = irrefutable_match!(&mut stack[0]; V::Bool);
-                ctx.gas.consume(interpret_cost::NOT_BOOL)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.not_bool)?;
                 *o = !*o;
             }
             overloads::Not::Int => {
                 let o = pop!(V::Int);
-                ctx.gas.consume(interpret_cost::not_num(&o)?)?;
+                ctx.gas
+                    .consume(interpret_cost::not_num(ctx.gas.costs(), &o)?)?;
                 stack.push(V::Int(!o));
             }
             overloads::Not::Nat => {
                 let o = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::not_num(&o)?)?;
+                ctx.gas
+                    .consume(interpret_cost::not_num(ctx.gas.costs(), &o)?)?;
                 stack.push(V::Int(!BigInt::from(o)))
             }
             overloads::Not::Bytes => {
                 let o = irrefutable_match!(&mut stack[0]; V::Bytes);
-                ctx.gas.consume(interpret_cost::not_bytes(o)?)?;
+                ctx.gas
+                    .consume(interpret_cost::not_bytes(ctx.gas.costs(), o)?)?;
                 for b in o.iter_mut() {
                     *b = !*b
                 }
             }
         },
+        I::Lsl => {
+            let x = pop!(V::Nat);
+            let s = shift_amount(pop!(V::Nat))?;
+            ctx.gas
+                .consume(interpret_cost::lsl_nat(ctx.gas.costs(), &x, s)?)?;
+            stack.push(V::Nat(x << s));
+        }
+        I::Lsr => {
+            let x = pop!(V::Nat);
+            let s = shift_amount(pop!(V::Nat))?;
+            ctx.gas
+                .consume(interpret_cost::lsr_nat(ctx.gas.costs(), &x)?)?;
+            stack.push(V::Nat(x >> s));
+        }
         I::Dip(opt_height, nested) => {
-            ctx.gas.consume(interpret_cost::dip(*opt_height)?)?;
+            ctx.gas
+                .consume(interpret_cost::dip(ctx.gas.costs(), *opt_height)?)?;
             let protected_height: u16 = opt_height.unwrap_or(1);
             let mut protected = stack.split_off(protected_height as usize);
             interpret(nested, ctx, arena, stack)?;
-            ctx.gas.consume(interpret_cost::undip(protected_height)?)?;
+            ctx.gas
+                .consume(interpret_cost::undip(ctx.gas.costs(), protected_height)?)?;
             stack.append(&mut protected);
         }
         I::Drop(opt_height) => {
-            ctx.gas.consume(interpret_cost::drop(*opt_height)?)?;
+            ctx.gas
+                .consume(interpret_cost::drop(ctx.gas.costs(), *opt_height)?)?;
             let drop_height: usize = opt_height.unwrap_or(1) as usize;
             stack.drop_top(drop_height);
         }
         I::Dup(opt_height) => {
-            ctx.gas.consume(interpret_cost::dup(*opt_height)?)?;
+            ctx.gas
+                .consume(interpret_cost::dup(ctx.gas.costs(), *opt_height)?)?;
             let dup_height: usize = opt_height.unwrap_or(1) as usize;
             stack.push(stack[dup_height - 1].clone());
         }
         I::Dig(dig_height) => {
-            ctx.gas.consume(interpret_cost::dig(*dig_height)?)?;
+            ctx.gas
+                .consume(interpret_cost::dig(ctx.gas.costs(), *dig_height)?)?;
             if *dig_height > 0 {
                 let e = stack.remove(*dig_height as usize);
                 stack.push(e);
             }
         }
         I::Dug(dug_height) => {
-            ctx.gas.consume(interpret_cost::dug(*dug_height)?)?;
+            ctx.gas
+                .consume(interpret_cost::dug(ctx.gas.costs(), *dug_height)?)?;
             if *dug_height > 0 {
                 let e = pop!();
                 stack.insert(*dug_height as usize, e);
             }
         }
         I::Gt => {
-            ctx.gas.consume(interpret_cost::GT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.gt)?;
             let i = pop!(V::Int);
             stack.push(V::Bool(i.is_positive()));
         }
         I::Ge => {
-            ctx.gas.consume(interpret_cost::GE)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.ge)?;
             let i = pop!(V::Int);
             stack.push(V::Bool(!i.is_negative()));
         }
         I::Eq => {
-            ctx.gas.consume(interpret_cost::EQ)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.eq)?;
             let i = pop!(V::Int);
             stack.push(V::Bool(i.is_zero()));
         }
         I::Neq => {
-            ctx.gas.consume(interpret_cost::NEQ)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.neq)?;
             let i = pop!(V::Int);
             stack.push(V::Bool(!i.is_zero()));
         }
         I::Le => {
-            ctx.gas.consume(interpret_cost::LE)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.le)?;
             let i = pop!(V::Int);
             stack.push(V::Bool(!i.is_positive()));
         }
         I::Lt => {
-            ctx.gas.consume(interpret_cost::LT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.lt)?;
             let i = pop!(V::Int);
             stack.push(V::Bool(i.is_negative()));
         }
         I::If(nested_t, nested_f) => {
-            ctx.gas.consume(interpret_cost::IF)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.if_)?;
             if pop!(V::Bool) {
                 interpret(nested_t, ctx, arena, stack)?;
             } else {
@@ -534,7 +824,7 @@ fn interpret_one<'a>(
             }
         }
         I::IfNone(when_none, when_some) => {
-            ctx.gas.consume(interpret_cost::IF_NONE)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.if_none)?;
             match pop!(V::Option) {
                 Some(x) => {
                     stack.push(*x);
@@ -544,7 +834,7 @@ fn interpret_one<'a>(
             }
         }
         I::IfCons(when_cons, when_nil) => {
-            ctx.gas.consume(interpret_cost::IF_CONS)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.if_cons)?;
             let lst = irrefutable_match!(&mut stack[0]; V::List);
             match lst.uncons() {
                 Some(x) => {
@@ -558,7 +848,7 @@ fn interpret_one<'a>(
             }
         }
         I::IfLeft(when_left, when_right) => {
-            ctx.gas.consume(interpret_cost::IF_LEFT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.if_left)?;
             let or = *pop!(V::Or);
             match or {
                 Or::Left(x) => {
@@ -573,40 +863,43 @@ fn interpret_one<'a>(
         }
         I::Abs => {
             let i = pop!(V::Int);
-            ctx.gas.consume(interpret_cost::abs(&i)?)?;
+            ctx.gas.consume(interpret_cost::abs(ctx.gas.costs(), &i)?)?;
             stack.push(V::Nat(i.into_parts().1));
         }
         I::IsNat => {
             let i = pop!(V::Int);
-            ctx.gas.consume(interpret_cost::ISNAT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.isnat)?;
             stack.push(V::new_option(i.try_into().ok().map(V::Nat)));
         }
         I::Int(overload) => match overload {
             overloads::Int::Nat => {
                 let i = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::INT_NAT)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.int_nat)?;
                 stack.push(V::Int(i.into()));
             }
             overloads::Int::Bls12381Fr => {
                 let i = pop!(V::Bls12381Fr);
-                ctx.gas.consume(interpret_cost::INT_BLS_FR)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.int_bls_fr)?;
                 stack.push(V::Int(i.to_big_int()))
             }
             overloads::Int::Bytes => {
                 let i = pop!(V::Bytes);
-                ctx.gas.consume(interpret_cost::int_bytes(i.len())?)?;
+                ctx.gas
+                    .consume(interpret_cost::int_bytes(ctx.gas.costs(), i.len())?)?;
                 stack.push(V::Int(BigInt::from_signed_bytes_be(&i)))
             }
         },
         I::Nat => {
             let i = pop!(V::Bytes);
-            ctx.gas.consume(interpret_cost::int_bytes(i.len())?)?;
+            ctx.gas
+                .consume(interpret_cost::int_bytes(ctx.gas.costs(), i.len())?)?;
             stack.push(V::Nat(BigUint::from_bytes_be(&i)))
         }
         I::Bytes(overload) => match overload {
             overloads::Bytes::Nat => {
                 let i = pop!(V::Nat);
-                ctx.gas.consume(interpret_cost::bytes_nat(&i)?)?;
+                ctx.gas
+                    .consume(interpret_cost::bytes_nat(ctx.gas.costs(), &i)?)?;
                 stack.push(V::Bytes(if i.is_zero() {
                     Vec::new() // empty
                 } else {
@@ -615,7 +908,8 @@ fn interpret_one<'a>(
             }
             overloads::Bytes::Int => {
                 let i = pop!(V::Int);
-                ctx.gas.consume(interpret_cost::bytes_int(&i)?)?;
+                ctx.gas
+                    .consume(interpret_cost::bytes_int(ctx.gas.costs(), &i)?)?;
                 stack.push(V::Bytes(if i.is_zero() {
                     Vec::new() // empty
                 } else {
@@ -624,21 +918,21 @@ fn interpret_one<'a>(
             }
         },
         I::Loop(nested) => {
-            ctx.gas.consume(interpret_cost::LOOP_ENTER)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.loop_enter)?;
             loop {
-                ctx.gas.consume(interpret_cost::LOOP)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.loop_)?;
                 if pop!(V::Bool) {
                     interpret(nested, ctx, arena, stack)?;
                 } else {
-                    ctx.gas.consume(interpret_cost::LOOP_EXIT)?;
+                    ctx.gas.consume(ctx.gas.costs().interpret.loop_exit)?;
                     break;
                 }
             }
         }
         I::LoopLeft(nested) => {
-            ctx.gas.consume(interpret_cost::LOOP_LEFT_ENTER)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.loop_left_enter)?;
             loop {
-                ctx.gas.consume(interpret_cost::LOOP)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.loop_)?;
                 match *pop!(V::Or) {
                     Or::Left(x) => {
                         stack.push(x);
@@ -646,19 +940,19 @@ fn interpret_one<'a>(
                     }
                     Or::Right(x) => {
                         stack.push(x);
-                        ctx.gas.consume(interpret_cost::LOOP_EXIT)?;
+                        ctx.gas.consume(ctx.gas.costs().interpret.loop_exit)?;
                         break;
                     }
                 }
             }
         }
         I::Iter(overload, nested) => {
-            ctx.gas.consume(interpret_cost::ITER)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.iter)?;
             match overload {
                 overloads::Iter::List => {
                     let lst = pop!(V::List);
                     for i in lst {
-                        ctx.gas.consume(interpret_cost::PUSH)?;
+                        ctx.gas.consume(ctx.gas.costs().interpret.push)?;
                         stack.push(i);
                         interpret(nested, ctx, arena, stack)?;
                     }
@@ -666,7 +960,7 @@ fn interpret_one<'a>(
                 overloads::Iter::Set => {
                     let set = pop!(V::Set);
                     for v in set {
-                        ctx.gas.consume(interpret_cost::PUSH)?;
+                        ctx.gas.consume(ctx.gas.costs().interpret.push)?;
                         stack.push(v);
                         interpret(nested, ctx, arena, stack)?;
                     }
@@ -674,7 +968,7 @@ fn interpret_one<'a>(
                 overloads::Iter::Map => {
                     let map = pop!(V::Map);
                     for (k, v) in map {
-                        ctx.gas.consume(interpret_cost::PUSH)?;
+                        ctx.gas.consume(ctx.gas.costs().interpret.push)?;
                         stack.push(V::new_pair(k, v));
                         interpret(nested, ctx, arena, stack)?;
                     }
@@ -683,12 +977,12 @@ fn interpret_one<'a>(
         }
         I::Map(overload, nested) => match overload {
             overloads::Map::List => {
-                ctx.gas.consume(interpret_cost::MAP_LIST)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.map_list)?;
                 let list = pop!(V::List);
                 let result = list
                     .into_iter()
                     .map(|elem| {
-                        ctx.gas.consume(interpret_cost::PUSH)?;
+                        ctx.gas.consume(ctx.gas.costs().interpret.push)?;
                         stack.push(elem);
                         interpret(nested, ctx, arena, stack)?;
                         Ok(pop!())
@@ -697,11 +991,11 @@ fn interpret_one<'a>(
                 stack.push(V::List(result));
             }
             overloads::Map::Option => {
-                ctx.gas.consume(interpret_cost::MAP_OPTION)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.map_option)?;
                 let option = pop!(V::Option);
                 let result = match option {
                     Some(elem) => {
-                        ctx.gas.consume(interpret_cost::PUSH)?;
+                        ctx.gas.consume(ctx.gas.costs().interpret.push)?;
                         stack.push(*elem);
                         interpret(nested, ctx, arena, stack)?;
                         Some(pop!())
@@ -711,10 +1005,10 @@ fn interpret_one<'a>(
                 stack.push(V::new_option(result));
             }
             overloads::Map::Map => {
-                ctx.gas.consume(interpret_cost::MAP_MAP)?;
+                ctx.gas.consume(ctx.gas.costs().interpret.map_map)?;
                 let mut map = pop!(V::Map);
                 for (key, val) in map.iter_mut() {
-                    ctx.gas.consume(interpret_cost::PUSH)?;
+                    ctx.gas.consume(ctx.gas.costs().interpret.push)?;
                     let val_temp = std::mem::replace(val, V::Unit);
                     stack.push(V::new_pair(key.clone(), val_temp));
                     interpret(nested, ctx, arena, stack)?;
@@ -724,11 +1018,11 @@ fn interpret_one<'a>(
             }
         },
         I::Push(v) => {
-            ctx.gas.consume(interpret_cost::PUSH)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.push)?;
             stack.push(v.clone());
         }
         I::Swap => {
-            ctx.gas.consume(interpret_cost::SWAP)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.swap)?;
             stack.swap(0, 1);
         }
         I::Failwith(ty) => {
@@ -737,43 +1031,48 @@ fn interpret_one<'a>(
         }
         I::Never => unreachable_state(),
         I::Unit => {
-            ctx.gas.consume(interpret_cost::UNIT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.unit)?;
             stack.push(V::Unit);
         }
+        // no-ops, not charged, as in the protocol
+        I::Cast | I::Rename => {}
         I::Car => {
-            ctx.gas.consume(interpret_cost::CAR)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.car)?;
             let (l, _) = *pop!(V::Pair);
             stack.push(l);
         }
         I::Cdr => {
-            ctx.gas.consume(interpret_cost::CDR)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.cdr)?;
             let (_, r) = *pop!(V::Pair);
             stack.push(r);
         }
         I::Pair => {
-            ctx.gas.consume(interpret_cost::PAIR)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.pair)?;
             let l = pop!();
             let r = pop!();
             stack.push(V::new_pair(l, r));
         }
         I::PairN(n) => {
-            ctx.gas.consume(interpret_cost::pair_n(*n as usize)?)?;
+            ctx.gas
+                .consume(interpret_cost::pair_n(ctx.gas.costs(), *n as usize)?)?;
             let res = stack
-                .drain_top(*n as usize)
+                .split_off(*n as usize)
+                .into_iter()
                 .rev()
                 .reduce(|acc, e| V::new_pair(e, acc))
                 .unwrap();
             stack.push(res);
         }
         I::Unpair => {
-            ctx.gas.consume(interpret_cost::UNPAIR)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.unpair)?;
             let (l, r) = *pop!(V::Pair);
             stack.push(r);
             stack.push(l);
         }
         I::UnpairN(n) => {
-            ctx.gas.consume(interpret_cost::unpair_n(*n as usize)?)?;
-            fn fill<'a>(n: u16, stack: &mut IStack<'a>, p: TypedValue<'a>) {
+            ctx.gas
+                .consume(interpret_cost::unpair_n(ctx.gas.costs(), *n as usize)?)?;
+            fn fill<'a>(n: u16, stack: &mut impl StackOps<TypedValue<'a>>, p: TypedValue<'a>) {
                 if n == 0 {
                     stack.push(p);
                 } else if let V::Pair(p) = p {
@@ -788,31 +1087,32 @@ fn interpret_one<'a>(
             fill(n - 1, stack, p);
         }
         I::ISome => {
-            ctx.gas.consume(interpret_cost::SOME)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.some)?;
             let v = pop!();
             stack.push(V::new_option(Some(v)));
         }
         I::None => {
-            ctx.gas.consume(interpret_cost::NONE)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.none)?;
             stack.push(V::new_option(None));
         }
         I::Compare => {
             let l = pop!();
             let r = pop!();
-            ctx.gas.consume(interpret_cost::compare(&l, &r)?)?;
+            ctx.gas
+                .consume(interpret_cost::compare(ctx.gas.costs(), &l, &r)?)?;
             let cmp = l.partial_cmp(&r).expect("comparison failed") as i8;
             stack.push(V::Int(cmp.into()));
         }
         I::Amount => {
-            ctx.gas.consume(interpret_cost::AMOUNT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.amount)?;
             stack.push(V::Mutez(ctx.amount));
         }
         I::Nil => {
-            ctx.gas.consume(interpret_cost::NIL)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.nil)?;
             stack.push(V::List(MichelsonList::new()));
         }
         I::Cons => {
-            ctx.gas.consume(interpret_cost::CONS)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.cons)?;
             let elt = pop!();
             let mut lst = pop!(V::List);
             // NB: this is slightly better than lists on average, but needs to
@@ -824,31 +1124,41 @@ fn interpret_one<'a>(
             overloads::Concat::TwoStrings => {
                 let mut s1 = pop!(V::String);
                 let s2 = pop!(V::String);
-                ctx.gas
-                    .consume(interpret_cost::concat_string_pair(s1.len(), s2.len())?)?;
+                ctx.gas.consume(interpret_cost::concat_string_pair(
+                    ctx.gas.costs(),
+                    s1.len(),
+                    s2.len(),
+                )?)?;
                 s1.push_str(&s2);
                 stack.push(V::String(s1));
             }
             overloads::Concat::TwoBytes => {
                 let mut bs1 = pop!(V::Bytes);
                 let bs2 = pop!(V::Bytes);
-                ctx.gas
-                    .consume(interpret_cost::concat_bytes_pair(bs1.len(), bs2.len())?)?;
+                ctx.gas.consume(interpret_cost::concat_bytes_pair(
+                    ctx.gas.costs(),
+                    bs1.len(),
+                    bs2.len(),
+                )?)?;
                 bs1.extend_from_slice(&bs2);
                 stack.push(V::Bytes(bs1))
             }
             overloads::Concat::ListOfStrings => {
                 let list = pop!(V::List);
-                ctx.gas
-                    .consume(interpret_cost::concat_list_precheck(list.len())?)?;
+                ctx.gas.consume(interpret_cost::concat_list_precheck(
+                    ctx.gas.costs(),
+                    list.len(),
+                )?)?;
 
                 let mut total_len = Checked::zero();
                 for val in &list {
                     let s = irrefutable_match!(val; V::String);
                     total_len += s.len()
                 }
-                ctx.gas
-                    .consume(interpret_cost::concat_string_list(total_len)?)?;
+                ctx.gas.consume(interpret_cost::concat_string_list(
+                    ctx.gas.costs(),
+                    total_len,
+                )?)?;
 
                 let mut result = String::with_capacity(total_len.ok_or(OutOfGas)?);
                 for val in list {
@@ -859,16 +1169,20 @@ fn interpret_one<'a>(
             }
             overloads::Concat::ListOfBytes => {
                 let list = pop!(V::List);
-                ctx.gas
-                    .consume(interpret_cost::concat_list_precheck(list.len())?)?;
+                ctx.gas.consume(interpret_cost::concat_list_precheck(
+                    ctx.gas.costs(),
+                    list.len(),
+                )?)?;
 
                 let mut total_len = Checked::zero();
                 for val in &list {
                     let bs = irrefutable_match!(val; V::Bytes);
                     total_len += bs.len()
                 }
-                ctx.gas
-                    .consume(interpret_cost::concat_bytes_list(total_len)?)?;
+                ctx.gas.consume(interpret_cost::concat_bytes_list(
+                    ctx.gas.costs(),
+                    total_len,
+                )?)?;
 
                 let mut result = Vec::with_capacity(total_len.ok_or(OutOfGas)?);
                 for val in &list {
@@ -880,12 +1194,17 @@ fn interpret_one<'a>(
         },
         I::EmptySet => {
             use std::collections::BTreeSet;
-            ctx.gas.consume(interpret_cost::EMPTY_SET)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.empty_set)?;
             stack.push(V::Set(BTreeSet::new()))
         }
+        I::EmptyMap => {
+            use std::collections::BTreeMap;
+            ctx.gas.consume(ctx.gas.costs().interpret.empty_map)?;
+            stack.push(V::Map(BTreeMap::new()))
+        }
         I::EmptyBigMap(kty, vty) => {
             use std::collections::BTreeMap;
-            ctx.gas.consume(interpret_cost::EMPTY_BIG_MAP)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.empty_big_map)?;
             stack.push(V::BigMap(BigMap {
                 id: None,
                 overlay: BTreeMap::new(),
@@ -897,14 +1216,16 @@ fn interpret_one<'a>(
             overloads::Mem::Set => {
                 let key = pop!();
                 let set = pop!(V::Set);
-                ctx.gas.consume(interpret_cost::set_mem(&key, set.len())?)?;
+                ctx.gas
+                    .consume(interpret_cost::set_mem(ctx.gas.costs(), &key, set.len())?)?;
                 let result = set.contains(&key);
                 stack.push(V::Bool(result));
             }
             overloads::Mem::Map => {
                 let key = pop!();
                 let map = pop!(V::Map);
-                ctx.gas.consume(interpret_cost::map_mem(&key, map.len())?)?;
+                ctx.gas
+                    .consume(interpret_cost::map_mem(ctx.gas.costs(), &key, map.len())?)?;
                 let result = map.contains_key(&key);
                 stack.push(V::Bool(result));
             }
@@ -912,8 +1233,11 @@ fn interpret_one<'a>(
                 let key = pop!();
                 let map = pop!(V::BigMap);
                 // the protocol deliberately uses map costs for the overlay
-                ctx.gas
-                    .consume(interpret_cost::map_mem(&key, map.overlay.len())?)?;
+                ctx.gas.consume(interpret_cost::map_mem(
+                    ctx.gas.costs(),
+                    &key,
+                    map.overlay.len(),
+                )?)?;
                 let result = map.mem(&key, ctx.big_map_storage.as_ref())?;
                 stack.push(V::Bool(result));
             }
@@ -922,7 +1246,8 @@ fn interpret_one<'a>(
             overloads::Get::Map => {
                 let key = pop!();
                 let map = pop!(V::Map);
-                ctx.gas.consume(interpret_cost::map_get(&key, map.len())?)?;
+                ctx.gas
+                    .consume(interpret_cost::map_get(ctx.gas.costs(), &key, map.len())?)?;
                 let result = map.get(&key);
                 stack.push(V::new_option(result.cloned()));
             }
@@ -930,14 +1255,18 @@ fn interpret_one<'a>(
                 let key = pop!();
                 let map = pop!(V::BigMap);
                 // the protocol intentionally uses map costs for the overlay
-                ctx.gas
-                    .consume(interpret_cost::map_get(&key, map.overlay.len())?)?;
+                ctx.gas.consume(interpret_cost::map_get(
+                    ctx.gas.costs(),
+                    &key,
+                    map.overlay.len(),
+                )?)?;
                 let result = map.get(arena, &key, ctx.big_map_storage.as_ref())?;
                 stack.push(V::new_option(result));
             }
         },
         I::GetN(n) => {
-            ctx.gas.consume(interpret_cost::get_n(*n as usize)?)?;
+            ctx.gas
+                .consume(interpret_cost::get_n(ctx.gas.costs(), *n as usize)?)?;
             let res = get_nth_field_ref(*n, &mut stack[0]);
             // this is a bit hacky, but borrow rules leave few other options
             stack[0] = std::mem::replace(res, V::Unit);
@@ -947,8 +1276,11 @@ fn interpret_one<'a>(
                 let key = pop!();
                 let new_present = pop!(V::Bool);
                 let set = irrefutable_match!(&mut stack[0]; V::Set);
-                ctx.gas
-                    .consume(interpret_cost::set_update(&key, set.len())?)?;
+                ctx.gas.consume(interpret_cost::set_update(
+                    ctx.gas.costs(),
+                    &key,
+                    set.len(),
+                )?)?;
                 if new_present {
                     set.insert(key)
                 } else {
@@ -959,8 +1291,11 @@ fn interpret_one<'a>(
                 let key = pop!();
                 let opt_new_val = pop!(V::Option);
                 let map = irrefutable_match!(&mut stack[0]; V::Map);
-                ctx.gas
-                    .consume(interpret_cost::map_update(&key, map.len())?)?;
+                ctx.gas.consume(interpret_cost::map_update(
+                    ctx.gas.costs(),
+                    &key,
+                    map.len(),
+                )?)?;
                 match opt_new_val {
                     None => map.remove(&key),
                     Some(val) => map.insert(key, *val),
@@ -971,8 +1306,11 @@ fn interpret_one<'a>(
                 let opt_new_val = pop!(V::Option);
                 let map = irrefutable_match!(&mut stack[0]; V::BigMap);
                 // the protocol intentionally uses map costs for the overlay
-                ctx.gas
-                    .consume(interpret_cost::map_update(&key, map.overlay.len())?)?;
+                ctx.gas.consume(interpret_cost::map_update(
+                    ctx.gas.costs(),
+                    &key,
+                    map.overlay.len(),
+                )?)?;
                 map.update(key, opt_new_val.map(|x| *x));
             }
         },
@@ -981,8 +1319,11 @@ fn interpret_one<'a>(
                 let key = pop!();
                 let opt_new_val = pop!(V::Option);
                 let map = irrefutable_match!(&mut stack[0]; V::Map);
-                ctx.gas
-                    .consume(interpret_cost::map_get_and_update(&key, map.len())?)?;
+                ctx.gas.consume(interpret_cost::map_get_and_update(
+                    ctx.gas.costs(),
+                    &key,
+                    map.len(),
+                )?)?;
                 let opt_old_val = match opt_new_val {
                     None => map.remove(&key),
                     Some(val) => map.insert(key, *val),
@@ -994,8 +1335,11 @@ fn interpret_one<'a>(
                 let opt_new_val = pop!(V::Option);
                 let map = irrefutable_match!(&mut stack[0]; V::BigMap);
                 // the protocol intentionally uses map costs for the overlay
-                ctx.gas
-                    .consume(interpret_cost::map_get_and_update(&key, map.overlay.len())?)?;
+                ctx.gas.consume(interpret_cost::map_get_and_update(
+                    ctx.gas.costs(),
+                    &key,
+                    map.overlay.len(),
+                )?)?;
                 let opt_old_val = map.get(arena, &key, ctx.big_map_storage.as_ref())?;
                 map.update(key, opt_new_val.map(|x| *x));
                 stack.push(V::new_option(opt_old_val));
@@ -1005,51 +1349,53 @@ fn interpret_one<'a>(
             macro_rules! run_size {
                 ($ctor:tt, $gas:ident) => {{
                     let e = pop!(V::$ctor);
-                    ctx.gas.consume(interpret_cost::$gas)?;
+                    ctx.gas.consume(ctx.gas.costs().interpret.$gas)?;
                     let res = e.len();
                     stack.push(V::Nat(res.into()));
                 }};
             }
             match overload {
-                overloads::Size::String => run_size!(String, SIZE_STRING),
-                overloads::Size::Bytes => run_size!(Bytes, SIZE_BYTES),
-                overloads::Size::List => run_size!(List, SIZE_LIST),
-                overloads::Size::Set => run_size!(Set, SIZE_SET),
-                overloads::Size::Map => run_size!(Map, SIZE_MAP),
+                overloads::Size::String => run_size!(String, size_string),
+                overloads::Size::Bytes => run_size!(Bytes, size_bytes),
+                overloads::Size::List => run_size!(List, size_list),
+                overloads::Size::Set => run_size!(Set, size_set),
+                overloads::Size::Map => run_size!(Map, size_map),
             }
         }
         I::UpdateN(n) => {
-            ctx.gas.consume(interpret_cost::update_n(*n as usize)?)?;
+            ctx.gas
+                .consume(interpret_cost::update_n(ctx.gas.costs(), *n as usize)?)?;
             let new_val = pop!();
             let field = get_nth_field_ref(*n, &mut stack[0]);
             *field = new_val;
         }
         I::ChainId => {
-            ctx.gas.consume(interpret_cost::CHAIN_ID)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.chain_id)?;
             stack.push(V::ChainId(ctx.chain_id.clone()));
         }
         I::ISelf(entrypoint) => {
-            ctx.gas.consume(interpret_cost::SELF)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.self_)?;
             stack.push(V::Contract(Address {
                 hash: ctx.self_address.clone(),
                 entrypoint: entrypoint.clone(),
             }));
         }
         I::Pack => {
-            ctx.gas.consume(interpret_cost::PACK)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.pack)?;
             let v = pop!();
             let arena = Arena::new();
             // In the Tezos implementation they also charge gas for the pass
             // that strips locations. We don't have it.
             let mich = v.into_micheline_optimized_legacy(&arena);
             ctx.gas
-                .consume(interpret_cost::micheline_encoding(&mich)?)?;
+                .consume(interpret_cost::micheline_encoding(ctx.gas.costs(), &mich)?)?;
             let encoded = mich.encode_for_pack();
             stack.push(V::Bytes(encoded));
         }
         I::Unpack(ty) => {
             let bytes = pop!(V::Bytes);
-            ctx.gas.consume(interpret_cost::unpack(bytes.as_slice())?)?;
+            ctx.gas
+                .consume(interpret_cost::unpack(ctx.gas.costs(), bytes.as_slice())?)?;
             let mut try_unpack = || -> Option<TypedValue> {
                 let mich = Micheline::decode_packed(arena, &bytes).ok()?;
                 crate::interpreter::typecheck_value(&mich, ctx, ty).ok()
@@ -1060,8 +1406,11 @@ fn interpret_one<'a>(
             let key = pop!(V::Key);
             let sig = pop!(V::Signature);
             let msg = pop!(V::Bytes);
-            ctx.gas
-                .consume(interpret_cost::check_signature(&key, &msg)?)?;
+            ctx.gas.consume(interpret_cost::check_signature(
+                ctx.gas.costs(),
+                &key,
+                &msg,
+            )?)?;
             stack.push(V::Bool(sig.check(&key, &msg)));
         }
         I::TransferTokens => {
@@ -1069,7 +1418,7 @@ fn interpret_one<'a>(
             let mutez_amount = pop!(V::Mutez);
             let contract_address = pop!(V::Contract);
             let counter = ctx.operation_counter();
-            ctx.gas.consume(interpret_cost::TRANSFER_TOKENS)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.transfer_tokens)?;
             stack.push(V::new_operation(
                 Operation::TransferTokens(TransferTokens {
                     param,
@@ -1082,14 +1431,14 @@ fn interpret_one<'a>(
         I::SetDelegate => {
             let opt_keyhash = pop!(V::Option).map(|kh| irrefutable_match!(*kh; V::KeyHash));
             let counter: u128 = ctx.operation_counter();
-            ctx.gas.consume(interpret_cost::SET_DELEGATE)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.set_delegate)?;
             stack.push(V::new_operation(
                 Operation::SetDelegate(SetDelegate(opt_keyhash)),
                 counter,
             ))
         }
         I::Address => {
-            ctx.gas.consume(interpret_cost::ADDRESS)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.address)?;
             let address = pop!(V::Contract);
             stack.push(V::Address(address));
         }
@@ -1122,7 +1471,8 @@ fn interpret_one<'a>(
                 overloads::Slice::String => {
                     let str = pop!(V::String);
 
-                    ctx.gas.consume(interpret_cost::slice(str.len())?)?;
+                    ctx.gas
+                        .consume(interpret_cost::slice(ctx.gas.costs(), str.len())?)?;
                     validate_bounds(offset, length, str.len())
                         .and_then(|range| str.get(range))
                         .map(|str| V::String(str.to_string()))
@@ -1130,7 +1480,8 @@ fn interpret_one<'a>(
                 overloads::Slice::Bytes => {
                     let bytes = pop!(V::Bytes);
 
-                    ctx.gas.consume(interpret_cost::slice(bytes.len())?)?;
+                    ctx.gas
+                        .consume(interpret_cost::slice(ctx.gas.costs(), bytes.len())?)?;
                     validate_bounds(offset, length, bytes.len())
                         .and_then(|range| bytes.get(range))
                         .map(|bytes| V::Bytes(bytes.to_owned()))
@@ -1139,21 +1490,21 @@ fn interpret_one<'a>(
             stack.push(V::new_option(result));
         }
         I::Left => {
-            ctx.gas.consume(interpret_cost::LEFT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.left)?;
             let left = pop!();
             stack.push(V::new_or(Or::Left(left)));
         }
         I::Right => {
-            ctx.gas.consume(interpret_cost::RIGHT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.right)?;
             let right = pop!();
             stack.push(V::new_or(Or::Right(right)));
         }
         I::Lambda(lam) => {
-            ctx.gas.consume(interpret_cost::LAMBDA)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.lambda)?;
             stack.push(V::Lambda(Closure::Lambda(lam.clone())));
         }
         I::Exec => {
-            ctx.gas.consume(interpret_cost::EXEC)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.exec)?;
             let mut arg = pop!();
             let mut closure = pop!(V::Lambda);
             loop {
@@ -1182,7 +1533,7 @@ fn interpret_one<'a>(
                         closure: inner,
                         ..
                     } => {
-                        ctx.gas.consume(interpret_cost::PAIR)?; // reasonable estimation
+                        ctx.gas.consume(ctx.gas.costs().interpret.pair)?; // reasonable estimation
                         arg = V::new_pair(*arg_val, arg);
                         closure = *inner;
                     }
@@ -1192,7 +1543,7 @@ fn interpret_one<'a>(
         I::Apply { arg_ty } => {
             let arg_val = pop!();
             let closure = pop!(V::Lambda);
-            ctx.gas.consume(interpret_cost::APPLY)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.apply)?;
             stack.push(V::Lambda(Closure::Apply {
                 arg_ty: arg_ty.clone(),
                 arg_val: Box::new(arg_val),
@@ -1200,14 +1551,14 @@ fn interpret_one<'a>(
             }))
         }
         I::HashKey => {
-            ctx.gas.consume(interpret_cost::HASH_KEY)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.hash_key)?;
             let key = pop!(V::Key);
             stack.push(TypedValue::KeyHash(key.hash()))
         }
         I::Ticket => {
             let content = pop!();
             let amount = pop!(V::Nat);
-            ctx.gas.consume(interpret_cost::TICKET)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.ticket)?;
             if amount.is_zero() {
                 // If the amount is zero, then we push a None value
                 // as per the specified instruction behavior.
@@ -1222,7 +1573,7 @@ fn interpret_one<'a>(
             }
         }
         I::ReadTicket => {
-            ctx.gas.consume(interpret_cost::READ_TICKET)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.read_ticket)?;
             stack.push(unwrap_ticket(
                 irrefutable_match!(&stack[0]; V::Ticket).as_ref().clone(),
             ));
@@ -1233,8 +1584,11 @@ fn interpret_one<'a>(
             let amount_left = irrefutable_match!(amounts.0; V::Nat);
             let amount_right = irrefutable_match!(amounts.1; V::Nat);
 
-            ctx.gas
-                .consume(interpret_cost::split_ticket(&amount_left, &amount_right)?)?;
+            ctx.gas.consume(interpret_cost::split_ticket(
+                ctx.gas.costs(),
+                &amount_left,
+                &amount_right,
+            )?)?;
             if amount_left.clone() + amount_right.clone() == ticket.amount
                 && amount_left.gt(&BigUint::zero())
                 && amount_right.gt(&BigUint::zero())
@@ -1257,8 +1611,11 @@ fn interpret_one<'a>(
             let tickets = pop!(V::Pair);
             let mut ticket_left = irrefutable_match!(tickets.0; V::Ticket);
             let ticket_right = irrefutable_match!(tickets.1; V::Ticket);
-            ctx.gas
-                .consume(interpret_cost::join_tickets(&ticket_left, &ticket_right)?)?;
+            ctx.gas.consume(interpret_cost::join_tickets(
+                ctx.gas.costs(),
+                &ticket_left,
+                &ticket_right,
+            )?)?;
             if ticket_left.content == ticket_right.content
                 && ticket_left.ticketer == ticket_right.ticketer
             {
@@ -1270,35 +1627,40 @@ fn interpret_one<'a>(
         }
         I::Blake2b => {
             let msg = irrefutable_match!(&mut stack[0]; V::Bytes);
-            ctx.gas.consume(interpret_cost::blake2b(msg)?)?;
+            ctx.gas
+                .consume(interpret_cost::blake2b(ctx.gas.costs(), msg)?)?;
             *msg = blake2b_256(msg).to_vec();
         }
         I::Keccak => {
             let msg = irrefutable_match!(&mut stack[0]; V::Bytes);
-            ctx.gas.consume(interpret_cost::keccak(msg)?)?;
+            ctx.gas
+                .consume(interpret_cost::keccak(ctx.gas.costs(), msg)?)?;
             *msg = keccak256(msg).to_vec();
         }
         I::Sha256 => {
             let msg = irrefutable_match!(&mut stack[0]; V::Bytes);
-            ctx.gas.consume(interpret_cost::sha256(msg)?)?;
+            ctx.gas
+                .consume(interpret_cost::sha256(ctx.gas.costs(), msg)?)?;
             *msg = sha256(msg).to_vec();
         }
         I::Sha3 => {
             let msg = irrefutable_match!(&mut stack[0]; V::Bytes);
-            ctx.gas.consume(interpret_cost::sha3(msg)?)?;
+            ctx.gas
+                .consume(interpret_cost::sha3(ctx.gas.costs(), msg)?)?;
             *msg = sha3_256(msg).to_vec();
         }
         I::Sha512 => {
             let msg = irrefutable_match!(&mut stack[0]; V::Bytes);
-            ctx.gas.consume(interpret_cost::sha512(msg)?)?;
+            ctx.gas
+                .consume(interpret_cost::sha512(ctx.gas.costs(), msg)?)?;
             *msg = sha512(msg).to_vec();
         }
         I::Balance => {
-            ctx.gas.consume(interpret_cost::BALANCE)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.balance)?;
             stack.push(V::Mutez(ctx.balance));
         }
         I::Contract(typ, ep) => {
-            ctx.gas.consume(interpret_cost::CONTRACT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.contract)?;
             let address = pop!(V::Address);
             stack.push(TypedValue::new_option(
                 typecheck_contract_address(ctx, address, ep.clone(), typ)
@@ -1307,40 +1669,41 @@ fn interpret_one<'a>(
             ));
         }
         I::Level => {
-            ctx.gas.consume(interpret_cost::LEVEL)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.level)?;
             stack.push(TypedValue::Nat(ctx.level.clone()));
         }
         I::MinBlockTime => {
-            ctx.gas.consume(interpret_cost::MIN_BLOCK_TIME)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.min_block_time)?;
             stack.push(TypedValue::Nat(ctx.min_block_time.clone()));
         }
         I::SelfAddress => {
-            ctx.gas.consume(interpret_cost::SELF_ADDRESS)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.self_address)?;
             stack.push(TypedValue::Address(Address {
                 hash: ctx.self_address.clone(),
                 entrypoint: Entrypoint::default(),
             }));
         }
         I::Sender => {
-            ctx.gas.consume(interpret_cost::SENDER)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.sender)?;
             stack.push(TypedValue::Address(Address {
                 hash: ctx.sender.clone(),
                 entrypoint: Entrypoint::default(),
             }));
         }
         I::Source => {
-            ctx.gas.consume(interpret_cost::SOURCE)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.source)?;
             stack.push(TypedValue::Address(Address {
                 hash: ctx.source.clone(),
                 entrypoint: Entrypoint::default(),
             }));
         }
         I::Now => {
-            ctx.gas.consume(interpret_cost::NOW)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.now)?;
             stack.push(TypedValue::Timestamp(ctx.now.clone()));
         }
         I::ImplicitAccount => {
-            ctx.gas.consume(interpret_cost::IMPLICIT_ACCOUNT)?;
+            ctx.gas
+                .consume(ctx.gas.costs().interpret.implicit_account)?;
             let keyhash = pop!(V::KeyHash);
             stack.push(TypedValue::Contract(Address {
                 hash: AddressHash::Implicit(keyhash),
@@ -1348,18 +1711,19 @@ fn interpret_one<'a>(
             }));
         }
         I::VotingPower => {
-            ctx.gas.consume(interpret_cost::VOTING_POWER)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.voting_power)?;
             let keyhash = pop!(V::KeyHash);
             stack.push(TypedValue::Nat((ctx.voting_powers)(&keyhash)))
         }
         I::TotalVotingPower => {
-            ctx.gas.consume(interpret_cost::TOTAL_VOTING_POWER)?;
+            ctx.gas
+                .consume(ctx.gas.costs().interpret.total_voting_power)?;
             stack.push(TypedValue::Nat(ctx.total_voting_power.clone()))
         }
         I::Emit { tag, arg_ty } => {
             let counter: u128 = ctx.operation_counter();
             let emit_val = pop!();
-            ctx.gas.consume(interpret_cost::EMIT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.emit)?;
             stack.push(TypedValue::new_operation(
                 Operation::Emit(Emit {
                     tag: tag.clone(),
@@ -1372,7 +1736,7 @@ fn interpret_one<'a>(
         I::PairingCheck => {
             let list = pop!(V::List);
             ctx.gas
-                .consume(interpret_cost::pairing_check(list.len())?)?;
+                .consume(interpret_cost::pairing_check(ctx.gas.costs(), list.len())?)?;
             let it = list.iter().map(|elt| {
                 let (g1, g2) = irrefutable_match!(elt; V::Pair).as_ref();
                 (
@@ -1384,7 +1748,7 @@ fn interpret_one<'a>(
             stack.push(V::Bool(res));
         }
         I::CreateContract(cs, micheline) => {
-            ctx.gas.consume(interpret_cost::CREATE_CONTRACT)?;
+            ctx.gas.consume(ctx.gas.costs().interpret.create_contract)?;
             let counter: u128 = ctx.operation_counter();
             let opt_keyhash = pop!(V::Option)
                 .as_ref()
@@ -1412,6 +1776,34 @@ fn interpret_one<'a>(
     Ok(())
 }
 
+/// Shift amount of `LSL` and `LSR`, which can't be greater than 256.
+fn shift_amount<'a>(s: BigUint) -> Result<u16, InterpretError<'a>> {
+    u16::try_from(s)
+        .ok()
+        .filter(|s| *s <= 256)
+        .ok_or(InterpretError::ShiftOverflow)
+}
+
+/// Euclidean division, i.e. the remainder is always non-negative. Returns
+/// [None] on division by zero.
+fn ediv_int(x: BigInt, y: BigInt) -> Option<(BigInt, BigUint)> {
+    if y.is_zero() {
+        return None;
+    }
+    // `/` and `%` truncate towards zero, so the remainder has the sign of the
+    // dividend; adjust the result if it's negative.
+    let (mut q, mut r) = (&x / &y, x % &y);
+    if r.is_negative() {
+        if y.is_positive() {
+            q -= 1;
+        } else {
+            q += 1;
+        }
+        r += y.abs();
+    }
+    Some((q, r.into_parts().1))
+}
+
 fn compute_contract_address(operation_group_hash: &[u8; 32], o_index: u32) -> Address {
     use tezos_crypto_rs::hash::{ContractKt1Hash, HashTrait};
     let mut input: [u8; 36] = [0; 36];
@@ -1455,7 +1847,7 @@ mod interpreter_tests {
     use crate::ast::michelson_address as addr;
     use crate::ast::or::Or::Left;
     use crate::bls;
-    use crate::gas::Gas;
+    use crate::gas::{Gas, GasCosts};
     use num_bigint::BigUint;
     use Instruction::*;
     use Option::None;
@@ -1568,6 +1960,242 @@ mod interpreter_tests {
         );
     }
 
+    #[test]
+    fn test_sub_num() {
+        #[track_caller]
+        fn check(overload: overloads::Sub, mut stack: IStack, expected: TypedValue) {
+            fn num(v: &TypedValue) -> BigInt {
+                match v {
+                    V::Nat(n) => n.clone().into(),
+                    V::Int(i) | V::Timestamp(i) => i.clone(),
+                    _ => panic!("not a number: {v:?}"),
+                }
+            }
+            let cost =
+                interpret_cost::sub_num(&GasCosts::default(), &num(&stack[0]), &num(&stack[1]))
+                    .unwrap();
+            let mut ctx = Ctx::default();
+            assert_eq!(interpret_one(&Sub(overload), &mut ctx, &mut stack), Ok(()));
+            assert_eq!(stack, stk![expected]);
+            assert_eq!(ctx.gas.milligas(), Gas::default().milligas() - cost);
+        }
+        check(
+            overloads::Sub::NatNat,
+            stk![V::nat(5), V::nat(3)],
+            V::int(-2),
+        );
+        check(
+            overloads::Sub::NatNat,
+            stk![V::nat(3), V::nat(5)],
+            V::int(2),
+        );
+        check(
+            overloads::Sub::IntInt,
+            stk![V::int(-5), V::int(3)],
+            V::int(8),
+        );
+        check(
+            overloads::Sub::IntNat,
+            stk![V::nat(5), V::int(3)],
+            V::int(-2),
+        );
+        check(
+            overloads::Sub::NatInt,
+            stk![V::int(-5), V::nat(3)],
+            V::int(8),
+        );
+        check(
+            overloads::Sub::TimestampInt,
+            stk![V::int(100), V::timestamp(1571659294)],
+            V::timestamp(1571659194),
+        );
+        check(
+            overloads::Sub::TimestampTimestamp,
+            stk![V::timestamp(1571659194), V::timestamp(1571659294)],
+            V::int(100),
+        );
+    }
+
+    #[test]
+    fn test_add_timestamp() {
+        #[track_caller]
+        fn check(overload: overloads::Add, mut stack: IStack) {
+            let mut ctx = Ctx::default();
+            assert_eq!(interpret_one(&Add(overload), &mut ctx, &mut stack), Ok(()));
+            assert_eq!(stack, stk![V::timestamp(1571659294)]);
+            assert!(ctx.gas.milligas() < Gas::default().milligas());
+        }
+        check(
+            overloads::Add::TimestampInt,
+            stk![V::int(100), V::timestamp(1571659194)],
+        );
+        check(
+            overloads::Add::IntTimestamp,
+            stk![V::timestamp(1571659394), V::int(-100)],
+        );
+    }
+
+    #[test]
+    fn test_sub_mutez_mutez() {
+        let mut stack = stk![V::Mutez(20), V::Mutez(2i64.pow(62))];
+        let mut ctx = Ctx::default();
+        assert!(interpret_one(&Sub(overloads::Sub::MutezMutez), &mut ctx, &mut stack).is_ok());
+        assert_eq!(ctx.gas.milligas(), Gas::default().milligas() - 15);
+        assert_eq!(stack, stk![V::Mutez(2i64.pow(62) - 20)]);
+        assert_eq!(
+            interpret_one(
+                &Sub(overloads::Sub::MutezMutez),
+                &mut ctx,
+                &mut stk![V::Mutez(5), V::Mutez(3)]
+            ),
+            Err(InterpretError::MutezUnderflow)
+        );
+        assert_eq!(
+            interpret_one(
+                &Sub(overloads::Sub::MutezMutez),
+                &mut ctx,
+                &mut stk![V::Mutez(5), V::Mutez(5)]
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_lsl() {
+        let mut stack = stk![V::nat(3), V::nat(6)];
+        let mut ctx = Ctx::default();
+        assert_eq!(interpret_one(&Lsl, &mut ctx, &mut stack), Ok(()));
+        assert_eq!(stack, stk![V::nat(48)]);
+        assert!(ctx.gas.milligas() < Ctx::default().gas.milligas());
+
+        let mut stack = stk![V::nat(256), V::nat(1)];
+        assert_eq!(interpret_one(&Lsl, &mut ctx, &mut stack), Ok(()));
+        assert_eq!(stack, stk![V::Nat(BigUint::from(1u32) << 256u32)]);
+    }
+
+    #[test]
+    fn test_lsr() {
+        let mut stack = stk![V::nat(2), V::nat(13)];
+        let mut ctx = Ctx::default();
+        assert_eq!(interpret_one(&Lsr, &mut ctx, &mut stack), Ok(()));
+        assert_eq!(stack, stk![V::nat(3)]);
+        assert!(ctx.gas.milligas() < Ctx::default().gas.milligas());
+    }
+
+    #[test]
+    fn test_shift_overflow() {
+        for instr in [Lsl, Lsr] {
+            let mut ctx = Ctx::default();
+            assert_eq!(
+                interpret_one(&instr, &mut ctx, &mut stk![V::nat(257), V::nat(1)]),
+                Err(InterpretError::ShiftOverflow)
+            );
+            assert_eq!(
+                interpret_one(
+                    &instr,
+                    &mut ctx,
+                    &mut stk![V::Nat(BigUint::from(u64::MAX)), V::nat(1)]
+                ),
+                Err(InterpretError::ShiftOverflow)
+            );
+        }
+    }
+
+    mod ediv {
+        use super::*;
+
+        #[track_caller]
+        fn check(
+            overload: overloads::Ediv,
+            mut stack: IStack,
+            expected: Option<(TypedValue, TypedValue)>,
+        ) {
+            let mut ctx = Ctx::default();
+            assert_eq!(interpret_one(&Ediv(overload), &mut ctx, &mut stack), Ok(()));
+            assert_eq!(
+                stack,
+                stk![V::new_option(expected.map(|(q, r)| V::new_pair(q, r)))]
+            );
+        }
+
+        #[test]
+        fn nat_nat() {
+            check(
+                overloads::Ediv::NatNat,
+                stk![V::nat(2), V::nat(7)],
+                Some((V::nat(3), V::nat(1))),
+            );
+            check(overloads::Ediv::NatNat, stk![V::nat(0), V::nat(7)], None);
+        }
+
+        #[test]
+        fn int_int() {
+            for (x, y, q, r) in [
+                (7, 2, 3, 1),
+                (-7, 2, -4, 1),
+                (7, -2, -3, 1),
+                (-7, -2, 4, 1),
+                (-6, 2, -3, 0),
+            ] {
+                check(
+                    overloads::Ediv::IntInt,
+                    stk![V::int(y), V::int(x)],
+                    Some((V::int(q), V::nat(r))),
+                );
+            }
+            check(overloads::Ediv::IntInt, stk![V::int(0), V::int(7)], None);
+        }
+
+        #[test]
+        fn int_nat() {
+            check(
+                overloads::Ediv::IntNat,
+                stk![V::nat(2), V::int(-7)],
+                Some((V::int(-4), V::nat(1))),
+            );
+            check(overloads::Ediv::IntNat, stk![V::nat(0), V::int(-7)], None);
+            check(
+                overloads::Ediv::NatInt,
+                stk![V::int(-2), V::nat(7)],
+                Some((V::int(-3), V::nat(1))),
+            );
+            check(overloads::Ediv::NatInt, stk![V::int(0), V::nat(7)], None);
+        }
+
+        #[test]
+        fn mutez_nat() {
+            check(
+                overloads::Ediv::MutezNat,
+                stk![V::nat(2), V::Mutez(7)],
+                Some((V::Mutez(3), V::Mutez(1))),
+            );
+            check(
+                overloads::Ediv::MutezNat,
+                stk![V::Nat(BigUint::from(u64::MAX)), V::Mutez(7)],
+                Some((V::Mutez(0), V::Mutez(7))),
+            );
+            check(
+                overloads::Ediv::MutezNat,
+                stk![V::nat(0), V::Mutez(7)],
+                None,
+            );
+        }
+
+        #[test]
+        fn mutez_mutez() {
+            check(
+                overloads::Ediv::MutezMutez,
+                stk![V::Mutez(2), V::Mutez(7)],
+                Some((V::nat(3), V::Mutez(1))),
+            );
+            check(
+                overloads::Ediv::MutezMutez,
+                stk![V::Mutez(0), V::Mutez(7)],
+                None,
+            );
+        }
+    }
+
     mod logic {
         use super::*;
 
@@ -1958,6 +2586,8 @@ mod interpreter_tests {
         test(0, 0u32);
         test(10, 10u32);
         test(-10, 10u32);
+        test(i64::MIN, i64::MIN.unsigned_abs());
+        test(-(BigInt::from(1) << 128u32), BigUint::from(1u32) << 128u32);
     }
 
     #[test]
@@ -2530,6 +3160,17 @@ mod interpreter_tests {
         assert_eq!(stack, stk![V::Unit]);
     }
 
+    #[test]
+    fn cast_rename() {
+        let mut ctx = Ctx::default();
+        for instr in [Cast, Rename] {
+            let mut stack = stk![V::nat(1)];
+            assert_eq!(interpret_one(&instr, &mut ctx, &mut stack), Ok(()));
+            assert_eq!(stack, stk![V::nat(1)]);
+        }
+        assert_eq!(ctx.gas.milligas(), Gas::default().milligas());
+    }
+
     #[test]
     fn unit_instruction() {
         let mut stack = stk![];
@@ -2852,7 +3493,7 @@ mod interpreter_tests {
         macro_rules! test {
             ($expr:tt, $res:tt) => {
                 let mut stack = stk!$expr;
-                let expected_cost = interpret_cost::compare(&stack[0], &stack[1]).unwrap()
+                let expected_cost = interpret_cost::compare(&GasCosts::default(), &stack[0], &stack[1]).unwrap()
                     + interpret_cost::INTERPRET_RET;
                 let mut ctx = Ctx::default();
                 assert!(interpret(&[Compare], &mut ctx, &mut stack).is_ok());
@@ -2863,6 +3504,10 @@ mod interpreter_tests {
         test!([V::int(5), V::int(6)], [V::int(1)]);
         test!([V::int(5), V::int(5)], [V::int(0)]);
         test!([V::int(6), V::int(5)], [V::int(-1)]);
+        test!([V::nat(5), V::nat(6)], [V::int(1)]);
+        test!([V::nat(5), V::nat(5)], [V::int(0)]);
+        test!([V::Mutez(6), V::Mutez(5)], [V::int(-1)]);
+        test!([V::Mutez(5), V::Mutez(5)], [V::int(0)]);
         test!([V::Bool(true), V::Bool(false)], [V::int(-1)]);
         test!([V::Bool(true), V::Bool(true)], [V::int(0)]);
         test!([V::Bool(false), V::Bool(true)], [V::int(1)]);
@@ -2872,12 +3517,85 @@ mod interpreter_tests {
             [V::int(-1)]
         );
         test!([V::Unit, V::Unit], [V::int(0)]);
+        test!(
+            [V::Bytes(vec![0x00, 0x00]), V::Bytes(vec![0x00])],
+            [V::int(-1)]
+        );
+        test!([V::Bytes(vec![0x00]), V::Bytes(vec![0x01])], [V::int(1)]);
+        test!([V::Bytes(vec![0xab]), V::Bytes(vec![0xab])], [V::int(0)]);
         test!(
             [V::new_option(Some(V::int(5))), V::Option(None)],
             [V::int(-1)]
         );
     }
 
+    /// `UNIT` nested in `depth` blocks of `DIP 0`, in a sequence.
+    fn nested_dip(depth: usize) -> Micheline<'static> {
+        use crate::parser::test_helpers::parse;
+        let src = "{ ".to_owned() + &"DIP 0 { ".repeat(depth) + "UNIT" + &" }".repeat(depth) + " }";
+        parse(Box::leak(src.into_boxed_str())).unwrap()
+    }
+
+    #[test]
+    fn nested_dip_depth_limit() {
+        use crate::typechecker::TcError;
+
+        let temp = Arena::new();
+        let mut ctx = Ctx::default();
+        ctx.max_interpret_depth = 10;
+        // the outermost sequence takes one level
+        assert_eq!(
+            nested_dip(10).typecheck_instruction(&mut ctx, None, &[]),
+            Err(TcError::StackOverflow(10))
+        );
+        assert_eq!(ctx.nesting_depth, 0);
+        let code = nested_dip(9)
+            .typecheck_instruction(&mut ctx, None, &[])
+            .unwrap();
+        let mut stack = stk![];
+        assert_eq!(code.interpret(&mut ctx, &temp, &mut stack), Ok(()));
+        assert_eq!(stack, stk![V::Unit]);
+        ctx.max_interpret_depth = 9;
+        let mut stack = stk![];
+        assert_eq!(
+            code.interpret(&mut ctx, &temp, &mut stack),
+            Err(InterpretError::StackOverflow(9))
+        );
+        assert_eq!(ctx.nesting_depth, 0);
+    }
+
+    #[test]
+    fn nested_dip_default_depth_limit() {
+        use crate::context::DEFAULT_MAX_INTERPRET_DEPTH;
+        use crate::typechecker::TcError;
+
+        // a thread with the default stack size, much smaller than what the
+        // nested blocks take without growing the stack
+        std::thread::spawn(|| {
+            let limit = DEFAULT_MAX_INTERPRET_DEPTH;
+            let temp = Arena::new();
+            let mut ctx = Ctx::default();
+            // the outermost sequence takes one level
+            assert_eq!(
+                nested_dip(limit).typecheck_instruction(&mut ctx, None, &[]),
+                Err(TcError::StackOverflow(limit))
+            );
+            ctx.max_interpret_depth = limit + 1;
+            let code = nested_dip(limit)
+                .typecheck_instruction(&mut ctx, None, &[])
+                .unwrap();
+            ctx.max_interpret_depth = limit;
+            let mut stack = stk![];
+            assert_eq!(
+                code.interpret(&mut ctx, &temp, &mut stack),
+                Err(InterpretError::StackOverflow(limit))
+            );
+            assert_eq!(ctx.nesting_depth, 0);
+        })
+        .join()
+        .unwrap();
+    }
+
     #[test]
     fn amount() {
         let mut stack = stk![];
@@ -2975,7 +3693,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::map_get(&V::int(1), 2).unwrap()
+                - interpret_cost::map_get(&GasCosts::default(), &V::int(1), 2).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3019,7 +3737,8 @@ mod interpreter_tests {
         );
         assert_eq!(
             ctx.gas.milligas(),
-            Gas::default().milligas() - interpret_cost::map_get(&TypedValue::int(1), 1).unwrap()
+            Gas::default().milligas()
+                - interpret_cost::map_get(&GasCosts::default(), &TypedValue::int(1), 1).unwrap()
         );
     }
 
@@ -3039,7 +3758,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::map_get(&V::int(100500), 2).unwrap()
+                - interpret_cost::map_get(&GasCosts::default(), &V::int(100500), 2).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3160,7 +3879,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::map_mem(&TypedValue::int(1), 2).unwrap()
+                - interpret_cost::map_mem(&GasCosts::default(), &TypedValue::int(1), 2).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3201,7 +3920,8 @@ mod interpreter_tests {
         assert_eq!(stack, stk![TypedValue::Bool(true)]);
         assert_eq!(
             ctx.gas.milligas(),
-            Gas::default().milligas() - interpret_cost::map_mem(&TypedValue::int(1), 0).unwrap()
+            Gas::default().milligas()
+                - interpret_cost::map_mem(&GasCosts::default(), &TypedValue::int(1), 0).unwrap()
         );
     }
 
@@ -3233,7 +3953,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::set_mem(&TypedValue::int(1), 2).unwrap()
+                - interpret_cost::set_mem(&GasCosts::default(), &TypedValue::int(1), 2).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3262,6 +3982,18 @@ mod interpreter_tests {
         );
     }
 
+    #[test]
+    fn empty_map() {
+        let mut ctx = Ctx::default();
+        let mut stack = stk![];
+        assert_eq!(interpret_one(&EmptyMap, &mut ctx, &mut stack), Ok(()));
+        assert_eq!(stack, stk![TypedValue::Map(BTreeMap::new())]);
+        assert_eq!(
+            ctx.gas.milligas(),
+            Gas::default().milligas() - interpret_cost::EMPTY_MAP
+        );
+    }
+
     #[test]
     fn empty_big_map() {
         let mut ctx = Ctx::default();
@@ -3305,7 +4037,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::set_update(&TypedValue::int(1), 0).unwrap()
+                - interpret_cost::set_update(&GasCosts::default(), &TypedValue::int(1), 0).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3327,7 +4059,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::set_update(&TypedValue::int(1), 1).unwrap()
+                - interpret_cost::set_update(&GasCosts::default(), &TypedValue::int(1), 1).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3349,7 +4081,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::set_update(&TypedValue::int(1), 1).unwrap()
+                - interpret_cost::set_update(&GasCosts::default(), &TypedValue::int(1), 1).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3370,7 +4102,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::set_update(&TypedValue::int(1), 0).unwrap()
+                - interpret_cost::set_update(&GasCosts::default(), &TypedValue::int(1), 0).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3398,7 +4130,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::map_update(&V::int(1), 0).unwrap()
+                - interpret_cost::map_update(&GasCosts::default(), &V::int(1), 0).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3426,7 +4158,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::map_update(&V::int(1), 1).unwrap()
+                - interpret_cost::map_update(&GasCosts::default(), &V::int(1), 1).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3444,7 +4176,7 @@ mod interpreter_tests {
         assert_eq!(
             ctx.gas.milligas(),
             Gas::default().milligas()
-                - interpret_cost::map_update(&V::int(1), 1).unwrap()
+                - interpret_cost::map_update(&GasCosts::default(), &V::int(1), 1).unwrap()
                 - interpret_cost::INTERPRET_RET
         );
     }
@@ -3464,6 +4196,22 @@ mod interpreter_tests {
         );
     }
 
+    #[test]
+    fn size_string_counts_bytes() {
+        // string literals are restricted to printable ASCII, but a string
+        // constructed otherwise may contain multi-byte characters
+        let mut stack = stk![TypedValue::String("é".into())];
+        assert_eq!(
+            interpret(
+                &[Size(overloads::Size::String)],
+                &mut Ctx::default(),
+                &mut stack
+            ),
+            Ok(())
+        );
+        assert_eq!(stack, stk![TypedValue::nat(2)]);
+    }
+
     #[test]
     fn size_bytes() {
         let mut ctx = Ctx::default();
@@ -3544,7 +4292,8 @@ mod interpreter_tests {
         );
         assert_eq!(
             ctx.gas.milligas(),
-            Gas::default().milligas() - interpret_cost::map_get_and_update(&V::int(1), 0).unwrap()
+            Gas::default().milligas()
+                - interpret_cost::map_get_and_update(&GasCosts::default(), &V::int(1), 0).unwrap()
         );
     }
 
@@ -3574,7 +4323,8 @@ mod interpreter_tests {
         );
         assert_eq!(
             ctx.gas.milligas(),
-            Gas::default().milligas() - interpret_cost::map_get_and_update(&V::int(1), 1).unwrap()
+            Gas::default().milligas()
+                - interpret_cost::map_get_and_update(&GasCosts::default(), &V::int(1), 1).unwrap()
         );
     }
 
@@ -3600,7 +4350,8 @@ mod interpreter_tests {
         );
         assert_eq!(
             ctx.gas.milligas(),
-            Gas::default().milligas() - interpret_cost::map_get_and_update(&V::int(1), 1).unwrap()
+            Gas::default().milligas()
+                - interpret_cost::map_get_and_update(&GasCosts::default(), &V::int(1), 1).unwrap()
         );
     }
 
@@ -3869,6 +4620,34 @@ mod interpreter_tests {
         assert_eq!(stack, stk![TypedValue::String("abcdef".into())]);
     }
 
+    #[test]
+    fn concat_empty_strings() {
+        let mut stack = stk![
+            TypedValue::String("abc".into()),
+            TypedValue::String("".into()),
+        ];
+        assert_eq!(
+            interpret(
+                &[Concat(overloads::Concat::TwoStrings)],
+                &mut Ctx::default(),
+                &mut stack
+            ),
+            Ok(())
+        );
+        assert_eq!(stack, stk![TypedValue::String("abc".into())]);
+
+        let mut stack = stk![TypedValue::String("".into()), TypedValue::String("".into()),];
+        assert_eq!(
+            interpret(
+                &[Concat(overloads::Concat::TwoStrings)],
+                &mut Ctx::default(),
+                &mut stack
+            ),
+            Ok(())
+        );
+        assert_eq!(stack, stk![TypedValue::String("".into())]);
+    }
+
     #[test]
     fn concat_two_bytes() {
         let mut stack = stk![
@@ -4123,12 +4902,16 @@ mod interpreter_tests {
     #[test]
     fn check_signature() {
         for (key, msg, sig, res) in michelson_signature::tests::signature_fixtures() {
+            let expected_cost =
+                interpret_cost::check_signature(&GasCosts::default(), &key, msg).unwrap();
             let mut stack = stk![V::Bytes(msg.to_vec()), V::Signature(sig), V::Key(key)];
+            let ctx = &mut Ctx::default();
+            assert_eq!(interpret_one(&CheckSignature, ctx, &mut stack), Ok(()));
+            assert_eq!(stack, stk![V::Bool(res)]);
             assert_eq!(
-                interpret_one(&CheckSignature, &mut Ctx::default(), &mut stack),
-                Ok(())
+                ctx.gas.milligas(),
+                Gas::default().milligas() - expected_cost
             );
-            assert_eq!(stack, stk![V::Bool(res)])
         }
     }
 
@@ -4531,8 +5314,12 @@ mod interpreter_tests {
         );
         assert_eq!(
             start_milligas - ctx.gas.milligas(),
-            interpret_cost::split_ticket(&ticket_exp_left.amount, &ticket_exp_right.amount)
-                .unwrap()
+            interpret_cost::split_ticket(
+                &GasCosts::default(),
+                &ticket_exp_left.amount,
+                &ticket_exp_right.amount
+            )
+            .unwrap()
                 + interpret_cost::INTERPRET_RET
         );
 
@@ -4574,7 +5361,7 @@ mod interpreter_tests {
         assert_eq!(interpret(&[JoinTickets], &mut ctx, &mut stack), Ok(()));
         assert_eq!(
             start_milligas - ctx.gas.milligas(),
-            interpret_cost::join_tickets(&ticket, &ticket_right_).unwrap()
+            interpret_cost::join_tickets(&GasCosts::default(), &ticket, &ticket_right_).unwrap()
                 + interpret_cost::INTERPRET_RET
         );
 
@@ -4649,6 +5436,8 @@ mod interpreter_tests {
             Blake2b;
             "00" => "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314";
             "deadbeef" => "f3e925002fed7cc0ded46842569eb5c90c910c091d8d04a1bdf96e0db719fd91";
+            "" => "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8";
+            "616263" => "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319";
         );
         test!(
             Keccak;
@@ -4659,6 +5448,8 @@ mod interpreter_tests {
             Sha256;
             "00" => "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d";
             "deadbeef" => "5f78c33274e43fa9de5659265c1d917e25c03722dcb0b8d27db8d5feaa813953";
+            "" => "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+            "616263" => "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
         );
         test!(
             Sha3;
@@ -4669,6 +5460,8 @@ mod interpreter_tests {
             Sha512;
             "00" => "b8244d028981d693af7b456af8efa4cad63d282e19ff14942c246e50d9351d22704a802a71c3580b6370de4ceb293c324a8423342557d4e5c38438f0e36910ee";
             "deadbeef" => "1284b2d521535196f22175d5f558104220a6ad7680e78b49fa6f20e57ea7b185d71ec1edb137e70eba528dedb141f5d2f8bb53149d262932b27cf41fed96aa7f";
+            "" => "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e";
+            "616263" => "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
         );
     }
 
@@ -4727,7 +5520,12 @@ mod interpreter_tests {
             stk![TypedValue::new_option(Some(V::Contract(addr)))],
             Contract(Type::Unit, Entrypoint::default()),
             Some(
-                tc_cost::ty_eq(Type::Int.size_for_gas(), Type::Int.size_for_gas()).unwrap()
+                tc_cost::ty_eq(
+                    &GasCosts::default(),
+                    Type::Int.size_for_gas(),
+                    Type::Int.size_for_gas(),
+                )
+                .unwrap()
                     + interpret_cost::CONTRACT
                     + interpret_cost::INTERPRET_RET,
             ),
@@ -4742,7 +5540,12 @@ mod interpreter_tests {
             stk![TypedValue::new_option(Some(V::Contract(addr)))],
             Contract(Type::Int, Entrypoint::default()),
             Some(
-                tc_cost::ty_eq(Type::Int.size_for_gas(), Type::Int.size_for_gas()).unwrap()
+                tc_cost::ty_eq(
+                    &GasCosts::default(),
+                    Type::Int.size_for_gas(),
+                    Type::Int.size_for_gas(),
+                )
+                .unwrap()
                     + interpret_cost::CONTRACT
                     + interpret_cost::INTERPRET_RET,
             ),
@@ -4758,7 +5561,12 @@ mod interpreter_tests {
             stk![TypedValue::new_option(Some(V::Contract(addr)))],
             Contract(Type::Int, Entrypoint::default()),
             Some(
-                tc_cost::ty_eq(Type::Int.size_for_gas(), Type::Int.size_for_gas()).unwrap()
+                tc_cost::ty_eq(
+                    &GasCosts::default(),
+                    Type::Int.size_for_gas(),
+                    Type::Int.size_for_gas(),
+                )
+                .unwrap()
                     + interpret_cost::CONTRACT
                     + interpret_cost::INTERPRET_RET,
             ),
@@ -4778,7 +5586,12 @@ mod interpreter_tests {
             stk![TypedValue::new_option(Some(V::Contract(expected_address)))],
             Contract(Type::Int, Entrypoint::try_from("foo").unwrap()),
             Some(
-                tc_cost::ty_eq(Type::Int.size_for_gas(), Type::Int.size_for_gas()).unwrap()
+                tc_cost::ty_eq(
+                    &GasCosts::default(),
+                    Type::Int.size_for_gas(),
+                    Type::Int.size_for_gas(),
+                )
+                .unwrap()
                     + interpret_cost::CONTRACT
                     + interpret_cost::INTERPRET_RET,
             ),
@@ -5351,6 +6164,93 @@ mod interpreter_tests {
                 );
             }
         }
+        mod mutez_overflow {
+            use super::*;
+
+            #[track_caller]
+            fn test_overflow(overload: overloads::Mul, input1: TypedValue, input2: TypedValue) {
+                let mut stack = stk![input2, input1];
+                assert_eq!(
+                    interpret_one(&Mul(overload), &mut Ctx::default(), &mut stack),
+                    Err(InterpretError::MutezOverflow)
+                );
+            }
+
+            #[test]
+            fn mutez_nat() {
+                use overloads::Mul::*;
+                test_overflow(MutezNat, V::Mutez(i64::MAX), V::nat(2));
+                test_overflow(MutezNat, V::Mutez(2i64.pow(32)), V::nat(2u64.pow(31)));
+                test_overflow(MutezNat, V::Mutez(1), V::nat(2u64.pow(63)));
+            }
+
+            #[test]
+            fn nat_mutez() {
+                use overloads::Mul::*;
+                test_overflow(NatMutez, V::nat(2), V::Mutez(i64::MAX));
+                test_overflow(NatMutez, V::nat(2u64.pow(31)), V::Mutez(2i64.pow(32)));
+                test_overflow(NatMutez, V::nat(2u64.pow(63)), V::Mutez(1));
+            }
+
+            #[test]
+            fn no_overflow_at_limit() {
+                use overloads::Mul::*;
+                test_mul(MutezNat, V::Mutez(i64::MAX), V::nat(1), V::Mutez(i64::MAX));
+                test_mul(NatMutez, V::nat(1), V::Mutez(i64::MAX), V::Mutez(i64::MAX));
+            }
+        }
+
+        #[track_caller]
+        fn mul_int_gas(i1: &BigInt, i2: &BigInt) -> u32 {
+            let mut stack = stk![V::Int(i2.clone()), V::Int(i1.clone())];
+            let ctx = &mut Ctx::default();
+            assert_eq!(
+                interpret_one(&Mul(overloads::Mul::IntInt), ctx, &mut stack),
+                Ok(())
+            );
+            assert_eq!(stack, stk![V::Int(i1 * i2)]);
+            Ctx::default().gas.milligas() - ctx.gas.milligas()
+        }
+
+        #[test]
+        fn big_ints() {
+            let i1: BigInt = "1234567890".repeat(10).parse().unwrap();
+            let i2: BigInt = -"9876543210".repeat(10).parse::<BigInt>().unwrap();
+            let mut stack = stk![V::Int(i2.clone()), V::Int(i1.clone())];
+            let ctx = &mut Ctx::default();
+            assert_eq!(
+                interpret_one(&Mul(overloads::Mul::IntInt), ctx, &mut stack),
+                Ok(())
+            );
+            assert_eq!(
+                stack,
+                stk![V::Int(
+                    "-1219326311370217952261850327338667885945115073915636335923676116445578859929\
+                     8790108215200135650052123609205801112635258986434993786160646167367779295611\
+                     949397448712086533622923332237463801111263526900"
+                        .parse()
+                        .unwrap()
+                )]
+            );
+            assert_eq!(
+                Ctx::default().gas.milligas() - ctx.gas.milligas(),
+                interpret_cost::mul_int(&GasCosts::default(), &i1, &i2).unwrap()
+            );
+        }
+
+        #[test]
+        fn gas_grows_with_operand_size() {
+            let small = BigInt::from(12345);
+            let medium: BigInt = "1234567890".repeat(5).parse().unwrap();
+            let large: BigInt = "1234567890".repeat(10).parse().unwrap();
+            let costs = [
+                mul_int_gas(&small, &small),
+                mul_int_gas(&medium, &medium),
+                mul_int_gas(&large, &large),
+            ];
+            assert!(costs[0] < costs[1] && costs[1] < costs[2], "{costs:?}");
+            assert_eq!(mul_int_gas(&medium, &small), mul_int_gas(&small, &medium));
+        }
     }
 
     mod neg {
@@ -5456,6 +6356,26 @@ mod interpreter_tests {
         assert_eq!(stack, expected_stack);
     }
 
+    #[test]
+    fn test_dig_dug_zero() {
+        let mut ctx = Ctx::default();
+        for instr in [Dig(0), Dug(0)] {
+            let mut stack = stk![V::nat(1), V::nat(2)];
+            assert_eq!(interpret_one(&instr, &mut ctx, &mut stack), Ok(()));
+            assert_eq!(stack, stk![V::nat(1), V::nat(2)]);
+        }
+    }
+
+    #[test]
+    fn test_dig_middle() {
+        // a is on top
+        let (a, b, c, d) = (V::nat(1), V::nat(2), V::nat(3), V::nat(4));
+        let mut stack = stk![d.clone(), c.clone(), b.clone(), a.clone()];
+        let mut ctx = Ctx::default();
+        assert_eq!(interpret_one(&Dig(2), &mut ctx, &mut stack), Ok(()));
+        assert_eq!(stack, stk![d, b, a, c]);
+    }
+
     #[test]
     fn unpack() {
         let mut stack = stk![V::Bytes(hex::decode("0500f1a2f3ad07").unwrap())];
@@ -37,7 +37,8 @@ macro_rules! coalesce {
 /// representation of the identifiers.
 macro_rules! defprim {
     ($(#[$meta:meta])* $ty:ident; $($(#[token($str:expr)])? $prim:ident),* $(,)*) => {
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumCount)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize))]
         #[allow(non_camel_case_types, clippy::upper_case_acronyms, missing_docs)]
         #[repr(u8)]
         $(#[$meta])*
@@ -116,6 +117,15 @@ defprim! {
     // If you add anything here, see the note about the order above.
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Prim {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let id = u.choose_index(<Prim as strum::EnumCount>::COUNT)? as u8;
+        // SAFETY: Prim is repr(u8), and id is within bounds.
+        Ok(unsafe { std::mem::transmute::<u8, Prim>(id) })
+    }
+}
+
 impl Prim {
     /// Write the primitive identifier (as per Micheline binary encoding) into
     /// the output vector.
@@ -171,7 +181,9 @@ pub(crate) fn try_ann_from_str(value: &str) -> Option<Annotation> {
 
 /// Tokens representing Michelson lexemes.
 #[derive(Debug, Clone, PartialEq, Eq, Logos)]
-#[logos(error = LexerError, skip r"[ \t\r\n\v\f]+|#[^\n]*\n")]
+// Skips whitespace, line comments `# ...` and block comments `/* ... */`.
+// Block comments don't nest.
+#[logos(error = LexerError, skip r"[ \t\r\n\v\f]+|#[^\n]*|/\*[^*]*\*+([^/*][^*]*\*+)*/")]
 pub enum Tok<'a> {
     /// A primitive token: a Micheline primitive, TZT primitive, or a macro
     /// token.
@@ -246,7 +258,7 @@ fn lex_macro(lex: &mut Lexer) -> Result<Macro, PrimError> {
     let mut inner = Macro::lexer(slice);
     let next = inner.next().ok_or_else(|| PrimError(slice.to_string()))?;
     // check if lexed token is at EOF
-    if matches!(inner.next(), Some(_)) {
+    if inner.next().is_some() {
         return Err(PrimError(slice.to_string()));
     }
     next.map_err(|_| PrimError(slice.to_string()))
@@ -274,6 +286,8 @@ fn lex_string(lex: &mut Lexer) -> Result<String, LexerError> {
     let s = lex.slice();
     // strip the quotes
     let s = &s[1..s.len() - 1];
+    // offset of the first character after the opening quote
+    let start = lex.span().start + 1;
 
     // check if all characters are printable ASCII
     if !s.chars().all(|c| matches!(c, ' '..='~')) {
@@ -284,18 +298,21 @@ fn lex_string(lex: &mut Lexer) -> Result<String, LexerError> {
     // this may overreserve, but no more than 2x
     res.reserve(s.len());
 
-    let unescape_char = |c| match c {
+    // `ix` is the offset of the escaped character; the string is ASCII, so
+    // character and byte offsets coincide.
+    let unescape_char = |c, ix: usize| match c {
         'n' => Ok('\n'),
         'r' => Ok('\r'),
+        't' => Ok('\t'),
         '"' => Ok('"'),
         '\\' => Ok('\\'),
-        _ => Err(LexerError::UndefinedEscape(c)),
+        _ => Err(LexerError::UndefinedEscape(c, start + ix - 1)),
     };
 
     let mut in_escape: bool = false;
-    for c in s.chars() {
+    for (ix, c) in s.chars().enumerate() {
         if in_escape {
-            res.push(unescape_char(c)?);
+            res.push(unescape_char(c, ix)?);
             in_escape = false;
         } else if matches!(c, '\\') {
             in_escape = true;
@@ -310,7 +327,10 @@ fn lex_string(lex: &mut Lexer) -> Result<String, LexerError> {
 /// Takes a lexed slice of hexadecimal digits prefixed by `0x`, removes the
 /// prefix and converts the digits pairwise to `u8`.
 fn lex_bytes(lex: &mut Lexer) -> Result<Vec<u8>, LexerError> {
-    Ok(hex::decode(&lex.slice()[2..])?)
+    hex::decode(&lex.slice()[2..]).map_err(|e| {
+        let span = lex.span();
+        LexerError::InvalidHex(e, span.start, span.end)
+    })
 }
 
 fn lex_annotation<'a>(lex: &mut Lexer<'a>) -> Annotation<'a> {
@@ -339,6 +359,7 @@ mod tests {
         assert_parse!(r#""foo\nbar""#, Ok("foo\nbar"));
         assert_parse!(r#""foo\"bar\"""#, Ok("foo\"bar\""));
         assert_parse!(r#""foo\rbar""#, Ok("foo\rbar"));
+        assert_parse!(r#""foo\tbar""#, Ok("foo\tbar"));
         assert_parse!(r#""foo\\rbar""#, Ok("foo\\rbar"));
         assert_parse!(r#""foo\\nbar""#, Ok("foo\\nbar"));
         assert_parse!(r#""foo\\\\bar""#, Ok("foo\\\\bar"));
@@ -348,7 +369,14 @@ mod tests {
             Err("forbidden character found in string literal \"हिन्दी\"")
         );
         // unknown escapes are not accepted
-        assert_parse!(r#""\a""#, Err("undefined escape sequence: \"\\a\""));
+        assert_parse!(
+            r#""\a""#,
+            Err("undefined escape sequence: \"\\a\" found at 1")
+        );
+        assert_parse!(
+            r#""foo\x""#,
+            Err("undefined escape sequence: \"\\x\" found at 4")
+        );
         // unterminated strings are not accepted
         assert_parse!(r#"""#, Err("unknown token"));
         assert_parse!(r#""\""#, Err("unknown token"));
@@ -377,7 +405,10 @@ mod tests {
         assert_parse("0x0000", Ok([0x00, 0x00]));
         assert_parse("0xabcd", Ok([0xab, 0xcd]));
         assert_parse("0x", Ok([]));
-        assert_parse::<0>("0x1", Err("invalid hex sequence: Odd number of digits"));
+        assert_parse::<0>(
+            "0x1",
+            Err("invalid hex sequence: Odd number of digits found at 0:3"),
+        );
         assert_parse::<0>("0xzz", Err("unknown primitive: zz"));
     }
 }
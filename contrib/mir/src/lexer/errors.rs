@@ -26,13 +26,19 @@ pub enum LexerError {
     /// Found a forbidden character in a string literal.
     #[error("forbidden character found in string literal \"{0}\"")]
     ForbiddenCharacterIn(String),
-    /// Found an undefined escape sequence in a string literal.
-    #[error("undefined escape sequence: \"\\{0}\"")]
-    UndefinedEscape(char),
+    /// Found an undefined escape sequence in a string literal. Carries the
+    /// escaped character and the offset of the backslash in the source.
+    #[error("undefined escape sequence: \"\\{0}\" found at {1}")]
+    UndefinedEscape(char, usize),
     /// Unknown primitive.
     #[error(transparent)]
     PrimError(#[from] PrimError),
-    /// Invalid hexadecimal sequence in a byte literal.
-    #[error("invalid hex sequence: {0}")]
-    InvalidHex(#[from] hex::FromHexError),
+    /// A block comment is not terminated. Carries the offset of the comment
+    /// start in the source.
+    #[error("unterminated comment starting at {0}")]
+    UnterminatedComment(usize),
+    /// Invalid hexadecimal sequence in a byte literal. Carries the start and
+    /// end offsets of the literal in the source.
+    #[error("invalid hex sequence: {0} found at {1}:{2}")]
+    InvalidHex(hex::FromHexError, usize, usize),
 }
@@ -16,13 +16,8 @@
 //! The library is currently incomplete. The following instructions are not
 //! supported:
 //!
-//! - `ADD: timestamp : int`
-//! - `ADD: int : timestamp`
-//! - `SUB`
-//! - `EDIV`
-//! - `LSL`
-//! - `LSR`
-//! - `EMPTY_MAP`
+//! - `LSL: bytes : nat`
+//! - `LSR: bytes : nat`
 //! - `SAPLING_EMPTY_STATE`
 //! - `SAPLING_VERIFY_UPDATE`
 //! - `OPEN_CHEST`
@@ -178,6 +173,42 @@ mod tests {
         r
     }
 
+    /// Parse, typecheck and interpret `src` on an empty stack, with the default
+    /// context.
+    #[track_caller]
+    fn run<'a>(
+        arena: &'a Arena<Micheline<'a>>,
+        src: &'a str,
+    ) -> Result<Stack<TypedValue<'a>>, interpreter::InterpretError<'a>> {
+        run_in(&mut Ctx::default(), arena, src)
+    }
+
+    /// Same as [run], but with the given context.
+    #[track_caller]
+    fn run_in<'a>(
+        ctx: &mut Ctx<'a>,
+        arena: &'a Arena<Micheline<'a>>,
+        src: &'a str,
+    ) -> Result<Stack<TypedValue<'a>>, interpreter::InterpretError<'a>> {
+        let ast = parse(src)
+            .unwrap()
+            .typecheck_instruction(ctx, None, &[])
+            .unwrap();
+        let mut istack = stk![];
+        ast.interpret(ctx, arena, &mut istack)?;
+        Ok(istack)
+    }
+
+    /// Parse and typecheck `src` for the input stack `stack`, with the default
+    /// context.
+    #[track_caller]
+    fn typechecked<'a>(src: &'a str, stack: &[Micheline<'a>]) -> Instruction<'a> {
+        parse(src)
+            .unwrap()
+            .typecheck_instruction(&mut Ctx::default(), None, stack)
+            .unwrap()
+    }
+
     #[test]
     fn interpret_test_expect_success() {
         let ast = parse(FIBONACCI_SRC).unwrap();
@@ -192,6 +223,21 @@ mod tests {
         assert!(istack.len() == 1 && istack[0] == TypedValue::int(55));
     }
 
+    #[test]
+    fn typecheck_with_output() {
+        let ast = parse(FIBONACCI_SRC).unwrap();
+        let (_, output) = ast
+            .typecheck_with_output(&mut Ctx::default(), None, &[app!(nat)])
+            .unwrap();
+        assert_eq!(output, tc_stk![Type::Int]);
+
+        let ast = parse("{ PUSH int 1; FAILWITH }").unwrap();
+        let (_, output) = ast
+            .typecheck_with_output(&mut Ctx::default(), None, &[])
+            .unwrap();
+        assert_eq!(output, FailingTypeStack::Failed);
+    }
+
     #[test]
     fn interpret_mutez_push_add() {
         let ast = parse("{ PUSH mutez 100; PUSH mutez 500; ADD }").unwrap();
@@ -203,6 +249,478 @@ mod tests {
         assert_eq!(istack, stk![TypedValue::Mutez(600)]);
     }
 
+    #[test]
+    fn interpret_string_push() {
+        assert_eq!(
+            run(&Arena::new(), r#"{ PUSH string "hello" }"#),
+            Ok(stk![TypedValue::String("hello".to_owned())])
+        );
+    }
+
+    #[test]
+    fn interpret_string_push_concat() {
+        assert_eq!(
+            run(
+                &Arena::new(),
+                r#"{ PUSH string "foo"; PUSH string "bar"; CONCAT }"#
+            ),
+            Ok(stk![TypedValue::String("barfoo".to_owned())])
+        );
+    }
+
+    #[test]
+    fn interpret_string_push_slice() {
+        // SLICE takes the offset from the top of the stack, then the length
+        assert_eq!(
+            run(
+                &Arena::new(),
+                r#"{ PUSH string "foobar"; PUSH nat 2; PUSH nat 3; SLICE }"#
+            ),
+            Ok(stk![TypedValue::new_option(Some(TypedValue::String(
+                "ba".to_owned()
+            )))])
+        );
+    }
+
+    #[test]
+    fn interpret_push_pair_unpair() {
+        assert_eq!(
+            run(
+                &Arena::new(),
+                "{ PUSH nat 2; PUSH int 1; PAIR; DUP; UNPAIR }"
+            ),
+            Ok(stk![
+                TypedValue::new_pair(TypedValue::int(1), TypedValue::nat(2)),
+                TypedValue::nat(2),
+                TypedValue::int(1)
+            ])
+        );
+    }
+
+    #[test]
+    fn interpret_push_pair_car_cdr() {
+        for (prim, expected) in [("CAR", TypedValue::int(1)), ("CDR", TypedValue::nat(2))] {
+            let src = format!("{{ PUSH (pair int nat) (Pair 1 2); {prim} }}");
+            assert_eq!(run(&Arena::new(), &src), Ok(stk![expected]));
+        }
+    }
+
+    #[test]
+    fn interpret_if_none() {
+        for (opt, expected) in [("NONE int", 0), ("PUSH int 5; SOME", 6)] {
+            let src = format!("{{ {opt}; IF_NONE {{ PUSH int 0 }} {{ PUSH int 1; ADD }} }}");
+            assert_eq!(
+                run(&Arena::new(), &src),
+                Ok(stk![TypedValue::int(expected)])
+            );
+        }
+    }
+
+    #[test]
+    fn interpret_if_left() {
+        for (or, expected) in [
+            ("PUSH nat 5; LEFT string", 5),
+            (r#"PUSH string "abc"; RIGHT nat"#, 3),
+        ] {
+            let src = format!("{{ {or}; IF_LEFT {{ INT }} {{ SIZE; INT }} }}");
+            assert_eq!(
+                run(&Arena::new(), &src),
+                Ok(stk![TypedValue::int(expected)])
+            );
+        }
+    }
+
+    #[test]
+    fn interpret_if_cons() {
+        // sum the first two elements, if any
+        let code = "IF_CONS { SWAP; IF_CONS { DIP { DROP }; ADD } { } } { PUSH int 0 }";
+        for (list, expected) in [("{ 1; 2; 3 }", 3), ("{ 5 }", 5), ("{}", 0)] {
+            let src = format!("{{ PUSH (list int) {list}; {code} }}");
+            assert_eq!(
+                run(&Arena::new(), &src),
+                Ok(stk![TypedValue::int(expected)])
+            );
+        }
+    }
+
+    #[test]
+    fn interpret_iter_sum() {
+        assert_eq!(
+            run(
+                &Arena::new(),
+                "{ PUSH (list int) { 1; 2; 3 }; PUSH int 0; SWAP; ITER { ADD } }"
+            ),
+            Ok(stk![TypedValue::int(6)])
+        );
+    }
+
+    #[test]
+    fn interpret_iter_empty() {
+        assert_eq!(
+            run(&Arena::new(), "{ PUSH int 0; NIL int; ITER { ADD } }"),
+            Ok(stk![TypedValue::int(0)])
+        );
+    }
+
+    #[test]
+    fn interpret_map_list() {
+        for (list, expected) in [("{ 1; 2; 3 }", vec![2, 3, 4]), ("{}", vec![])] {
+            let src = format!("{{ PUSH (list int) {list}; MAP {{ PUSH int 1; ADD }} }}");
+            let expected = expected
+                .into_iter()
+                .map(TypedValue::int)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                run(&Arena::new(), &src),
+                Ok(stk![TypedValue::List(expected.into())])
+            );
+        }
+    }
+
+    #[test]
+    fn interpret_compare() {
+        for (x, y, expected) in [
+            ("\"abc\"", "\"abd\"", -1),
+            ("\"abc\"", "\"abc\"", 0),
+            ("\"b\"", "\"abc\"", 1),
+        ] {
+            // COMPARE compares the top of the stack to the element below it
+            let src = format!("{{ PUSH string {y}; PUSH string {x}; COMPARE }}");
+            assert_eq!(
+                run(&Arena::new(), &src),
+                Ok(stk![TypedValue::int(expected)])
+            );
+        }
+    }
+
+    #[test]
+    fn interpret_compare_unit() {
+        assert_eq!(
+            run(&Arena::new(), "{ PUSH unit Unit; UNIT; COMPARE }"),
+            Ok(stk![TypedValue::int(0)])
+        );
+    }
+
+    #[test]
+    fn interpret_map() {
+        let src = r#"{ EMPTY_MAP nat string ;
+                       PUSH (option string) (Some "b") ; PUSH nat 2 ; UPDATE ;
+                       PUSH (option string) (Some "a") ; PUSH nat 1 ; UPDATE ;
+                       PUSH (option string) (Some "c") ; PUSH nat 3 ; UPDATE ;
+                       PUSH (option string) None ; PUSH nat 3 ; UPDATE ;
+                       DUP ; PUSH nat 1 ; GET ;
+                       SWAP ; DUP ; PUSH nat 3 ; MEM ;
+                       SWAP ; PUSH string "" ; SWAP ; ITER { CDR ; SWAP ; CONCAT } }"#;
+        // bindings are iterated in key order, regardless of insertion order
+        assert_eq!(
+            run(&Arena::new(), src),
+            Ok(stk![
+                TypedValue::new_option(Some(TypedValue::String("a".to_owned()))),
+                TypedValue::Bool(false),
+                TypedValue::String("ab".to_owned())
+            ])
+        );
+    }
+
+    #[test]
+    fn interpret_big_map() {
+        let src = r#"{ EMPTY_BIG_MAP nat string ;
+                       PUSH (option string) (Some "b") ; PUSH nat 2 ; UPDATE ;
+                       PUSH (option string) (Some "a") ; PUSH nat 1 ; UPDATE ;
+                       PUSH (option string) None ; PUSH nat 2 ; UPDATE ;
+                       DUP ; PUSH nat 1 ; GET ;
+                       SWAP ; DUP ; PUSH nat 2 ; MEM ;
+                       SWAP ; PUSH nat 3 ; GET }"#;
+        assert_eq!(
+            run(&Arena::new(), src),
+            Ok(stk![
+                TypedValue::new_option(Some(TypedValue::String("a".to_owned()))),
+                TypedValue::Bool(false),
+                TypedValue::new_option(None)
+            ])
+        );
+    }
+
+    #[test]
+    fn interpret_set() {
+        let src = "{ EMPTY_SET nat ;
+                     PUSH bool True ; PUSH nat 3 ; UPDATE ;
+                     PUSH bool True ; PUSH nat 1 ; UPDATE ;
+                     PUSH bool True ; PUSH nat 3 ; UPDATE ;
+                     PUSH bool False ; PUSH nat 7 ; UPDATE ;
+                     PUSH bool True ; PUSH nat 5 ; UPDATE ;
+                     PUSH bool False ; PUSH nat 5 ; UPDATE ;
+                     DUP ; SIZE ; SWAP ; DUP ; PUSH nat 5 ; MEM ;
+                     SWAP ; PUSH nat 3 ; MEM }";
+        assert_eq!(
+            run(&Arena::new(), src),
+            Ok(stk![
+                TypedValue::nat(2),
+                TypedValue::Bool(false),
+                TypedValue::Bool(true)
+            ])
+        );
+    }
+
+    #[test]
+    fn interpret_timestamp_arith() {
+        // both literal forms denote the same timestamp
+        let src = r#"{ PUSH int 60 ; PUSH timestamp "2019-09-26T10:59:51Z" ; ADD ;
+                       PUSH timestamp 1569495591 ; SWAP ; SUB }"#;
+        assert_eq!(run(&Arena::new(), src), Ok(stk![TypedValue::int(60)]));
+    }
+
+    #[test]
+    fn interpret_lambda_exec() {
+        #[track_caller]
+        fn exec_cost(body: &str, expected: TypedValue) -> u32 {
+            let src = format!("{{ LAMBDA int int {body}; PUSH int 41; EXEC }}");
+            let ast = typechecked(&src, &[]);
+            let temp = Arena::new();
+            let mut ctx = Ctx::default();
+            let mut istack = stk![];
+            assert!(ast.interpret(&mut ctx, &temp, &mut istack).is_ok());
+            assert_eq!(istack, stk![expected]);
+            Gas::default().milligas() - ctx.gas.milligas()
+        }
+        let cost = exec_cost("{ PUSH int 1; ADD }", TypedValue::int(42));
+        // the nested execution is charged
+        assert!(cost > exec_cost("{}", TypedValue::int(41)));
+    }
+
+    #[test]
+    fn interpret_apply() {
+        let lambda = "LAMBDA (pair int int) int { UNPAIR; SUB }";
+        for src in [
+            format!("{{ {lambda}; PUSH int 1; APPLY; PUSH int 2; EXEC }}"),
+            format!("{{ {lambda}; PUSH (pair int int) (Pair 1 2); EXEC }}"),
+        ] {
+            assert_eq!(run(&Arena::new(), &src), Ok(stk![TypedValue::int(-1)]));
+        }
+    }
+
+    #[test]
+    fn interpret_loop_left_countdown() {
+        // count the iterations needed to get from 5 below zero
+        let src = "{ PUSH nat 0 ; PUSH nat 5 ; LEFT unit ;
+                     LOOP_LEFT { DIP { PUSH nat 1 ; ADD } ;
+                                 PUSH nat 1 ; SWAP ; SUB ; ISNAT ;
+                                 IF_NONE { UNIT ; RIGHT nat } { LEFT unit } } ;
+                     DROP }";
+        assert_eq!(run(&Arena::new(), src), Ok(stk![TypedValue::nat(6)]));
+    }
+
+    #[test]
+    fn interpret_pack_unpack() {
+        for (ty, val, expected) in [
+            ("int", "-42", TypedValue::int(-42)),
+            ("string", r#""foo""#, TypedValue::String("foo".to_owned())),
+            (
+                "pair nat bool",
+                "Pair 1 True",
+                TypedValue::new_pair(TypedValue::nat(1), TypedValue::Bool(true)),
+            ),
+            (
+                "list int",
+                "{ 1; 2 }",
+                TypedValue::List(vec![TypedValue::int(1), TypedValue::int(2)].into()),
+            ),
+        ] {
+            let src = format!("{{ PUSH ({ty}) ({val}); PACK; UNPACK ({ty}) }}");
+            assert_eq!(
+                run(&Arena::new(), &src),
+                Ok(stk![TypedValue::new_option(Some(expected))])
+            );
+        }
+    }
+
+    #[test]
+    fn interpret_unpack_truncated() {
+        // 1000 packs to 0x0500a80f, drop the last byte
+        let src = "{ PUSH int 1000 ; PACK ; PUSH nat 3 ; PUSH nat 0 ; SLICE ;
+                     IF_NONE { PUSH bytes 0x } {} ; UNPACK int }";
+        assert_eq!(
+            run(&Arena::new(), src),
+            Ok(stk![TypedValue::new_option(None)])
+        );
+    }
+
+    #[test]
+    fn interpret_map_iter_map_size() {
+        let map = r#"PUSH (map string int) { Elt "a" 1 ; Elt "b" 2 ; Elt "c" 3 }"#;
+        let expected_map = TypedValue::Map(
+            [("a", 2), ("b", 4), ("c", 6)]
+                .into_iter()
+                .map(|(k, v)| (TypedValue::String(k.to_owned()), TypedValue::int(v)))
+                .collect(),
+        );
+        for (code, expected) in [
+            // sum the values
+            ("PUSH int 0 ; SWAP ; ITER { CDR ; ADD }", TypedValue::int(6)),
+            // double the values
+            ("MAP { CDR ; PUSH int 2 ; MUL }", expected_map),
+            ("SIZE", TypedValue::nat(3)),
+        ] {
+            let src = format!("{{ {map} ; {code} }}");
+            assert_eq!(run(&Arena::new(), &src), Ok(stk![expected]));
+        }
+    }
+
+    #[test]
+    fn interpret_reuse_truncated_stack() {
+        let ast = typechecked("{ DROP ; PUSH int 1 ; PUSH int 2 }", &[app!(unit)]);
+        let temp = Arena::new();
+        let mut ctx = Ctx::default();
+        let mut istack = stk![TypedValue::String("bottom".to_owned()), TypedValue::Unit];
+        for _ in 0..2 {
+            assert!(ast.interpret(&mut ctx, &temp, &mut istack).is_ok());
+            assert_eq!(
+                istack,
+                stk![
+                    TypedValue::String("bottom".to_owned()),
+                    TypedValue::int(1),
+                    TypedValue::int(2)
+                ]
+            );
+            istack.truncate_to(1);
+            istack.push(TypedValue::Unit);
+        }
+    }
+
+    #[test]
+    fn interpret_chain_id_compare_pack() {
+        let src = r#"{ CHAIN_ID; PUSH chain_id "NetXynUjJNZm7wi"; COMPARE;
+                       CHAIN_ID; PACK; UNPACK chain_id;
+                       IF_NONE { UNIT; FAILWITH } { CHAIN_ID; COMPARE } }"#;
+        assert_eq!(
+            run(&Arena::new(), src),
+            Ok(stk![TypedValue::int(0), TypedValue::int(0)])
+        );
+    }
+
+    #[test]
+    fn interpret_hash_key_compare() {
+        let src = r#"{ PUSH key "edpktxDQJUF9AqUegbhhD9zJWBCPRJ3PtewuwiuAxrnaQbRmdi2tW1"; HASH_KEY;
+                       PUSH key_hash "tz1Nw5nr152qddEjKT2dKBH8XcBMDAg72iLw"; COMPARE }"#;
+        assert_eq!(run(&Arena::new(), src), Ok(stk![TypedValue::int(0)]));
+    }
+
+    #[test]
+    fn interpret_numeric_coercions() {
+        for (code, expected) in [
+            ("PUSH nat 5; INT", TypedValue::int(5)),
+            ("PUSH bls12_381_fr 7; INT", TypedValue::int(7)),
+            ("PUSH bytes 0xff; INT", TypedValue::int(-1)),
+            ("PUSH bytes 0x0102; NAT", TypedValue::nat(258)),
+            ("PUSH nat 258; BYTES", TypedValue::Bytes(vec![1, 2])),
+            ("PUSH int -1; BYTES", TypedValue::Bytes(vec![0xff])),
+            ("PUSH int -7; ABS", TypedValue::nat(7)),
+            (
+                "PUSH int 3; ISNAT",
+                TypedValue::new_option(Some(TypedValue::nat(3))),
+            ),
+            ("PUSH int -3; ISNAT", TypedValue::new_option(None)),
+            ("PUSH nat 3; NEG", TypedValue::int(-3)),
+        ] {
+            let src = format!("{{ {code} }}");
+            assert_eq!(run(&Arena::new(), &src), Ok(stk![expected]), "{code}");
+        }
+    }
+
+    #[test]
+    fn interpret_bitwise() {
+        for (op, expected) in [("AND", 8), ("OR", 14), ("XOR", 6)] {
+            let src = format!("{{ PUSH nat 12; PUSH nat 10; {op} }}");
+            assert_eq!(
+                run(&Arena::new(), &src),
+                Ok(stk![TypedValue::nat(expected)])
+            );
+        }
+    }
+
+    #[test]
+    fn interpret_not_int() {
+        assert_eq!(
+            run(&Arena::new(), "{ PUSH int 5; NOT }"),
+            Ok(stk![TypedValue::int(-6)])
+        );
+    }
+
+    #[test]
+    fn interpret_failwith() {
+        assert_eq!(
+            run(&Arena::new(), r#"{ PUSH string "boom"; FAILWITH }"#),
+            Err(interpreter::InterpretError::FailedWith(
+                Type::String,
+                TypedValue::String("boom".to_owned())
+            ))
+        );
+    }
+
+    #[test]
+    fn interpret_failwith_in_branch() {
+        // the failing branch doesn't constrain the stack after IF
+        assert_eq!(
+            run(
+                &Arena::new(),
+                r#"{ PUSH string "boom"; PUSH bool False; IF { FAILWITH } { DROP; PUSH int 1 }; PUSH int 2; ADD }"#
+            ),
+            Ok(stk![TypedValue::int(3)])
+        );
+    }
+
+    #[test]
+    fn interpret_bytes_push() {
+        assert_eq!(
+            run(&Arena::new(), "{ PUSH bytes 0xdeadbeef }"),
+            Ok(stk![TypedValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef])])
+        );
+    }
+
+    #[test]
+    fn interpret_mutez_push_mul() {
+        assert_eq!(
+            run(&Arena::new(), "{ PUSH mutez 100; PUSH nat 3; MUL }"),
+            Ok(stk![TypedValue::Mutez(300)])
+        );
+    }
+
+    #[test]
+    fn interpret_nat_push_sub() {
+        assert_eq!(
+            run(&Arena::new(), "{ PUSH nat 3; PUSH nat 5; SUB }"),
+            Ok(stk![TypedValue::int(2)])
+        );
+    }
+
+    #[test]
+    fn interpret_mutez_push_sub_underflow() {
+        assert_eq!(
+            run(&Arena::new(), "{ PUSH mutez 5; PUSH mutez 3; SUB }"),
+            Err(interpreter::InterpretError::MutezUnderflow)
+        );
+    }
+
+    #[test]
+    fn interpret_int_push_ediv() {
+        // EDIV divides the top of the stack by the element below it
+        assert_eq!(
+            run(&Arena::new(), "{ PUSH int 2; PUSH int 7; EDIV }"),
+            Ok(stk![TypedValue::new_option(Some(TypedValue::new_pair(
+                TypedValue::int(3),
+                TypedValue::nat(1)
+            )))])
+        );
+    }
+
+    #[test]
+    fn interpret_int_push_ediv_by_zero() {
+        assert_eq!(
+            run(&Arena::new(), "{ PUSH int 0; PUSH int 7; EDIV }"),
+            Ok(stk![TypedValue::new_option(None)])
+        );
+    }
+
     #[test]
     fn interpret_test_gas_consumption() {
         let ast = parse(FIBONACCI_SRC).unwrap();
@@ -234,6 +752,155 @@ mod tests {
         );
     }
 
+    #[test]
+    fn interpret_test_gas_costs() {
+        use crate::gas::GasCosts;
+        let src = format!("{{ PUSH nat 5 ; {FIBONACCI_SRC} }}");
+        let consumed = |costs| {
+            let temp = Arena::new();
+            let mut ctx = Ctx::default();
+            ctx.gas = Gas::default().with_costs(costs);
+            assert_eq!(run_in(&mut ctx, &temp, &src), Ok(stk![TypedValue::int(5)]));
+            Gas::default().milligas() - ctx.gas.milligas()
+        };
+        let default = consumed(GasCosts::default());
+        let doubled = consumed(GasCosts::default().map(|cost| 2 * cost));
+        assert_eq!(doubled, 2 * default);
+        // `INT` runs once, so a dearer one costs exactly that much more
+        let mut costs = GasCosts::default();
+        costs.interpret.int_nat += 1000;
+        assert_eq!(consumed(costs), default + 1000);
+    }
+
+    #[test]
+    fn interpret_custom_stack() {
+        use crate::stack::{IStack, Stack, StackOps};
+        use std::ops::{Index, IndexMut};
+
+        /// Records the maximum depth reached, including the elements
+        /// protected by `DIP`.
+        struct DepthCounting<'a> {
+            stack: IStack<'a>,
+            protected: usize,
+            max_depth: usize,
+        }
+
+        impl DepthCounting<'_> {
+            fn record(&mut self) {
+                self.max_depth = self.max_depth.max(self.stack.len() + self.protected);
+            }
+        }
+
+        impl<'a> Index<usize> for DepthCounting<'a> {
+            type Output = TypedValue<'a>;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                &self.stack[index]
+            }
+        }
+
+        impl IndexMut<usize> for DepthCounting<'_> {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                &mut self.stack[index]
+            }
+        }
+
+        impl<'a> StackOps<TypedValue<'a>> for DepthCounting<'a> {
+            fn push(&mut self, elt: TypedValue<'a>) {
+                self.stack.push(elt);
+                self.record();
+            }
+
+            fn pop(&mut self) -> Option<TypedValue<'a>> {
+                self.stack.pop()
+            }
+
+            fn len(&self) -> usize {
+                self.stack.len()
+            }
+
+            fn remove(&mut self, i: usize) -> TypedValue<'a> {
+                self.stack.remove(i)
+            }
+
+            fn insert(&mut self, i: usize, e: TypedValue<'a>) {
+                self.stack.insert(i, e);
+                self.record();
+            }
+
+            fn swap(&mut self, i1: usize, i2: usize) {
+                self.stack.swap(i1, i2)
+            }
+
+            fn drop_top(&mut self, size: usize) {
+                self.stack.drop_top(size)
+            }
+
+            fn split_off(&mut self, size: usize) -> Stack<TypedValue<'a>> {
+                self.protected += size;
+                self.stack.split_off(size)
+            }
+
+            fn append(&mut self, other: &mut Stack<TypedValue<'a>>) {
+                self.protected -= other.len();
+                self.stack.append(other);
+            }
+        }
+
+        let ast = parse(FIBONACCI_SRC).unwrap();
+        let ast = ast
+            .typecheck_instruction(&mut Ctx::default(), None, &[app!(nat)])
+            .unwrap();
+        let mut istack = DepthCounting {
+            stack: stk![TypedValue::nat(5)],
+            protected: 0,
+            max_depth: 1,
+        };
+        let temp = Arena::new();
+        assert!(ast
+            .interpret(&mut Ctx::default(), &temp, &mut istack)
+            .is_ok());
+        assert_eq!(istack.stack, stk![TypedValue::int(5)]);
+        assert_eq!(istack.protected, 0);
+        assert_eq!(istack.max_depth, 4);
+    }
+
+    #[test]
+    fn interpret_trace() {
+        let ast = typechecked(FIBONACCI_SRC, &[app!(nat)]);
+        let mut istack = stk![TypedValue::nat(5)];
+        let temp = Arena::new();
+        let mut ctx = Ctx::default();
+        let steps = interpreter::trace(&ast, &mut ctx, &temp, &mut istack)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        // INT ; PUSH ; DUP ; GT ; IF
+        assert_eq!(steps.len(), 5);
+        assert_eq!(steps[1].stack, stk![TypedValue::int(5), TypedValue::int(0)]);
+        assert_eq!(steps[4].stack, stk![TypedValue::int(5)]);
+        assert_eq!(istack, stk![TypedValue::int(5)]);
+        // same gas as interpreting all at once
+        assert_eq!(Gas::default().milligas() - ctx.gas.milligas(), 1287);
+    }
+
+    #[test]
+    fn interpret_trace_out_of_gas() {
+        let ast = typechecked(FIBONACCI_SRC, &[app!(nat)]);
+        let mut istack = stk![TypedValue::nat(5)];
+        let temp = Arena::new();
+        let ctx = &mut Ctx::default();
+        ctx.gas = Gas::new(1000);
+        let steps: Vec<_> = interpreter::trace(&ast, ctx, &temp, &mut istack).collect();
+        assert!(steps.len() > 1);
+        assert!(steps[..steps.len() - 1].iter().all(Result::is_ok));
+        assert_eq!(
+            steps.last(),
+            Some(&Err(interpreter::InterpretError::OutOfGas(
+                crate::gas::OutOfGas
+            )))
+        );
+    }
+
     #[test]
     fn interpret_test_macro_if_some() {
         let ast = parse(MACRO_IF_SOME_SRC).unwrap();
@@ -263,6 +930,24 @@ mod tests {
         assert_eq!(stack, tc_stk![Type::Int])
     }
 
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_roundtrip() {
+        let ast = parse(FIBONACCI_SRC).unwrap();
+        let json = ast.to_json();
+        assert_eq!(Micheline::from_json(&Arena::new(), &json), Ok(ast));
+    }
+
+    #[test]
+    fn pretty_print_roundtrip() {
+        let ast = parse(FIBONACCI_SRC).unwrap();
+        assert_eq!(parse(&ast.to_string()).unwrap(), ast);
+        assert_eq!(parse(&format!("{ast:#}")).unwrap(), ast);
+        // printing is idempotent
+        let printed = ast.to_string();
+        assert_eq!(parse(&printed).unwrap().to_string(), printed);
+    }
+
     #[test]
     fn typecheck_gas() {
         let ast = parse(FIBONACCI_SRC).unwrap();
@@ -555,6 +1240,23 @@ mod tests {
         )
     }
 
+    #[test]
+    fn amount() {
+        run_e2e_test(
+            &Arena::new(),
+            "AMOUNT",
+            stk![],
+            stk![Type::Mutez],
+            stk![],
+            stk![TypedValue::Mutez(1000000),],
+            {
+                let mut c = Ctx::default();
+                c.amount = 1000000;
+                c
+            },
+        )
+    }
+
     #[test]
     fn contract() {
         let addr = Address::try_from("KT1BRd2ka5q2cPRdXALtXD1QZ38CPam2j1ye").unwrap();
@@ -854,6 +1556,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn now_level_constant() {
+        run_e2e_test(
+            &Arena::new(),
+            "{ NOW; PUSH int 10; ADD; NOW; SUB; LEVEL; LEVEL; SUB }",
+            stk![],
+            stk![Type::Int, Type::Int],
+            stk![],
+            stk![TypedValue::int(-10), TypedValue::int(0)],
+            {
+                let mut c = Ctx::default();
+                c.now = 4500i32.into();
+                c.level = 45u32.into();
+                c
+            },
+        );
+    }
+
+    #[test]
+    fn context_instructions() {
+        let self_address = "KT1RJ6PbjHpwc3M5rw5s2Nbmefwbuwbdxton";
+        let sender = "KT1BRd2ka5q2cPRdXALtXD1QZ38CPam2j1ye";
+        let source = "tz1Nw5nr152qddEjKT2dKBH8XcBMDAg72iLw";
+        let chain_id =
+            tezos_crypto_rs::hash::ChainId::from_base58_check("NetXdQprcVkpaWU").unwrap();
+        run_e2e_test(
+            &Arena::new(),
+            "{ SELF_ADDRESS; CHAIN_ID; LEVEL; NOW; SOURCE; SENDER; BALANCE; AMOUNT }",
+            stk![],
+            stk![
+                Type::Address,
+                Type::ChainId,
+                Type::Nat,
+                Type::Timestamp,
+                Type::Address,
+                Type::Address,
+                Type::Mutez,
+                Type::Mutez
+            ],
+            stk![],
+            stk![
+                TypedValue::Address(Address::try_from(self_address).unwrap()),
+                TypedValue::ChainId(chain_id.clone()),
+                TypedValue::nat(123),
+                TypedValue::timestamp(1700000000),
+                TypedValue::Address(Address::try_from(source).unwrap()),
+                TypedValue::Address(Address::try_from(sender).unwrap()),
+                TypedValue::Mutez(500),
+                TypedValue::Mutez(20)
+            ],
+            {
+                let mut c = Ctx::default();
+                c.self_address = self_address.try_into().unwrap();
+                c.chain_id = chain_id;
+                c.level = 123u32.into();
+                c.now = 1700000000i64.into();
+                c.source = source.try_into().unwrap();
+                c.sender = sender.try_into().unwrap();
+                c.balance = 500;
+                c.amount = 20;
+                c
+            },
+        );
+    }
+
+    #[test]
+    fn get_n_update_n() {
+        let pair = |s: &str| {
+            TypedValue::new_pair(
+                TypedValue::int(1),
+                TypedValue::new_pair(TypedValue::String(s.to_owned()), TypedValue::Bool(true)),
+            )
+        };
+        let ty = Type::new_pair(Type::Int, Type::new_pair(Type::String, Type::Bool));
+        run_e2e_test(
+            &Arena::new(),
+            r#"{ DUP; GET 3; DIP { PUSH string "bar"; UPDATE 3 } }"#,
+            stk![ty.clone()],
+            stk![ty, Type::String],
+            stk![pair("foo")],
+            stk![pair("bar"), TypedValue::String("foo".to_owned())],
+            Ctx::default(),
+        );
+    }
+
     #[test]
     fn implicit_account() {
         let key_hash = KeyHash::try_from("tz3d9na7gPpt5jxdjGBFzoGQigcStHB8w1uq").unwrap();
@@ -8,6 +8,7 @@
 //! Michelson parser.
 
 pub mod macros;
+pub mod spans;
 
 use crate::ast::*;
 use crate::lexer::{LexerError, Tok};
@@ -15,6 +16,7 @@ use crate::syntax;
 use lalrpop_util::ParseError;
 use logos::Logos;
 use macros::MacroError;
+use spans::{Span, SpanMap};
 use typed_arena::Arena;
 
 /// Errors that can happen during parsing, aside from parser-specific ones.
@@ -26,6 +28,10 @@ pub enum ParserError {
     /// An error happened during macro expansion.
     #[error(transparent)]
     MacroError(#[from] MacroError),
+    /// `DUP 0` was found. The argument of `DUP` must be a natural between 1
+    /// and 1023 inclusive; the location is that of the argument.
+    #[error("expected a natural between 1 and 1023 at {0}, but got 0")]
+    Dup0(Span),
 }
 
 /// A parser for Michelson. Carries an [Arena] for placing [Micheline] nodes
@@ -33,6 +39,9 @@ pub enum ParserError {
 pub struct Parser<'a> {
     /// The [Arena] to place [Micheline] nodes into.
     pub arena: Arena<Micheline<'a>>,
+    /// Source locations of the parsed nodes, only recorded by a parser
+    /// constructed with [Parser::with_spans].
+    pub spans: SpanMap,
 }
 
 impl Default for Parser<'_> {
@@ -46,12 +55,27 @@ impl<'a> Parser<'a> {
     pub fn new() -> Self {
         Parser {
             arena: Arena::new(),
+            spans: SpanMap::default(),
+        }
+    }
+
+    /// Construct a new parser which records the source locations of the
+    /// parsed nodes in [Parser::spans].
+    pub fn with_spans() -> Self {
+        Parser {
+            arena: Arena::new(),
+            spans: SpanMap::recording(),
         }
     }
 
     /// Parse Michelson code or value into [Micheline].
     pub fn parse(&'a self, src: &'a str) -> Result<Micheline, ParseError<usize, Tok, ParserError>> {
-        syntax::MichelineNakedParser::new().parse(&self.arena, spanned_lexer(src))
+        syntax::MichelineNakedParser::new().parse(
+            &self.arena,
+            &self.spans,
+            true,
+            spanned_lexer(src),
+        )
     }
 
     /// Parse Michelson script into [Micheline]. Top-level refers to a full
@@ -61,7 +85,12 @@ impl<'a> Parser<'a> {
         &'a self,
         src: &'a str,
     ) -> Result<Micheline, ParseError<usize, Tok, ParserError>> {
-        syntax::MichelineTopLevelParser::new().parse(&self.arena, spanned_lexer(src))
+        syntax::MichelineTopLevelParser::new().parse(
+            &self.arena,
+            &self.spans,
+            true,
+            spanned_lexer(src),
+        )
     }
 }
 
@@ -74,6 +103,10 @@ pub(crate) fn spanned_lexer(
         .spanned()
         .map(|(tok_or_err, span)| match tok_or_err {
             Ok(tok) => Ok((span.start, tok, span.end)),
+            // a terminated block comment would have been skipped
+            Err(LexerError::UnknownToken) if src[span.start..].starts_with("/*") => {
+                Err(LexerError::UnterminatedComment(span.start).into())
+            }
             Err(err) => Err(err.into()),
         })
 }
@@ -305,6 +338,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn malformed_anns() {
+        for src in ["CAR %", "CAR @", "CAR :", "CAR %.a", "(int :)"] {
+            assert_eq!(
+                parse(src).unwrap_err().to_string(),
+                "unknown token",
+                "{src}"
+            );
+        }
+    }
+
     #[test]
     fn invalid_prim() {
         assert_eq!(
@@ -362,6 +406,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn string_push_undefined_escape() {
+        assert_eq!(
+            parse(r#"PUSH string "foo\qbar""#).unwrap_err().to_string(),
+            "undefined escape sequence: \"\\q\" found at 16"
+        );
+    }
+
+    #[test]
+    fn comments() {
+        let expected = parse("{ PUSH int 1 ; ADD }").unwrap();
+        for src in [
+            "{ PUSH int 1 # comment\n ; ADD }",
+            "{ PUSH int 1 ; ADD } # comment at the end",
+            "# comment\n{ PUSH int 1 ; ADD }",
+            "{ PUSH /* comment */ int 1 ; ADD }",
+            "{ PUSH int 1 ; /* multi-line\n * comment **/ ADD }",
+            "{ PUSH int 1/**/; ADD }",
+            "{ PUSH int 1 ; ADD /* # */ }",
+        ] {
+            assert_eq!(parse(src).unwrap(), expected, "{src}");
+        }
+        // a string may contain comment markers
+        assert_eq!(
+            parse(r##"PUSH string "# /* */""##).unwrap(),
+            app!(PUSH[app!(string), "# /* */"])
+        );
+    }
+
+    #[test]
+    fn comment_spans() {
+        use super::{spans::Span, Parser};
+
+        let parser = Parser::with_spans();
+        let src = "{ /* one */ UNIT ; # two\n DROP }";
+        let Micheline::Seq([unit, drop]) = parser.parse(src).unwrap() else {
+            panic!("unexpected parse result")
+        };
+        let span = |start, end| Some(Span { start, end });
+        assert_eq!(parser.spans.get(unit), span(12, 16));
+        assert_eq!(parser.spans.get(drop), span(26, 30));
+    }
+
+    #[test]
+    fn unterminated_comment() {
+        assert_eq!(
+            parse("{ PUSH int 1 ; /* ADD }").unwrap_err().to_string(),
+            "unterminated comment starting at 15"
+        );
+        assert_eq!(
+            parse("{ UNIT /* a */ /* b * }").unwrap_err().to_string(),
+            "unterminated comment starting at 15"
+        );
+    }
+
+    #[test]
+    fn dup0() {
+        use super::{spans::Span, ParserError};
+        use lalrpop_util::ParseError;
+        assert_eq!(
+            parse("{ DUP 0 }"),
+            Err(ParseError::User {
+                error: ParserError::Dup0(Span { start: 6, end: 7 })
+            })
+        );
+        assert_eq!(
+            parse("{ DUP 0 }").unwrap_err().to_string(),
+            "expected a natural between 1 and 1023 at 6:7, but got 0"
+        );
+        assert_eq!(parse("DUP"), Ok(app!(DUP)));
+        assert_eq!(parse("DUP 1"), Ok(app!(DUP[1])));
+    }
+
     #[test]
     fn bytes_push() {
         assert_eq!(
@@ -370,6 +487,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bytes_push_odd_length() {
+        assert_eq!(
+            parse("PUSH bytes 0xdeadf00").unwrap_err().to_string(),
+            "invalid hex sequence: Odd number of digits found at 11:20"
+        );
+    }
+
     #[test]
     fn address_ty_push() {
         assert_eq!(
@@ -377,4 +502,35 @@ mod tests {
             app!(PUSH[app!(address), "tz1Nw5nr152qddEjKT2dKBH8XcBMDAg72iLw"])
         );
     }
+
+    #[test]
+    fn spans() {
+        use super::{spans::Span, Parser};
+
+        let parser = Parser::with_spans();
+        let src = "{ PUSH (pair int nat) (Pair 1 2); DIP { DROP } }";
+        let Micheline::Seq([push, dip]) = parser.parse(src).unwrap() else {
+            panic!("unexpected parse result")
+        };
+        let span = |start, end| Some(Span { start, end });
+        assert_eq!(parser.spans.get(push), span(2, 32));
+        assert_eq!(parser.spans.get(dip), span(34, 46));
+        let Micheline::App(_, [ty, _], _) = push else {
+            panic!("unexpected parse result")
+        };
+        assert_eq!(parser.spans.get(ty), span(7, 21));
+        // nodes not allocated by the parser have no location
+        assert_eq!(parser.spans.get(&app!(DROP)), None);
+    }
+
+    #[test]
+    fn no_spans_by_default() {
+        use super::Parser;
+
+        let parser = Parser::new();
+        let Micheline::Seq([drop]) = parser.parse("{ DROP }").unwrap() else {
+            panic!("unexpected parse result")
+        };
+        assert_eq!(parser.spans.get(drop), None);
+    }
 }
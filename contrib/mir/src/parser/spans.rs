@@ -0,0 +1,91 @@
+/******************************************************************************/
+/*                                                                            */
+/* SPDX-License-Identifier: MIT                                               */
+/* Copyright (c) [2023] Serokell <hi@serokell.io>                             */
+/*                                                                            */
+/******************************************************************************/
+
+//! Source locations of parsed [Micheline] nodes.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use typed_arena::Arena;
+
+use crate::ast::Micheline;
+
+/// Location of a node in the source, as a range of byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Span {
+    /// Offset of the first byte of the node.
+    pub start: usize,
+    /// Offset just past the last byte of the node.
+    pub end: usize,
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.start, self.end)
+    }
+}
+
+/// Source locations of the nodes allocated by a [Parser](super::Parser), i.e.
+/// elements of sequences and primitive arguments. The root node and nodes
+/// produced by macro expansion have no location.
+///
+/// Locations are only recorded by a parser constructed with
+/// [Parser::with_spans](super::Parser::with_spans); otherwise the map stays
+/// empty.
+///
+/// Nodes are identified by their address, which is stable, as they are
+/// allocated in the parser's arena.
+#[derive(Debug, Default)]
+pub struct SpanMap(Option<RefCell<HashMap<usize, Span>>>);
+
+impl SpanMap {
+    /// An empty map, recording the locations of the nodes allocated through it.
+    pub(crate) fn recording() -> Self {
+        SpanMap(Some(RefCell::default()))
+    }
+
+    /// Get the location of a node.
+    pub fn get(&self, node: &Micheline) -> Option<Span> {
+        self.get_by_address(address(node))
+    }
+
+    pub(crate) fn get_by_address(&self, addr: usize) -> Option<Span> {
+        self.0.as_ref()?.borrow().get(&addr).copied()
+    }
+
+    /// Allocate the nodes in the arena, recording their locations if enabled.
+    pub(crate) fn alloc<'a>(
+        &self,
+        arena: &'a Arena<Micheline<'a>>,
+        nodes: Vec<(usize, Micheline<'a>, usize)>,
+    ) -> &'a [Micheline<'a>] {
+        let map = match &self.0 {
+            Some(map) => map,
+            None => {
+                return Micheline::alloc_iter(arena, nodes.into_iter().map(|(_, node, _)| node))
+            }
+        };
+        let spans: Vec<Span> = nodes
+            .iter()
+            .map(|(start, _, end)| Span {
+                start: *start,
+                end: *end,
+            })
+            .collect();
+        let nodes = Micheline::alloc_iter(arena, nodes.into_iter().map(|(_, node, _)| node));
+        let mut map = map.borrow_mut();
+        for (node, span) in nodes.iter().zip(spans) {
+            map.insert(address(node), span);
+        }
+        nodes
+    }
+}
+
+/// Address of a node, used to identify it.
+pub(crate) fn address(node: &Micheline) -> usize {
+    node as *const Micheline as usize
+}
@@ -7,7 +7,8 @@
 
 //! Serialization to and deserialization from bytes. Used for `PACK` and
 //! `UNPACK` instructions respectively, but can be used for general-purpose
-//! Michelson data serialization as well.
+//! Michelson data serialization as well. Conversion to and from the Micheline
+//! JSON representation is also provided, behind the `json` feature.
 //!
 //! Functions are defined as associated functions on [crate::ast::Micheline],
 //! see it for more.
@@ -16,5 +17,10 @@ mod constants;
 mod decode;
 mod encode;
 mod integration_tests;
+#[cfg(feature = "json")]
+mod json;
 
 pub use {decode::*, encode::*};
+
+#[cfg(feature = "json")]
+pub use json::*;
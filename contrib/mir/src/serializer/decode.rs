@@ -201,7 +201,7 @@ fn get_bytes<'a>(bytes: &mut BytesIt<'a>) -> Result<&'a [u8], DecodeError> {
     bytes.take(len).ok_or(DecodeError::UnexpectedEOF)
 }
 
-fn validate_str(bytes: &[u8]) -> Result<&str, DecodeError> {
+pub(super) fn validate_str(bytes: &[u8]) -> Result<&str, DecodeError> {
     // check if all characters are printable ASCII
     if !bytes
         .iter()
@@ -247,7 +247,7 @@ fn decode_seq<'a>(
     Ok(res)
 }
 
-fn validate_ann(bytes: &[u8]) -> Result<Annotation<'static>, DecodeError> {
+pub(super) fn validate_ann(bytes: &[u8]) -> Result<Annotation<'static>, DecodeError> {
     // @%|@%%|%@|[@:%][_0-9a-zA-Z][_0-9a-zA-Z\.%@]*
     macro_rules! alpha_num {
       () => {
@@ -366,6 +366,21 @@ mod test {
             check_err("0x010000000100", DecodeError::ForbiddenStringCharacter);
         }
 
+        #[test]
+        fn truncated() {
+            // number tag without the number
+            check_err("0x00", DecodeError::UnexpectedEOF);
+            // zarith continuation bit set on the last byte
+            check_err("0x0095b4de", DecodeError::UnexpectedEOF);
+            check_err("0x00f1a2f3ad", DecodeError::UnexpectedEOF);
+            // string shorter than its length
+            check_err("0x01000000036162", DecodeError::UnexpectedEOF);
+            // sequence shorter than its length
+            check_err("0x0200000004030b", DecodeError::UnexpectedEOF);
+            // missing second argument
+            check_err("0x0707030a", DecodeError::UnexpectedEOF);
+        }
+
         mod number {
             use super::*;
 
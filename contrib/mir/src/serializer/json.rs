@@ -0,0 +1,285 @@
+/******************************************************************************/
+/*                                                                            */
+/* SPDX-License-Identifier: MIT                                               */
+/* Copyright (c) [2023] Serokell <hi@serokell.io>                             */
+/*                                                                            */
+/******************************************************************************/
+
+//! Conversion to and from the Micheline JSON representation, as used by
+//! `octez-client` and the node RPC.
+//!
+//! *N.B.* Only available when the `json` feature is enabled.
+
+use num_bigint::BigInt;
+use serde_json::{Map, Value};
+use typed_arena::Arena;
+
+use super::decode::{validate_ann, validate_str};
+use crate::ast::{annotations::Annotations, IntoMicheline, Micheline, TypedValue};
+use crate::lexer::{errors::PrimError, Prim};
+
+/// Errors that can happen when converting from JSON.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum JsonError {
+    /// The JSON value is not a valid Micheline node.
+    #[error("expected a Micheline node, but got: {0}")]
+    UnexpectedValue(Value),
+    /// The value of an `int` node is not a decimal integer.
+    #[error("invalid integer: {0}")]
+    BadInt(String),
+    /// The value of a `bytes` node is not a hexadecimal string.
+    #[error("invalid bytes: {0}")]
+    BadBytes(String),
+    /// Forbidden character found in the value of a `string` node.
+    #[error("forbidden character in string")]
+    ForbiddenStringCharacter,
+    /// Unknown primitive in a `prim` node.
+    #[error(transparent)]
+    UnknownPrim(#[from] PrimError),
+    /// Malformed annotation in a `prim` node.
+    #[error("invalid annotation: {0}")]
+    BadAnnotation(String),
+}
+
+impl<'a> Micheline<'a> {
+    /// Convert to the Micheline JSON representation, e.g. `{ "prim": "ADD" }`
+    /// or `{ "int": "5" }`. Sequences are represented as JSON arrays.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Micheline::Int(i) => node("int", i.to_string()),
+            Micheline::String(s) => node("string", s.clone()),
+            Micheline::Bytes(bs) => node("bytes", hex::encode(bs)),
+            Micheline::Seq(elts) => Value::Array(elts.iter().map(Self::to_json).collect()),
+            Micheline::App(prim, args, anns) => {
+                let mut obj = Map::new();
+                obj.insert("prim".to_owned(), Value::String(prim.to_string()));
+                if !args.is_empty() {
+                    obj.insert(
+                        "args".to_owned(),
+                        Value::Array(args.iter().map(Self::to_json).collect()),
+                    );
+                }
+                if !anns.is_empty() {
+                    obj.insert(
+                        "annots".to_owned(),
+                        Value::Array(anns.iter().map(|a| Value::String(a.to_string())).collect()),
+                    );
+                }
+                Value::Object(obj)
+            }
+        }
+    }
+
+    /// Convert from the Micheline JSON representation, allocating nested
+    /// nodes in the [Arena]. Inverse of [Micheline::to_json].
+    pub fn from_json(arena: &'a Arena<Micheline<'a>>, json: &Value) -> Result<Self, JsonError> {
+        let unexpected = || JsonError::UnexpectedValue(json.clone());
+        let obj = match json {
+            Value::Array(elts) => return Ok(Micheline::Seq(from_json_seq(arena, elts)?)),
+            Value::Object(obj) => obj,
+            _ => return Err(unexpected()),
+        };
+        let field = |name| match obj.get(name) {
+            Some(Value::String(s)) if obj.len() == 1 => Ok(s),
+            _ => Err(unexpected()),
+        };
+        if obj.contains_key("int") {
+            let s = field("int")?;
+            let digits = s.strip_prefix('-').unwrap_or(s);
+            if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+                return Err(JsonError::BadInt(s.clone()));
+            }
+            // can't fail, validated above
+            Ok(Micheline::Int(s.parse::<BigInt>().unwrap()))
+        } else if obj.contains_key("string") {
+            let s = validate_str(field("string")?.as_bytes())
+                .map_err(|_| JsonError::ForbiddenStringCharacter)?;
+            Ok(Micheline::String(s.to_owned()))
+        } else if obj.contains_key("bytes") {
+            let s = field("bytes")?;
+            let bs = hex::decode(s).map_err(|_| JsonError::BadBytes(s.clone()))?;
+            Ok(Micheline::Bytes(bs))
+        } else {
+            if obj
+                .keys()
+                .any(|k| !matches!(k.as_str(), "prim" | "args" | "annots"))
+            {
+                return Err(unexpected());
+            }
+            let prim: Prim = match obj.get("prim") {
+                Some(Value::String(s)) => s.parse()?,
+                _ => return Err(unexpected()),
+            };
+            let args = match obj.get("args") {
+                None => &[],
+                Some(Value::Array(args)) => from_json_seq(arena, args)?,
+                Some(_) => return Err(unexpected()),
+            };
+            let anns = match obj.get("annots") {
+                None => Annotations::new(),
+                Some(Value::Array(anns)) => anns
+                    .iter()
+                    .map(|ann| match ann {
+                        Value::String(s) => validate_ann(s.as_bytes())
+                            .map_err(|_| JsonError::BadAnnotation(s.clone())),
+                        _ => Err(unexpected()),
+                    })
+                    .collect::<Result<_, _>>()?,
+                Some(_) => return Err(unexpected()),
+            };
+            Ok(Micheline::App(prim, args, anns))
+        }
+    }
+}
+
+impl TypedValue<'_> {
+    /// Convert to the Micheline JSON representation, untyping the value with
+    /// [IntoMicheline::into_micheline_optimized_legacy], i.e. as `PACK` does.
+    pub fn to_json(&self) -> Value {
+        let arena = Arena::new();
+        self.clone()
+            .into_micheline_optimized_legacy(&arena)
+            .to_json()
+    }
+}
+
+/// A JSON object with a single string field.
+fn node(name: &str, value: String) -> Value {
+    Value::Object(Map::from_iter([(name.to_owned(), Value::String(value))]))
+}
+
+fn from_json_seq<'a>(
+    arena: &'a Arena<Micheline<'a>>,
+    elts: &[Value],
+) -> Result<&'a [Micheline<'a>], JsonError> {
+    let elts = elts
+        .iter()
+        .map(|elt| Micheline::from_json(arena, elt))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Micheline::alloc_iter(arena, elts.into_iter()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::annotations::Annotation;
+    use crate::parser::test_helpers::parse;
+    use serde_json::json;
+
+    #[track_caller]
+    fn roundtrip(src: &str, expected: Value) {
+        let ast = parse(src).unwrap();
+        assert_eq!(ast.to_json(), expected);
+        assert_eq!(Micheline::from_json(&Arena::new(), &expected), Ok(ast));
+    }
+
+    #[test]
+    fn atoms() {
+        roundtrip("-12", json!({ "int": "-12" }));
+        roundtrip(r#""foo""#, json!({ "string": "foo" }));
+        roundtrip("0xdeadbeef", json!({ "bytes": "deadbeef" }));
+        roundtrip("Unit", json!({ "prim": "Unit" }));
+    }
+
+    #[test]
+    fn apps_and_seqs() {
+        roundtrip(
+            "{ PUSH @v (pair %p int nat) (Pair 1 2); DROP }",
+            json!([
+                {
+                    "prim": "PUSH",
+                    "args": [
+                        { "prim": "pair", "args": [{ "prim": "int" }, { "prim": "nat" }], "annots": ["%p"] },
+                        { "prim": "Pair", "args": [{ "int": "1" }, { "int": "2" }] }
+                    ],
+                    "annots": ["@v"]
+                },
+                { "prim": "DROP" }
+            ]),
+        );
+        roundtrip("{}", json!([]));
+    }
+
+    #[test]
+    fn typed_value() {
+        let value = TypedValue::new_pair(
+            TypedValue::int(1),
+            TypedValue::new_option(Some(TypedValue::String("foo".to_owned()))),
+        );
+        assert_eq!(
+            value.to_json(),
+            json!({
+                "prim": "Pair",
+                "args": [
+                    { "int": "1" },
+                    { "prim": "Some", "args": [{ "string": "foo" }] }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn uppercase_bytes() {
+        assert_eq!(
+            Micheline::from_json(&Arena::new(), &json!({ "bytes": "DEADBEEF" })),
+            Ok(Micheline::Bytes(vec![0xde, 0xad, 0xbe, 0xef]))
+        );
+    }
+
+    #[test]
+    fn special_annotation() {
+        assert_eq!(
+            Micheline::from_json(&Arena::new(), &json!({ "prim": "CAR", "annots": ["@%%"] })),
+            Ok(Micheline::App(
+                Prim::CAR,
+                &[],
+                [Annotation::Special("@%%".into())].into()
+            ))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        #[track_caller]
+        fn check_err(json: Value, err: JsonError) {
+            assert_eq!(Micheline::from_json(&Arena::new(), &json), Err(err));
+        }
+        check_err(json!(1), JsonError::UnexpectedValue(json!(1)));
+        check_err(json!({}), JsonError::UnexpectedValue(json!({})));
+        check_err(
+            json!({ "int": 1 }),
+            JsonError::UnexpectedValue(json!({ "int": 1 })),
+        );
+        check_err(
+            json!({ "int": "1", "string": "a" }),
+            JsonError::UnexpectedValue(json!({ "int": "1", "string": "a" })),
+        );
+        check_err(json!({ "int": "1.5" }), JsonError::BadInt("1.5".to_owned()));
+        check_err(json!({ "int": "+1" }), JsonError::BadInt("+1".to_owned()));
+        check_err(json!({ "int": "-" }), JsonError::BadInt("-".to_owned()));
+        check_err(
+            json!({ "bytes": "abc" }),
+            JsonError::BadBytes("abc".to_owned()),
+        );
+        check_err(
+            json!({ "string": "\u{1}" }),
+            JsonError::ForbiddenStringCharacter,
+        );
+        check_err(
+            json!({ "prim": "FOO" }),
+            JsonError::UnknownPrim(PrimError("FOO".to_owned())),
+        );
+        check_err(
+            json!({ "prim": "CAR", "annots": ["%"] }),
+            JsonError::BadAnnotation("%".to_owned()),
+        );
+        check_err(
+            json!({ "prim": "CAR", "args": {} }),
+            JsonError::UnexpectedValue(json!({ "prim": "CAR", "args": {} })),
+        );
+        check_err(
+            json!({ "prim": "CAR", "foo": [] }),
+            JsonError::UnexpectedValue(json!({ "prim": "CAR", "foo": [] })),
+        );
+    }
+}
@@ -40,6 +40,12 @@ impl FailingTypeStack {
     }
 }
 
+impl From<TypeStack> for FailingTypeStack {
+    fn from(stack: TypeStack) -> Self {
+        FailingTypeStack::Ok(stack)
+    }
+}
+
 /// Construct a `Stack` with the given content. Note that stack top is the
 /// _rightmost_ element.
 #[macro_export]
@@ -62,6 +68,7 @@ pub use {stk, tc_stk};
 
 /// A stack abstraction based on `Vec`.
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Stack<T>(Vec<T>);
 
 impl<T> Stack<T> {
@@ -143,6 +150,18 @@ impl<T> Stack<T> {
             .truncate(len.checked_sub(size).expect("size too large in drop_top"));
     }
 
+    /// Removes elements from the top of the stack, keeping only the bottom
+    /// `size` elements. Does nothing if the stack has `size` elements or
+    /// fewer. Useful for resetting a stack between contract calls.
+    pub fn truncate_to(&mut self, size: usize) {
+        self.0.truncate(size)
+    }
+
+    /// Removes all elements from the stack.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
     /// Removes the specified number of elements from the top of the stack and
     /// returns them as an iterator over the removed items, starting with the stack's top.
     ///
@@ -206,6 +225,96 @@ impl<T> Stack<T> {
     }
 }
 
+/// Stack operations used by the interpreter, allowing to plug in a custom
+/// stack implementation, e.g. an instrumented one for fuzzing or profiling.
+/// Indices are counted from the top, with `0` being the top. See [Stack], the
+/// default implementation, for the semantics of each method.
+///
+/// Note that lambdas are executed on their own [Stack], not on the
+/// implementation passed in.
+pub trait StackOps<T>: IndexMut<usize, Output = T> {
+    /// Push an element onto the top of the stack.
+    fn push(&mut self, elt: T);
+
+    /// Pop an element off the top of the stack.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Get the stack's element count.
+    fn len(&self) -> usize;
+
+    /// Check if the stack is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove and return the element at position `i`, see [Stack::remove].
+    fn remove(&mut self, i: usize) -> T;
+
+    /// Insert an element at position `i`, see [Stack::insert].
+    fn insert(&mut self, i: usize, e: T);
+
+    /// Swap two elements, see [Stack::swap].
+    fn swap(&mut self, i1: usize, i2: usize);
+
+    /// Remove the top `size` elements, see [Stack::drop_top].
+    fn drop_top(&mut self, size: usize);
+
+    /// Split off the top `size` elements, see [Stack::split_off].
+    fn split_off(&mut self, size: usize) -> Stack<T>;
+
+    /// Move elements from `other` to the top of the stack, see
+    /// [Stack::append].
+    fn append(&mut self, other: &mut Stack<T>);
+
+    /// Reserve space for at least `additional` elements. Does nothing by
+    /// default.
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+}
+
+impl<T> StackOps<T> for Stack<T> {
+    fn push(&mut self, elt: T) {
+        Stack::push(self, elt)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        Stack::pop(self)
+    }
+
+    fn len(&self) -> usize {
+        Stack::len(self)
+    }
+
+    fn remove(&mut self, i: usize) -> T {
+        Stack::remove(self, i)
+    }
+
+    fn insert(&mut self, i: usize, e: T) {
+        Stack::insert(self, i, e)
+    }
+
+    fn swap(&mut self, i1: usize, i2: usize) {
+        Stack::swap(self, i1, i2)
+    }
+
+    fn drop_top(&mut self, size: usize) {
+        Stack::drop_top(self, size)
+    }
+
+    fn split_off(&mut self, size: usize) -> Stack<T> {
+        Stack::split_off(self, size)
+    }
+
+    fn append(&mut self, other: &mut Stack<T>) {
+        Stack::append(self, other)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Stack::reserve(self, additional)
+    }
+}
+
 /// Newtype for specifying the order of elements in a [Stack] vs elements in
 /// a [Vec]/slice. Used in the [From] trait for [Stack]. _First_ element of
 /// the [Vec] will end up at the _top_ of the stack. `from()` conversion has
@@ -301,6 +410,12 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
 impl<T> IntoIterator for Stack<T> {
     type IntoIter = IntoIter<T>;
 
@@ -435,6 +550,24 @@ mod tests {
         assert_eq!(stk, stk![1]);
     }
 
+    #[test]
+    fn truncate_to() {
+        let mut stk = stk![1, 2, 3, 4];
+        stk.truncate_to(5);
+        assert_eq!(stk, stk![1, 2, 3, 4]);
+        stk.truncate_to(2);
+        assert_eq!(stk, stk![1, 2]);
+        stk.truncate_to(0);
+        assert_eq!(stk, stk![]);
+    }
+
+    #[test]
+    fn clear() {
+        let mut stk = stk![1, 2, 3, 4];
+        stk.clear();
+        assert_eq!(stk, stk![]);
+    }
+
     #[test]
     fn drain_top() {
         let mut stk = stk![1, 2, 3, 4];
@@ -447,7 +580,7 @@ mod tests {
     fn drain_top_0() {
         let mut stk = stk![1, 2, 3, 4];
         let drained = stk.drain_top(0);
-        assert_eq!(drained.collect::<Vec<_>>(), vec![]);
+        assert_eq!(drained.collect::<Vec<_>>(), Vec::<i32>::new());
         assert_eq!(stk, stk![1, 2, 3, 4]);
     }
 
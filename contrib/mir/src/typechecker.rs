@@ -17,8 +17,12 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::rc::Rc;
 use tezos_crypto_rs::{base58::FromBase58CheckError, hash::FromBytesError};
 
+pub mod lint;
+pub mod policy;
 pub mod type_props;
 
+use lint::Warning;
+use policy::InstructionPolicy;
 use type_props::TypeProperty;
 
 use crate::ast::annotations::{AnnotationError, NO_ANNS};
@@ -28,16 +32,22 @@ use crate::ast::micheline::{
     micheline_unsupported_instructions, micheline_unsupported_types, micheline_values,
 };
 use crate::ast::michelson_address::AddressHash;
-use crate::context::Ctx;
+use crate::context::{with_nesting_stack, Ctx};
 use crate::gas::OutOfGas;
 use crate::gas::{self, tc_cost, Gas};
 use crate::irrefutable_match::irrefutable_match;
 use crate::lexer::Prim;
+use crate::parser::spans::{self, Span, SpanMap};
 use crate::stack::*;
 use crate::{ast::*, bls};
 
 /// Typechecker error type.
 #[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", content = "details")
+)]
 pub enum TcError {
     /// Two stacks didn't compare equal when they should have.
     #[error("type stacks not equal: {0:?} != {1:?}")]
@@ -54,7 +64,11 @@ pub enum TcError {
     /// Failed to interpret a number as a value of some type due to a numeric
     /// conversion error.
     #[error("numeric conversion failed: {0}")]
-    NumericConversion(#[from] TryFromBigIntError<()>),
+    NumericConversion(
+        #[from]
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+        TryFromBigIntError<()>,
+    ),
     /// Types are not equal when they should be.
     #[error(transparent)]
     TypesNotEqual(#[from] TypesNotEqual),
@@ -160,10 +174,30 @@ pub enum TcError {
     /// All branches of a `MAP` instruction's code block are failing.
     #[error("all branches of a MAP block use FAILWITH, its type cannot be inferred")]
     MapBlockFail,
+    /// Encountered an instruction forbidden by the [InstructionPolicy].
+    #[error("instruction {0} is forbidden by the policy")]
+    InstructionForbidden(Prim),
+    /// Code blocks are nested deeper than [Ctx::max_interpret_depth].
+    #[error("stack overflow: code blocks nested deeper than {0}")]
+    StackOverflow(usize),
+}
+
+/// Serialize an error without a structured representation as its message.
+#[cfg(feature = "serde")]
+fn serialize_display<S: serde::Serializer>(
+    x: &impl std::fmt::Display,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(x)
 }
 
 /// Errors happening when typechecking a value of type `chain_id`.
 #[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", content = "details")
+)]
 pub enum ChainIdError {
     /// Error happened when typechecking a (supposedly) base58-check encoded
     /// string as `chain_id`.
@@ -188,6 +222,11 @@ impl From<FromBytesError> for ChainIdError {
 
 /// More detailed, optional explanation for [TcError::NoMatchingOverload].
 #[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", content = "details")
+)]
 pub enum NoMatchingOverloadReason {
     /// Input stack is too short.
     #[error("stack too short, expected at least {expected}")]
@@ -218,6 +257,11 @@ pub enum NoMatchingOverloadReason {
 
 /// More detailed explanation for [TcError::StacksNotEqual]
 #[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", content = "details")
+)]
 pub enum StacksNotEqualReason {
     /// The given types in the stacks do not match.
     #[error(transparent)]
@@ -230,8 +274,31 @@ pub enum StacksNotEqualReason {
 /// Generic type mismatch error.
 #[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
 #[error("types not equal: {0:?} != {1:?}")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TypesNotEqual(Type, Type);
 
+/// [TcError] together with the location of the innermost instruction that
+/// failed to typecheck, see [Micheline::typecheck_with_spans].
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SpannedTcError {
+    /// Location of the failed instruction, if known.
+    pub span: Option<Span>,
+    /// The error itself.
+    #[source]
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub error: TcError,
+}
+
+impl std::fmt::Display for SpannedTcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.span {
+            Some(span) => write!(f, "error at {span}: {}", self.error),
+            None => self.error.fmt(f),
+        }
+    }
+}
+
 impl<'a> Micheline<'a> {
     /// Typechecks `Micheline` as a value, given its type (also as `Micheline`).
     /// Validates the type.
@@ -257,6 +324,19 @@ impl<'a> Micheline<'a> {
         self_type: Option<&Micheline>,
         stack: &[Micheline],
     ) -> Result<Instruction<'a>, TcError> {
+        let (instr, _) = self.typecheck_with_output(ctx, self_type, stack)?;
+        Ok(instr)
+    }
+
+    /// Same as [Micheline::typecheck_instruction], but additionally returns
+    /// the resulting stack type, which is [FailingTypeStack::Failed] if the
+    /// code always fails.
+    pub fn typecheck_with_output(
+        &self,
+        ctx: &mut Ctx,
+        self_type: Option<&Micheline>,
+        stack: &[Micheline],
+    ) -> Result<(Instruction<'a>, FailingTypeStack), TcError> {
         let entrypoints = self_type
             .map(|ty| {
                 let (entrypoints, ty) = parse_parameter_ty_with_entrypoints(ctx, ty)?;
@@ -270,7 +350,62 @@ impl<'a> Micheline<'a> {
             .map(|ty| parse_ty(ctx, ty))
             .collect::<Result<_, TcError>>()?;
         let mut opt_stack = FailingTypeStack::Ok(checked_stack);
-        typecheck_instruction(self, ctx, entrypoints.as_ref(), &mut opt_stack)
+        let instr = typecheck_instruction(self, ctx, entrypoints.as_ref(), &mut opt_stack)?;
+        Ok((instr, opt_stack))
+    }
+
+    /// Same as [Micheline::typecheck_instruction], but fails with
+    /// [TcError::InstructionForbidden] if the code uses any instruction
+    /// forbidden by `policy`, including in nested code blocks.
+    pub fn typecheck_with_policy(
+        &self,
+        ctx: &mut Ctx,
+        self_type: Option<&Micheline>,
+        stack: &[Micheline],
+        policy: &InstructionPolicy,
+    ) -> Result<Instruction<'a>, TcError> {
+        policy.check(self)?;
+        self.typecheck_instruction(ctx, self_type, stack)
+    }
+
+    /// Same as [Micheline::typecheck_instruction], but on success additionally
+    /// returns non-fatal warnings about code that can be simplified, see
+    /// [lint::lint]. Their locations are looked up in `spans`, as for
+    /// [Micheline::typecheck_with_spans].
+    pub fn typecheck_with_warnings(
+        &self,
+        ctx: &mut Ctx,
+        self_type: Option<&Micheline>,
+        stack: &[Micheline],
+        spans: &SpanMap,
+    ) -> Result<(Instruction<'a>, Vec<Warning>), TcError> {
+        let instr = self.typecheck_instruction(ctx, self_type, stack)?;
+        Ok((instr, lint::lint(self, spans)))
+    }
+
+    /// Same as [Micheline::typecheck_instruction], but on failure additionally
+    /// reports the location of the innermost failed instruction, as recorded in
+    /// `spans` by the [Parser](crate::parser::Parser) that produced the code,
+    /// which must be constructed with
+    /// [Parser::with_spans](crate::parser::Parser::with_spans).
+    pub fn typecheck_with_spans(
+        &self,
+        ctx: &mut Ctx,
+        self_type: Option<&Micheline>,
+        stack: &[Micheline],
+        spans: &SpanMap,
+    ) -> Result<Instruction<'a>, SpannedTcError> {
+        ctx.failed_instructions = Some(Vec::new());
+        let res = self.typecheck_instruction(ctx, self_type, stack);
+        let failed = ctx.failed_instructions.take().unwrap_or_default();
+        res.map_err(|error| SpannedTcError {
+            // nodes produced by macro expansion have no location, use that of
+            // the macro itself
+            span: failed
+                .into_iter()
+                .find_map(|addr| spans.get_by_address(addr)),
+            error,
+        })
     }
 
     /// Parse `Micheline` as a type. Validates the type.
@@ -370,7 +505,7 @@ fn parse_ty_with_entrypoints(
 ) -> Result<Type, TcError> {
     use Micheline::*;
     use Prim::*;
-    ctx.gas.consume(gas::tc_cost::PARSE_TYPE_STEP)?;
+    ctx.gas.consume(ctx.gas.costs().tc.parse_type_step)?;
     fn make_pair(
         ctx: &mut Ctx,
         args: (&Micheline, &Micheline, &[Micheline]),
@@ -557,9 +692,25 @@ fn typecheck<'a>(
     self_entrypoints: Option<&Entrypoints>,
     opt_stack: &mut FailingTypeStack,
 ) -> Result<Vec<Instruction<'a>>, TcError> {
-    ast.iter()
-        .map(|i| typecheck_instruction(i, ctx, self_entrypoints, opt_stack))
-        .collect()
+    if ctx.nesting_depth >= ctx.max_interpret_depth {
+        return Err(TcError::StackOverflow(ctx.max_interpret_depth));
+    }
+    ctx.nesting_depth += 1;
+    let res = with_nesting_stack(|| {
+        ast.iter()
+            .map(|i| {
+                typecheck_instruction(i, ctx, self_entrypoints, opt_stack).map_err(|err| {
+                    // the innermost instruction is recorded first
+                    if let Some(failed) = &mut ctx.failed_instructions {
+                        failed.push(spans::address(i));
+                    }
+                    err
+                })
+            })
+            .collect()
+    });
+    ctx.nesting_depth -= 1;
+    res
 }
 
 macro_rules! nothing_to_none {
@@ -687,7 +838,7 @@ pub(crate) fn typecheck_instruction<'a>(
         };
     }
 
-    ctx.gas.consume(gas::tc_cost::INSTR_STEP)?;
+    ctx.gas.consume(ctx.gas.costs().tc.instr_step)?;
 
     use Micheline::*;
     use Prim::*;
@@ -725,6 +876,15 @@ pub(crate) fn typecheck_instruction<'a>(
             pop!();
             I::Add(overloads::Add::MutezMutez)
         }
+        (App(ADD, [], _), [.., T::Int, T::Timestamp]) => {
+            pop!();
+            stack[0] = T::Timestamp;
+            I::Add(overloads::Add::TimestampInt)
+        }
+        (App(ADD, [], _), [.., T::Timestamp, T::Int]) => {
+            pop!();
+            I::Add(overloads::Add::IntTimestamp)
+        }
         (App(ADD, [], _), [.., T::Bls12381Fr, T::Bls12381Fr]) => {
             pop!();
             I::Add(overloads::Add::Bls12381Fr)
@@ -816,6 +976,42 @@ pub(crate) fn typecheck_instruction<'a>(
         (App(NEG, [], _), []) => no_overload!(NEG, len 1),
         (App(NEG, expect_args!(0), _), _) => unexpected_micheline!(),
 
+        (App(SUB, [], _), [.., T::Nat, T::Nat]) => {
+            pop!();
+            stack[0] = T::Int;
+            I::Sub(overloads::Sub::NatNat)
+        }
+        (App(SUB, [], _), [.., T::Int, T::Int]) => {
+            pop!();
+            I::Sub(overloads::Sub::IntInt)
+        }
+        (App(SUB, [], _), [.., T::Nat, T::Int]) => {
+            pop!();
+            stack[0] = T::Int;
+            I::Sub(overloads::Sub::IntNat)
+        }
+        (App(SUB, [], _), [.., T::Int, T::Nat]) => {
+            pop!();
+            I::Sub(overloads::Sub::NatInt)
+        }
+        (App(SUB, [], _), [.., T::Mutez, T::Mutez]) => {
+            pop!();
+            I::Sub(overloads::Sub::MutezMutez)
+        }
+        (App(SUB, [], _), [.., T::Int, T::Timestamp]) => {
+            pop!();
+            stack[0] = T::Timestamp;
+            I::Sub(overloads::Sub::TimestampInt)
+        }
+        (App(SUB, [], _), [.., T::Timestamp, T::Timestamp]) => {
+            pop!();
+            stack[0] = T::Int;
+            I::Sub(overloads::Sub::TimestampTimestamp)
+        }
+        (App(SUB, [], _), [.., _, _]) => no_overload!(SUB),
+        (App(SUB, [], _), [_] | []) => no_overload!(SUB, len 2),
+        (App(SUB, expect_args!(0), _), _) => unexpected_micheline!(),
+
         (App(SUB_MUTEZ, [], _), [.., T::Mutez, T::Mutez]) => {
             pop!();
             stack[0] = Type::new_option(T::Mutez);
@@ -825,6 +1021,40 @@ pub(crate) fn typecheck_instruction<'a>(
         (App(SUB_MUTEZ, [], _), [] | [_]) => no_overload!(SUB_MUTEZ, len 2),
         (App(SUB_MUTEZ, expect_args!(0), _), _) => unexpected_micheline!(),
 
+        (App(EDIV, [], _), [.., T::Nat, T::Nat]) => {
+            pop!();
+            stack[0] = Type::new_option(Type::new_pair(T::Nat, T::Nat));
+            I::Ediv(overloads::Ediv::NatNat)
+        }
+        (App(EDIV, [], _), [.., T::Int, T::Nat]) => {
+            pop!();
+            stack[0] = Type::new_option(Type::new_pair(T::Int, T::Nat));
+            I::Ediv(overloads::Ediv::NatInt)
+        }
+        (App(EDIV, [], _), [.., T::Nat, T::Int]) => {
+            pop!();
+            stack[0] = Type::new_option(Type::new_pair(T::Int, T::Nat));
+            I::Ediv(overloads::Ediv::IntNat)
+        }
+        (App(EDIV, [], _), [.., T::Int, T::Int]) => {
+            pop!();
+            stack[0] = Type::new_option(Type::new_pair(T::Int, T::Nat));
+            I::Ediv(overloads::Ediv::IntInt)
+        }
+        (App(EDIV, [], _), [.., T::Nat, T::Mutez]) => {
+            pop!();
+            stack[0] = Type::new_option(Type::new_pair(T::Mutez, T::Mutez));
+            I::Ediv(overloads::Ediv::MutezNat)
+        }
+        (App(EDIV, [], _), [.., T::Mutez, T::Mutez]) => {
+            pop!();
+            stack[0] = Type::new_option(Type::new_pair(T::Nat, T::Mutez));
+            I::Ediv(overloads::Ediv::MutezMutez)
+        }
+        (App(EDIV, [], _), [.., _, _]) => no_overload!(EDIV),
+        (App(EDIV, [], _), [_] | []) => no_overload!(EDIV, len 2),
+        (App(EDIV, expect_args!(0), _), _) => unexpected_micheline!(),
+
         (App(AND, [], _), [.., T::Nat, T::Nat]) => {
             pop!();
             I::And(overloads::And::NatNat)
@@ -880,6 +1110,22 @@ pub(crate) fn typecheck_instruction<'a>(
         (App(NOT, [], _), []) => no_overload!(NOT, len 1),
         (App(NOT, expect_args!(0), _), _) => unexpected_micheline!(),
 
+        (App(LSL, [], _), [.., T::Nat, T::Nat]) => {
+            pop!();
+            I::Lsl
+        }
+        (App(LSL, [], _), [.., _, _]) => no_overload!(LSL),
+        (App(LSL, [], _), [_] | []) => no_overload!(LSL, len 2),
+        (App(LSL, expect_args!(0), _), _) => unexpected_micheline!(),
+
+        (App(LSR, [], _), [.., T::Nat, T::Nat]) => {
+            pop!();
+            I::Lsr
+        }
+        (App(LSR, [], _), [.., _, _]) => no_overload!(LSR),
+        (App(LSR, [], _), [_] | []) => no_overload!(LSR, len 2),
+        (App(LSR, expect_args!(0), _), _) => unexpected_micheline!(),
+
         (App(DIP, args, _), ..) => {
             let (opt_height, nested) = match args {
                 [Int(height), Seq(nested)] => (Option::Some(validate_u10(height)?), nested),
@@ -888,7 +1134,8 @@ pub(crate) fn typecheck_instruction<'a>(
             };
             let protected_height = opt_height.unwrap_or(1) as usize;
 
-            ctx.gas.consume(gas::tc_cost::dip_n(&opt_height)?)?;
+            ctx.gas
+                .consume(gas::tc_cost::dip_n(ctx.gas.costs(), &opt_height)?)?;
 
             ensure_stack_len(Prim::DIP, stack, protected_height)?;
             // Here we split off the protected portion of the stack, typecheck the code with the
@@ -908,7 +1155,8 @@ pub(crate) fn typecheck_instruction<'a>(
                 _ => unexpected_micheline!(),
             };
             let drop_height: usize = opt_height.unwrap_or(1) as usize;
-            ctx.gas.consume(gas::tc_cost::drop_n(&opt_height)?)?;
+            ctx.gas
+                .consume(gas::tc_cost::drop_n(ctx.gas.costs(), &opt_height)?)?;
             ensure_stack_len(Prim::DROP, stack, drop_height)?;
             stack.drop_top(drop_height);
             I::Drop(opt_height)
@@ -932,8 +1180,9 @@ pub(crate) fn typecheck_instruction<'a>(
 
         (App(DIG, [Int(height)], _), ..) => {
             let dig_height = validate_u10(height)?;
-            ensure_stack_len(Prim::DIG, stack, dig_height as usize)?;
-            ctx.gas.consume(gas::tc_cost::dig_n(dig_height as usize)?)?;
+            ensure_stack_len(Prim::DIG, stack, dig_height as usize + 1)?;
+            ctx.gas
+                .consume(gas::tc_cost::dig_n(ctx.gas.costs(), dig_height as usize)?)?;
             if dig_height > 0 {
                 let e = stack.remove(dig_height as usize);
                 stack.push(e);
@@ -945,9 +1194,10 @@ pub(crate) fn typecheck_instruction<'a>(
 
         (App(DUG, [Int(height)], _), ..) => {
             let dug_height = validate_u10(height)?;
-            ctx.gas.consume(gas::tc_cost::dug_n(dug_height as usize)?)?;
+            ensure_stack_len(Prim::DUG, stack, dug_height as usize + 1)?;
+            ctx.gas
+                .consume(gas::tc_cost::dug_n(ctx.gas.costs(), dug_height as usize)?)?;
             if dug_height > 0 {
-                ensure_stack_len(Prim::DUG, stack, dug_height as usize)?;
                 let e = pop!();
                 stack.insert(dug_height as usize, e);
             }
@@ -1303,6 +1553,18 @@ pub(crate) fn typecheck_instruction<'a>(
         }
         (App(UNIT, ..), _) => unexpected_micheline!(),
 
+        (App(CAST, [ty], _), [.., stk_ty]) => {
+            let ty = parse_ty(ctx, ty)?;
+            ensure_ty_eq(&mut ctx.gas, &ty, stk_ty)?;
+            I::Cast
+        }
+        (App(CAST, [_], _), []) => no_overload!(CAST, len 1),
+        (App(CAST, expect_args!(1), _), _) => unexpected_micheline!(),
+
+        (App(RENAME, [], _), [.., _]) => I::Rename,
+        (App(RENAME, [], _), []) => no_overload!(RENAME, len 1),
+        (App(RENAME, expect_args!(0), _), _) => unexpected_micheline!(),
+
         (App(CAR, [], _), [.., T::Pair(..)]) => {
             let l = pop!(T::Pair).0.clone();
             stack.push(l);
@@ -1335,7 +1597,8 @@ pub(crate) fn typecheck_instruction<'a>(
             if stack.len() < n as usize {
                 no_overload!(PAIR, len n as usize);
             }
-            ctx.gas.consume(tc_cost::pair_n(n as usize)?)?;
+            ctx.gas
+                .consume(tc_cost::pair_n(ctx.gas.costs(), n as usize)?)?;
             // unwrap is fine, n is non-zero.
             let res = stack
                 .drain_top(n as usize)
@@ -1360,7 +1623,8 @@ pub(crate) fn typecheck_instruction<'a>(
             if n < 2 {
                 return Err(TcError::PairN01(UNPAIR, n));
             }
-            ctx.gas.consume(tc_cost::unpair_n(n as usize)?)?;
+            ctx.gas
+                .consume(tc_cost::unpair_n(ctx.gas.costs(), n as usize)?)?;
             fn fill(n: u16, stack: &mut Stack<Type>, p: &Type) -> Result<(), TcError> {
                 if n == 0 {
                     stack.push(p.clone());
@@ -1468,6 +1732,15 @@ pub(crate) fn typecheck_instruction<'a>(
         }
         (App(EMPTY_SET, expect_args!(1), _), _) => unexpected_micheline!(),
 
+        (App(EMPTY_MAP, [kty, vty], _), _) => {
+            let kty = parse_ty(ctx, kty)?;
+            kty.ensure_prop(&mut ctx.gas, TypeProperty::Comparable)?;
+            let vty = parse_ty(ctx, vty)?;
+            stack.push(T::new_map(kty, vty));
+            I::EmptyMap
+        }
+        (App(EMPTY_MAP, expect_args!(2), _), _) => unexpected_micheline!(),
+
         (App(EMPTY_BIG_MAP, [kty, vty], _), _) => {
             let kty = parse_ty(ctx, kty)?;
             kty.ensure_prop(&mut ctx.gas, TypeProperty::Comparable)?;
@@ -1525,7 +1798,8 @@ pub(crate) fn typecheck_instruction<'a>(
             // no_overload! below won't report the type on the top of the stack.
             let ty = &mut stack[0];
             let n = validate_u10(n)?;
-            ctx.gas.consume(tc_cost::get_n(n as usize)?)?;
+            ctx.gas
+                .consume(tc_cost::get_n(ctx.gas.costs(), n as usize)?)?;
             let res = match get_nth_field_ref(n, ty) {
                 Ok(res) => res,
                 Err(ty) => no_overload!(GET, NMOR::ExpectedPair(ty)),
@@ -2103,7 +2377,7 @@ pub(crate) fn typecheck_value<'a>(
     use Micheline as V;
     use Type as T;
     use TypedValue as TV;
-    ctx.gas.consume(gas::tc_cost::VALUE_STEP)?;
+    ctx.gas.consume(ctx.gas.costs().tc.value_step)?;
     macro_rules! invalid_value_for_type {
         () => {
             TcError::InvalidValueForType(format!("{v:?}"), t.clone())
@@ -2192,14 +2466,14 @@ pub(crate) fn typecheck_value<'a>(
             })
         }
         (T::Address, V::String(str)) => {
-            ctx.gas.consume(gas::tc_cost::KEY_HASH_READABLE)?;
+            ctx.gas.consume(ctx.gas.costs().tc.key_hash_readable)?;
             TV::Address(
                 Address::from_base58_check(str)
                     .map_err(|e| TcError::ByteReprError(T::Address, e))?,
             )
         }
         (T::Address, V::Bytes(bs)) => {
-            ctx.gas.consume(gas::tc_cost::KEY_HASH_OPTIMIZED)?;
+            ctx.gas.consume(ctx.gas.costs().tc.key_hash_optimized)?;
             TV::Address(Address::from_bytes(bs).map_err(|e| TcError::ByteReprError(T::Address, e))?)
         }
         (T::Contract(ty), addr) => {
@@ -2208,53 +2482,53 @@ pub(crate) fn typecheck_value<'a>(
                 .map(TypedValue::Contract)?
         }
         (T::ChainId, V::String(str)) => {
-            ctx.gas.consume(gas::tc_cost::CHAIN_ID_READABLE)?;
+            ctx.gas.consume(ctx.gas.costs().tc.chain_id_readable)?;
             TV::ChainId(
                 ChainId::from_base58_check(str).map_err(|x| TcError::ChainIdError(x.into()))?,
             )
         }
         (T::ChainId, V::Bytes(bs)) => {
             use tezos_crypto_rs::hash::HashTrait;
-            ctx.gas.consume(gas::tc_cost::CHAIN_ID_OPTIMIZED)?;
+            ctx.gas.consume(ctx.gas.costs().tc.chain_id_optimized)?;
             TV::ChainId(ChainId::try_from_bytes(bs).map_err(|x| TcError::ChainIdError(x.into()))?)
         }
         (T::Bytes, V::Bytes(bs)) => TV::Bytes(bs.clone()),
         (T::Key, V::String(str)) => {
-            ctx.gas.consume(gas::tc_cost::KEY_READABLE)?;
+            ctx.gas.consume(ctx.gas.costs().tc.key_readable)?;
             TV::Key(Key::from_base58_check(str).map_err(|e| TcError::ByteReprError(T::Key, e))?)
         }
         (T::Key, V::Bytes(bs)) => {
-            ctx.gas.consume(gas::tc_cost::KEY_OPTIMIZED)?;
+            ctx.gas.consume(ctx.gas.costs().tc.key_optimized)?;
             TV::Key(Key::from_bytes(bs).map_err(|e| TcError::ByteReprError(T::Key, e))?)
         }
         (T::Signature, V::String(str)) => {
-            ctx.gas.consume(gas::tc_cost::KEY_READABLE)?;
+            ctx.gas.consume(ctx.gas.costs().tc.key_readable)?;
             TV::Signature(
                 Signature::from_base58_check(str)
                     .map_err(|e| TcError::ByteReprError(T::Signature, e))?,
             )
         }
         (T::Signature, V::Bytes(bs)) => {
-            ctx.gas.consume(gas::tc_cost::KEY_OPTIMIZED)?;
+            ctx.gas.consume(ctx.gas.costs().tc.key_optimized)?;
             TV::Signature(
                 Signature::from_bytes(bs).map_err(|e| TcError::ByteReprError(T::Signature, e))?,
             )
         }
         (T::KeyHash, V::String(str)) => {
-            ctx.gas.consume(gas::tc_cost::KEY_HASH_READABLE)?;
+            ctx.gas.consume(ctx.gas.costs().tc.key_hash_readable)?;
             TV::KeyHash(
                 KeyHash::from_base58_check(str)
                     .map_err(|e| TcError::ByteReprError(T::KeyHash, e))?,
             )
         }
         (T::KeyHash, V::Bytes(bs)) => {
-            ctx.gas.consume(gas::tc_cost::KEY_HASH_OPTIMIZED)?;
+            ctx.gas.consume(ctx.gas.costs().tc.key_hash_optimized)?;
             TV::KeyHash(KeyHash::from_bytes(bs).map_err(|e| TcError::ByteReprError(T::KeyHash, e))?)
         }
         (T::Timestamp, V::Int(n)) => TV::Timestamp(n.clone()),
         (T::Timestamp, V::String(n)) => {
             ctx.gas
-                .consume(gas::tc_cost::timestamp_decoding(n.len())?)?;
+                .consume(gas::tc_cost::timestamp_decoding(ctx.gas.costs(), n.len())?)?;
             let dt = DateTime::parse_from_rfc3339(n)
                 .map_err(|e| TcError::InvalidValueForType(e.to_string(), T::Timestamp))?;
             TV::Timestamp(dt.timestamp().into())
@@ -2291,19 +2565,19 @@ pub(crate) fn typecheck_value<'a>(
             }
         }
         (T::Bls12381Fr, V::Int(i)) => {
-            ctx.gas.consume(gas::tc_cost::BLS_FR)?;
+            ctx.gas.consume(ctx.gas.costs().tc.bls_fr)?;
             TV::Bls12381Fr(bls::Fr::from_big_int(i))
         }
         (T::Bls12381Fr, V::Bytes(bs)) => {
-            ctx.gas.consume(gas::tc_cost::BLS_FR)?;
+            ctx.gas.consume(ctx.gas.costs().tc.bls_fr)?;
             TV::Bls12381Fr(bls::Fr::from_bytes(bs).ok_or_else(|| invalid_value_for_type!())?)
         }
         (T::Bls12381G1, V::Bytes(bs)) => {
-            ctx.gas.consume(gas::tc_cost::BLS_G1)?;
+            ctx.gas.consume(ctx.gas.costs().tc.bls_g1)?;
             TV::new_bls12381_g1(bls::G1::from_bytes(bs).ok_or_else(|| invalid_value_for_type!())?)
         }
         (T::Bls12381G2, V::Bytes(bs)) => {
-            ctx.gas.consume(gas::tc_cost::BLS_G2)?;
+            ctx.gas.consume(ctx.gas.costs().tc.bls_g2)?;
             TV::new_bls12381_g2(bls::G2::from_bytes(bs).ok_or_else(|| invalid_value_for_type!())?)
         }
         (_, _) => return Err(invalid_value_for_type!()),
@@ -2405,8 +2679,8 @@ where
                 let mut ctx = self.ctx.borrow_mut();
                 let cur_key = (self.to_key)(&cur);
                 let next_key = (self.to_key)(next);
-                ctx.gas
-                    .consume(gas::interpret_cost::compare(cur_key, next_key)?)?;
+                let cost = gas::interpret_cost::compare(ctx.gas.costs(), cur_key, next_key)?;
+                ctx.gas.consume(cost)?;
                 match cur_key.cmp(next_key) {
                     std::cmp::Ordering::Less => (),
                     std::cmp::Ordering::Equal => {
@@ -2433,6 +2707,7 @@ fn typecheck_set<'a>(
     vs: &[Micheline<'a>],
 ) -> Result<BTreeSet<TypedValue<'a>>, TcError> {
     ctx.gas.consume(gas::tc_cost::construct_set(
+        ctx.gas.costs(),
         elem_ty.size_for_gas(),
         vs.len(),
     )?)?;
@@ -2459,6 +2734,7 @@ fn typecheck_map<'a, V>(
     value_mapper: fn(TypedValue<'a>) -> V,
 ) -> Result<BTreeMap<TypedValue<'a>, V>, TcError> {
     ctx.gas.consume(gas::tc_cost::construct_map(
+        ctx.gas.costs(),
         key_type.size_for_gas(),
         vs.len(),
     )?)?;
@@ -2548,7 +2824,11 @@ fn ensure_stacks_eq(ctx: &mut Ctx, stack1: &TypeStack, stack2: &TypeStack) -> Re
 }
 
 fn ensure_ty_eq(gas: &mut Gas, ty1: &Type, ty2: &Type) -> Result<(), TcError> {
-    gas.consume(gas::tc_cost::ty_eq(ty1.size_for_gas(), ty2.size_for_gas())?)?;
+    gas.consume(gas::tc_cost::ty_eq(
+        gas.costs(),
+        ty1.size_for_gas(),
+        ty2.size_for_gas(),
+    )?)?;
     if ty1 != ty2 {
         Err(TypesNotEqual(ty1.clone(), ty2.clone()).into())
     } else {
@@ -2577,6 +2857,23 @@ mod typecheck_tests {
         super::typecheck_instruction(i, ctx, None, opt_stack)
     }
 
+    #[test]
+    fn annotations_ignored() {
+        let typecheck = |src| {
+            let mut stack = tc_stk![];
+            let instr =
+                typecheck_instruction(&parse(src).unwrap(), &mut Ctx::default(), &mut stack);
+            (instr, stack)
+        };
+        assert_eq!(
+            typecheck(
+                "{ PUSH @n nat 2 ; PUSH @i (int :t) 1 ; PAIR @p %x %y ;
+                   DUP @p2 ; CAR %x ; DIP { CDR @cdr } ; DROP }"
+            ),
+            typecheck("{ PUSH nat 2 ; PUSH int 1 ; PAIR ; DUP ; CAR ; DIP { CDR } ; DROP }")
+        );
+    }
+
     #[test]
     fn test_dup() {
         let mut stack = tc_stk![Type::Nat];
@@ -2940,6 +3237,218 @@ mod typecheck_tests {
         assert_eq!(ctx.gas.milligas(), Gas::default().milligas() - 440);
     }
 
+    #[test]
+    fn test_add_timestamp() {
+        for (stack, overload) in [
+            (
+                tc_stk![Type::Int, Type::Timestamp],
+                overloads::Add::TimestampInt,
+            ),
+            (
+                tc_stk![Type::Timestamp, Type::Int],
+                overloads::Add::IntTimestamp,
+            ),
+        ] {
+            let mut stack = stack;
+            let mut ctx = Ctx::default();
+            assert_eq!(
+                typecheck_instruction(&app!(ADD), &mut ctx, &mut stack),
+                Ok(Add(overload))
+            );
+            assert_eq!(stack, tc_stk![Type::Timestamp]);
+            assert_eq!(ctx.gas.milligas(), Gas::default().milligas() - 440);
+        }
+    }
+
+    #[test]
+    fn test_sub() {
+        for (stack, overload, result) in [
+            (
+                tc_stk![Type::Nat, Type::Nat],
+                overloads::Sub::NatNat,
+                Type::Int,
+            ),
+            (
+                tc_stk![Type::Int, Type::Int],
+                overloads::Sub::IntInt,
+                Type::Int,
+            ),
+            (
+                tc_stk![Type::Nat, Type::Int],
+                overloads::Sub::IntNat,
+                Type::Int,
+            ),
+            (
+                tc_stk![Type::Int, Type::Nat],
+                overloads::Sub::NatInt,
+                Type::Int,
+            ),
+            (
+                tc_stk![Type::Mutez, Type::Mutez],
+                overloads::Sub::MutezMutez,
+                Type::Mutez,
+            ),
+            (
+                tc_stk![Type::Int, Type::Timestamp],
+                overloads::Sub::TimestampInt,
+                Type::Timestamp,
+            ),
+            (
+                tc_stk![Type::Timestamp, Type::Timestamp],
+                overloads::Sub::TimestampTimestamp,
+                Type::Int,
+            ),
+        ] {
+            let mut stack = stack;
+            let mut ctx = Ctx::default();
+            assert_eq!(
+                typecheck_instruction(&app!(SUB), &mut ctx, &mut stack),
+                Ok(Sub(overload))
+            );
+            assert_eq!(stack, tc_stk![result]);
+            assert_eq!(ctx.gas.milligas(), Gas::default().milligas() - 440);
+        }
+    }
+
+    #[test]
+    fn test_sub_mismatch() {
+        let mut stack = tc_stk![Type::Timestamp, Type::Int];
+        let mut ctx = Ctx::default();
+        assert_eq!(
+            typecheck_instruction(&app!(SUB), &mut ctx, &mut stack),
+            Err(TcError::NoMatchingOverload {
+                instr: Prim::SUB,
+                stack: stk![Type::Timestamp, Type::Int],
+                reason: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_sub_short() {
+        too_short_test(&app!(SUB), Prim::SUB, 2);
+    }
+
+    #[test]
+    fn test_ediv() {
+        for (stack, overload, quotient, remainder) in [
+            (
+                tc_stk![Type::Nat, Type::Nat],
+                overloads::Ediv::NatNat,
+                Type::Nat,
+                Type::Nat,
+            ),
+            (
+                tc_stk![Type::Nat, Type::Int],
+                overloads::Ediv::IntNat,
+                Type::Int,
+                Type::Nat,
+            ),
+            (
+                tc_stk![Type::Int, Type::Nat],
+                overloads::Ediv::NatInt,
+                Type::Int,
+                Type::Nat,
+            ),
+            (
+                tc_stk![Type::Int, Type::Int],
+                overloads::Ediv::IntInt,
+                Type::Int,
+                Type::Nat,
+            ),
+            (
+                tc_stk![Type::Nat, Type::Mutez],
+                overloads::Ediv::MutezNat,
+                Type::Mutez,
+                Type::Mutez,
+            ),
+            (
+                tc_stk![Type::Mutez, Type::Mutez],
+                overloads::Ediv::MutezMutez,
+                Type::Nat,
+                Type::Mutez,
+            ),
+        ] {
+            let mut stack = stack;
+            let mut ctx = Ctx::default();
+            assert_eq!(
+                typecheck_instruction(&app!(EDIV), &mut ctx, &mut stack),
+                Ok(Ediv(overload))
+            );
+            assert_eq!(
+                stack,
+                tc_stk![Type::new_option(Type::new_pair(quotient, remainder))]
+            );
+        }
+    }
+
+    #[test]
+    fn test_ediv_mismatch() {
+        let mut stack = tc_stk![Type::Mutez, Type::Nat];
+        let mut ctx = Ctx::default();
+        assert_eq!(
+            typecheck_instruction(&app!(EDIV), &mut ctx, &mut stack),
+            Err(TcError::NoMatchingOverload {
+                instr: Prim::EDIV,
+                stack: stk![Type::Mutez, Type::Nat],
+                reason: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_ediv_short() {
+        too_short_test(&app!(EDIV), Prim::EDIV, 2);
+    }
+
+    mod comparisons {
+        use super::*;
+
+        macro_rules! test {
+            ($prim:ident, $instr:ident) => {
+                #[allow(non_snake_case)]
+                mod $prim {
+                    use super::*;
+
+                    #[test]
+                    fn ok() {
+                        let mut stack = tc_stk![Type::Int];
+                        assert_eq!(
+                            typecheck_instruction(&app!($prim), &mut Ctx::default(), &mut stack),
+                            Ok($instr)
+                        );
+                        assert_eq!(stack, tc_stk![Type::Bool]);
+                    }
+
+                    #[test]
+                    fn mismatch() {
+                        let mut stack = tc_stk![Type::Nat];
+                        assert_eq!(
+                            typecheck_instruction(&app!($prim), &mut Ctx::default(), &mut stack),
+                            Err(TcError::NoMatchingOverload {
+                                instr: Prim::$prim,
+                                stack: stk![Type::Nat],
+                                reason: Some(TypesNotEqual(Type::Int, Type::Nat).into())
+                            })
+                        );
+                    }
+
+                    #[test]
+                    fn too_short() {
+                        too_short_test(&app!($prim), Prim::$prim, 1);
+                    }
+                }
+            };
+        }
+
+        test!(EQ, Eq);
+        test!(NEQ, Neq);
+        test!(LT, Lt);
+        test!(GT, Gt);
+        test!(LE, Le);
+        test!(GE, Ge);
+    }
+
     #[test]
     fn test_binary_bitwise_operators() {
         for ty in &[Type::Bool, Type::Nat, Type::Bytes] {
@@ -3087,6 +3596,41 @@ mod typecheck_tests {
         );
     }
 
+    #[test]
+    fn test_shifts() {
+        for (prim, instr) in [(Prim::LSL, Lsl), (Prim::LSR, Lsr)] {
+            let mut stack = tc_stk![Type::Nat, Type::Nat];
+            let mut ctx = Ctx::default();
+            assert_eq!(
+                typecheck_instruction(&Micheline::App(prim, &[], NO_ANNS), &mut ctx, &mut stack),
+                Ok(instr)
+            );
+            assert_eq!(stack, tc_stk![Type::Nat]);
+        }
+    }
+
+    #[test]
+    fn test_shifts_mismatch() {
+        for prim in [Prim::LSL, Prim::LSR] {
+            let mut stack = tc_stk![Type::Int, Type::Nat];
+            let mut ctx = Ctx::default();
+            assert_eq!(
+                typecheck_instruction(&Micheline::App(prim, &[], NO_ANNS), &mut ctx, &mut stack),
+                Err(TcError::NoMatchingOverload {
+                    instr: prim,
+                    stack: stk![Type::Int, Type::Nat],
+                    reason: None
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_shifts_short() {
+        too_short_test(&app!(LSL), Prim::LSL, 2);
+        too_short_test(&app!(LSR), Prim::LSR, 2);
+    }
+
     #[test]
     fn test_add_bls12_381_fr() {
         let mut stack = tc_stk![Type::Bls12381Fr, Type::Bls12381Fr];
@@ -3521,10 +4065,26 @@ mod typecheck_tests {
 
     #[test]
     fn test_never() {
+        let mut stack = tc_stk![Type::Never];
         assert_eq!(
-            typecheck_instruction(&app!(NEVER), &mut Ctx::default(), &mut tc_stk![Type::Never]),
+            typecheck_instruction(&app!(NEVER), &mut Ctx::default(), &mut stack),
             Ok(Never)
         );
+        assert_eq!(stack, FailingTypeStack::Failed);
+    }
+
+    #[test]
+    fn test_never_closes_if_left() {
+        let mut stack = tc_stk![Type::new_or(Type::Int, Type::Never)];
+        assert_eq!(
+            typecheck_instruction(
+                &parse("IF_LEFT {} { NEVER }").unwrap(),
+                &mut Ctx::default(),
+                &mut stack
+            ),
+            Ok(IfLeft(vec![], vec![Never]))
+        );
+        assert_eq!(stack, tc_stk![Type::Int]);
     }
 
     #[test]
@@ -3562,6 +4122,32 @@ mod typecheck_tests {
         test_ok!("{ PUSH (or never unit) (Right Unit); IF_LEFT { NEVER } {}; DROP }");
     }
 
+    #[test]
+    fn unify_failed_stacks() {
+        let live = || FailingTypeStack::from(stk![Type::Int]);
+        for (dest, aux) in [
+            (live(), FailingTypeStack::Failed),
+            (FailingTypeStack::Failed, live()),
+            (live(), live()),
+        ] {
+            let mut dest = dest;
+            assert_eq!(unify_stacks(&mut Ctx::default(), &mut dest, aux), Ok(()));
+            assert_eq!(dest, live());
+        }
+
+        let mut dest = FailingTypeStack::Failed;
+        assert_eq!(
+            unify_stacks(&mut Ctx::default(), &mut dest, FailingTypeStack::Failed),
+            Ok(())
+        );
+        assert_eq!(dest, FailingTypeStack::Failed);
+
+        assert!(matches!(
+            unify_stacks(&mut Ctx::default(), &mut live(), tc_stk![Type::Nat]),
+            Err(TcError::StacksNotEqual(..))
+        ));
+    }
+
     #[test]
     fn string_values() {
         assert_eq!(
@@ -3753,6 +4339,52 @@ mod typecheck_tests {
         assert_eq!(stack, tc_stk![Type::Unit]);
     }
 
+    #[test]
+    fn cast() {
+        let mut stack = tc_stk![Type::Nat, Type::new_pair(Type::Int, Type::Unit)];
+        assert_eq!(
+            typecheck_instruction(
+                &parse("CAST (pair (int :a) unit)").unwrap(),
+                &mut Ctx::default(),
+                &mut stack
+            ),
+            Ok(Cast)
+        );
+        assert_eq!(
+            stack,
+            tc_stk![Type::Nat, Type::new_pair(Type::Int, Type::Unit)]
+        );
+    }
+
+    #[test]
+    fn cast_mismatch() {
+        let mut stack = tc_stk![Type::Nat];
+        assert_eq!(
+            typecheck_instruction(&parse("CAST int").unwrap(), &mut Ctx::default(), &mut stack),
+            Err(TcError::TypesNotEqual(TypesNotEqual(Type::Int, Type::Nat)))
+        );
+    }
+
+    #[test]
+    fn cast_too_short() {
+        too_short_test(&parse("CAST int").unwrap(), Prim::CAST, 1);
+    }
+
+    #[test]
+    fn rename() {
+        let mut stack = tc_stk![Type::Nat];
+        assert_eq!(
+            typecheck_instruction(
+                &parse("RENAME @x").unwrap(),
+                &mut Ctx::default(),
+                &mut stack
+            ),
+            Ok(Rename)
+        );
+        assert_eq!(stack, tc_stk![Type::Nat]);
+        too_short_test(&app!(RENAME), Prim::RENAME, 1);
+    }
+
     #[test]
     fn push_pair_value() {
         let mut stack = tc_stk![];
@@ -4682,6 +5314,36 @@ mod typecheck_tests {
         assert_eq!(stack, tc_stk![Type::new_set(Type::Int)]);
     }
 
+    #[test]
+    fn empty_map() {
+        let mut stack = tc_stk![];
+        assert_eq!(
+            typecheck_instruction(
+                &parse("EMPTY_MAP int string").unwrap(),
+                &mut Ctx::default(),
+                &mut stack
+            ),
+            Ok(EmptyMap)
+        );
+        assert_eq!(stack, tc_stk![Type::new_map(Type::Int, Type::String)]);
+    }
+
+    #[test]
+    fn empty_map_incomparable() {
+        let mut stack = tc_stk![];
+        assert_eq!(
+            typecheck_instruction(
+                &parse("EMPTY_MAP (list int) string").unwrap(),
+                &mut Ctx::default(),
+                &mut stack
+            ),
+            Err(TcError::InvalidTypeProperty(
+                TypeProperty::Comparable,
+                Type::new_list(Type::Int)
+            ))
+        );
+    }
+
     #[test]
     fn empty_big_map() {
         let mut stack = tc_stk![];
@@ -5334,6 +5996,31 @@ mod typecheck_tests {
                 reason: None
             })
         );
+        let mut stack = tc_stk![Type::Timestamp, Type::Timestamp];
+        assert_eq!(
+            typecheck_instruction(&app!(ADD), &mut ctx, &mut stack),
+            Err(TcError::NoMatchingOverload {
+                instr: Prim::ADD,
+                stack: stk![Type::Timestamp, Type::Timestamp],
+                reason: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_add_bools() {
+        assert_eq!(
+            typecheck_instruction(
+                &parse("{ PUSH bool True; PUSH bool False; ADD }").unwrap(),
+                &mut Ctx::default(),
+                &mut tc_stk![],
+            ),
+            Err(TcError::NoMatchingOverload {
+                instr: Prim::ADD,
+                stack: stk![Type::Bool, Type::Bool],
+                reason: None
+            })
+        );
     }
 
     #[test]
@@ -6259,6 +6946,21 @@ mod typecheck_tests {
         );
     }
 
+    #[test]
+    fn pack_instr_big_map() {
+        assert_eq!(
+            typecheck_instruction(
+                &parse("{ EMPTY_BIG_MAP int unit; PACK }").unwrap(),
+                &mut Ctx::default(),
+                &mut tc_stk![]
+            ),
+            Err(TcError::InvalidTypeProperty(
+                TypeProperty::Packable,
+                Type::new_big_map(Type::Int, Type::Unit)
+            ))
+        );
+    }
+
     #[test]
     fn self_instr() {
         let stk = &mut tc_stk![];
@@ -6988,6 +7690,40 @@ mod typecheck_tests {
         );
     }
 
+    #[test]
+    fn push_lambda_ignores_outer_stack() {
+        // the body is typechecked against the lambda's own input, not the
+        // current stack
+        let mut stack = tc_stk![Type::String];
+        assert_eq!(
+            typecheck_instruction(
+                &parse("PUSH (lambda int int) { PUSH int 1; ADD }").unwrap(),
+                &mut Ctx::default(),
+                &mut stack
+            ),
+            Ok(Push(TypedValue::Lambda(Closure::Lambda(Lambda::Lambda {
+                micheline_code: seq! { app!(PUSH[app!(int), 1]); app!(ADD) },
+                code: vec![Push(TypedValue::int(1)), Add(overloads::Add::IntInt)].into()
+            }))))
+        );
+        assert_eq!(
+            stack,
+            tc_stk![Type::String, Type::new_lambda(Type::Int, Type::Int)]
+        );
+        assert_eq!(
+            typecheck_instruction(
+                &parse(r#"PUSH (lambda int int) { PUSH string "x" }"#).unwrap(),
+                &mut Ctx::default(),
+                &mut tc_stk![Type::String]
+            ),
+            Err(TcError::StacksNotEqual(
+                stk![Type::Int, Type::String],
+                stk![Type::Int],
+                StacksNotEqualReason::LengthsDiffer(2, 1)
+            ))
+        );
+    }
+
     #[test]
     fn push_lambda_bad_input() {
         assert_eq!(
@@ -7703,6 +8439,14 @@ mod typecheck_tests {
         );
     }
 
+    #[test]
+    fn dig_dug_short() {
+        too_short_test(&parse("DIG 0").unwrap(), Prim::DIG, 1);
+        too_short_test(&parse("DIG 3").unwrap(), Prim::DIG, 4);
+        too_short_test(&parse("DUG 0").unwrap(), Prim::DUG, 1);
+        too_short_test(&parse("DUG 3").unwrap(), Prim::DUG, 4);
+    }
+
     #[test]
     fn unpack() {
         let stk = &mut tc_stk![Type::Bytes];
@@ -7932,4 +8676,244 @@ mod typecheck_tests {
             })
         );
     }
+
+    #[test]
+    fn typecheck_with_policy() {
+        let policy = InstructionPolicy::new().deny(Prim::FAILWITH);
+        let typecheck = |src| {
+            parse(src).unwrap().typecheck_with_policy(
+                &mut Ctx::default(),
+                None,
+                &[app!(bool)],
+                &policy,
+            )
+        };
+        assert_eq!(
+            typecheck("{ FAILWITH }"),
+            Err(TcError::InstructionForbidden(Prim::FAILWITH))
+        );
+        assert_eq!(
+            typecheck("{ IF { PUSH nat 1; FAILWITH } {} }"),
+            Err(TcError::InstructionForbidden(Prim::FAILWITH))
+        );
+        assert_eq!(
+            typecheck("{ DROP; PUSH (lambda unit unit) { FAILWITH } }"),
+            Err(TcError::InstructionForbidden(Prim::FAILWITH))
+        );
+        assert_eq!(
+            typecheck("{ NOT; DROP }"),
+            Ok(Seq(vec![Not(overloads::Not::Bool), Drop(None)]))
+        );
+    }
+
+    #[test]
+    fn typecheck_with_default_policy() {
+        assert!(parse("{ FAILWITH }")
+            .unwrap()
+            .typecheck_with_policy(
+                &mut Ctx::default(),
+                None,
+                &[app!(bool)],
+                &InstructionPolicy::default(),
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn typecheck_with_warnings() {
+        use crate::parser::{spans::Span, Parser};
+        use lint::{Warning, WarningKind};
+        let typecheck = |src| {
+            let parser = Parser::with_spans();
+            let (_, warnings) = parser
+                .parse(src)
+                .unwrap()
+                .typecheck_with_warnings(
+                    &mut Ctx::default(),
+                    None,
+                    &[app!(int), app!(int)],
+                    &parser.spans,
+                )
+                .unwrap();
+            warnings
+        };
+        let span = |start, end| Some(Span { start, end });
+        assert_eq!(
+            typecheck("{ SWAP; SWAP; ADD }"),
+            vec![Warning {
+                kind: WarningKind::SwapSwap,
+                span: span(2, 12)
+            }]
+        );
+        assert_eq!(
+            typecheck("{ ADD; DUP; DROP }"),
+            vec![Warning {
+                kind: WarningKind::DupDrop,
+                span: span(7, 16)
+            }]
+        );
+        assert_eq!(
+            typecheck("{ DIP { DUP 1; DROP 1 }; ADD }"),
+            vec![Warning {
+                kind: WarningKind::DupDrop,
+                span: span(8, 21)
+            }]
+        );
+        assert_eq!(typecheck("{ SWAP; DUP; ADD; DROP; DROP }"), vec![]);
+        assert_eq!(
+            typecheck("{ DUP 2; DROP; ADD }"),
+            vec![Warning {
+                kind: WarningKind::DupDrop,
+                span: span(2, 13)
+            }]
+        );
+        assert_eq!(typecheck("{ DUP; DROP 2; UNIT }"), vec![]);
+        assert_eq!(
+            typecheck("{ SWAP; SWAP; ADD }")[0].to_string(),
+            "warning at 2:12: SWAP immediately followed by SWAP is a no-op"
+        );
+    }
+
+    #[test]
+    fn typecheck_with_warnings_no_spans() {
+        use lint::{Warning, WarningKind};
+        let (_, warnings) = parse("{ SWAP; SWAP; ADD }")
+            .unwrap()
+            .typecheck_with_warnings(
+                &mut Ctx::default(),
+                None,
+                &[app!(int), app!(int)],
+                &SpanMap::default(),
+            )
+            .unwrap();
+        assert_eq!(
+            warnings,
+            vec![Warning {
+                kind: WarningKind::SwapSwap,
+                span: None
+            }]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "warning: SWAP immediately followed by SWAP is a no-op"
+        );
+    }
+
+    #[test]
+    fn typecheck_with_warnings_fails() {
+        assert_eq!(
+            parse("{ SWAP; SWAP }").unwrap().typecheck_with_warnings(
+                &mut Ctx::default(),
+                None,
+                &[app!(int)],
+                &SpanMap::default()
+            ),
+            Err(TcError::NoMatchingOverload {
+                instr: Prim::SWAP,
+                stack: stk![Type::Int],
+                reason: Some(NoMatchingOverloadReason::StackTooShort { expected: 2 })
+            })
+        );
+    }
+
+    #[test]
+    fn typecheck_with_spans() {
+        use crate::parser::{spans::Span, Parser};
+
+        let parser = Parser::with_spans();
+        let src = "{ PUSH int 1; DIP { PUSH unit Unit; NEG } }";
+        let res = parser.parse(src).unwrap().typecheck_with_spans(
+            &mut Ctx::default(),
+            None,
+            &[],
+            &parser.spans,
+        );
+        let start = src.find("NEG").unwrap();
+        let err = res.unwrap_err();
+        assert_eq!(
+            err,
+            SpannedTcError {
+                span: Some(Span {
+                    start,
+                    end: start + 3
+                }),
+                error: TcError::NoMatchingOverload {
+                    instr: Prim::NEG,
+                    stack: stk![Type::Unit],
+                    reason: None
+                }
+            }
+        );
+        assert!(err.to_string().starts_with("error at 36:39: "));
+    }
+
+    #[test]
+    fn typecheck_with_spans_macro() {
+        use crate::parser::{spans::Span, Parser};
+
+        // the expansion of IFCMPEQ has no location, so the error inside it is
+        // reported at the macro
+        let parser = Parser::with_spans();
+        let src = "{ UNIT; IFCMPEQ {} {} }";
+        let err = parser
+            .parse(src)
+            .unwrap()
+            .typecheck_with_spans(&mut Ctx::default(), None, &[], &parser.spans)
+            .unwrap_err();
+        let start = src.find("IFCMPEQ").unwrap();
+        assert_eq!(
+            err.span,
+            Some(Span {
+                start,
+                end: start + "IFCMPEQ {} {}".len()
+            })
+        );
+    }
+
+    #[test]
+    fn typecheck_with_spans_unknown_location() {
+        use crate::parser::Parser;
+
+        // the root node isn't part of a sequence, so there's no span for it
+        let parser = Parser::with_spans();
+        let err = parser
+            .parse("NEG")
+            .unwrap()
+            .typecheck_with_spans(&mut Ctx::default(), None, &[app!(unit)], &parser.spans)
+            .unwrap_err();
+        assert_eq!(err.span, None);
+        assert_eq!(err.to_string(), err.error.to_string());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_error() {
+        use crate::parser::Parser;
+        use serde_json::json;
+
+        let parser = Parser::with_spans();
+        let src = "{ DROP; DUP 4 }";
+        let err = parser
+            .parse(src)
+            .unwrap()
+            .typecheck_with_spans(
+                &mut Ctx::default(),
+                None,
+                &[app!(unit), app!(int), app!(nat), app!(bool)],
+                &parser.spans,
+            )
+            .unwrap_err();
+        assert_eq!(
+            serde_json::to_value(err).unwrap(),
+            json!({
+                "span": { "start": 8, "end": 13 },
+                "kind": "NoMatchingOverload",
+                "details": {
+                    "instr": "DUP",
+                    "stack": ["Unit", "Int", "Nat"],
+                    "reason": { "kind": "StackTooShort", "details": { "expected": 4 } }
+                }
+            })
+        );
+    }
 }
@@ -0,0 +1,99 @@
+/******************************************************************************/
+/*                                                                            */
+/* SPDX-License-Identifier: MIT                                               */
+/* Copyright (c) [2023] Serokell <hi@serokell.io>                             */
+/*                                                                            */
+/******************************************************************************/
+
+//! Non-fatal warnings about code that typechecks, but can be simplified, see
+//! [Micheline::typecheck_with_warnings].
+
+use crate::ast::Micheline;
+use crate::lexer::Prim;
+use crate::parser::spans::{Span, SpanMap};
+
+/// The kind of a [Warning].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningKind {
+    /// `DUP`, or `DUP n`, immediately followed by `DROP`, which is a no-op.
+    DupDrop,
+    /// `SWAP` immediately followed by `SWAP`, which is a no-op.
+    SwapSwap,
+}
+
+impl std::fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WarningKind::DupDrop => write!(f, "DUP immediately followed by DROP is a no-op"),
+            WarningKind::SwapSwap => write!(f, "SWAP immediately followed by SWAP is a no-op"),
+        }
+    }
+}
+
+/// A warning about a suspicious sequence of instructions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// What is suspicious about the code.
+    pub kind: WarningKind,
+    /// Location of the offending instructions, if known.
+    pub span: Option<Span>,
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.span {
+            Some(span) => write!(f, "warning at {span}: {}", self.kind),
+            None => write!(f, "warning: {}", self.kind),
+        }
+    }
+}
+
+/// Collect warnings about the code, including nested code blocks and lambda
+/// literals. Locations are looked up in `spans`, see [SpanMap].
+pub fn lint(ast: &Micheline, spans: &SpanMap) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    lint_node(ast, spans, &mut warnings);
+    warnings
+}
+
+fn lint_node(ast: &Micheline, spans: &SpanMap, warnings: &mut Vec<Warning>) {
+    let children = match ast {
+        Micheline::Seq(instrs) => {
+            lint_seq(instrs, spans, warnings);
+            instrs
+        }
+        Micheline::App(_, args, _) => args,
+        Micheline::Int(..) | Micheline::String(..) | Micheline::Bytes(..) => return,
+    };
+    for child in children.iter() {
+        lint_node(child, spans, warnings);
+    }
+}
+
+fn lint_seq(instrs: &[Micheline], spans: &SpanMap, warnings: &mut Vec<Warning>) {
+    use Micheline::App;
+    for pair in instrs.windows(2) {
+        let kind = match pair {
+            [App(Prim::DUP, ..), drop @ App(Prim::DROP, ..)] if is_drop_one(drop) => {
+                WarningKind::DupDrop
+            }
+            [App(Prim::SWAP, [], _), App(Prim::SWAP, [], _)] => WarningKind::SwapSwap,
+            _ => continue,
+        };
+        let span = spans.get(&pair[0]).map(|first| Span {
+            start: first.start,
+            end: spans.get(&pair[1]).map_or(first.end, |second| second.end),
+        });
+        warnings.push(Warning { kind, span });
+    }
+}
+
+/// Check that the instruction has no argument or the argument is `1`, i.e.
+/// `DROP`/`DROP 1`.
+fn is_drop_one(instr: &Micheline) -> bool {
+    match instr {
+        Micheline::App(_, [], _) => true,
+        Micheline::App(_, [Micheline::Int(n)], _) => *n == 1.into(),
+        _ => false,
+    }
+}
@@ -0,0 +1,59 @@
+/******************************************************************************/
+/*                                                                            */
+/* SPDX-License-Identifier: MIT                                               */
+/* Copyright (c) [2023] Serokell <hi@serokell.io>                             */
+/*                                                                            */
+/******************************************************************************/
+
+//! Restricting the set of instructions a program may use, see
+//! [InstructionPolicy].
+
+use std::collections::HashSet;
+
+use super::TcError;
+use crate::ast::Micheline;
+use crate::lexer::Prim;
+
+/// A set of instructions forbidden in a program, used with
+/// [Micheline::typecheck_with_policy]. Useful for sandboxed environments, e.g.
+/// to forbid instructions with side effects, like `EMIT` or
+/// `TRANSFER_TOKENS`.
+///
+/// The default policy allows everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstructionPolicy {
+    denied: HashSet<Prim>,
+}
+
+impl InstructionPolicy {
+    /// Construct a policy allowing all instructions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forbid the given instruction.
+    pub fn deny(mut self, prim: Prim) -> Self {
+        self.denied.insert(prim);
+        self
+    }
+
+    /// Check whether the instruction is forbidden by the policy.
+    pub fn is_denied(&self, prim: Prim) -> bool {
+        self.denied.contains(&prim)
+    }
+
+    /// Check that the program doesn't use any forbidden instruction, including
+    /// in nested code blocks and lambda literals.
+    pub(super) fn check(&self, ast: &Micheline) -> Result<(), TcError> {
+        match ast {
+            Micheline::App(prim, args, _) => {
+                if self.is_denied(*prim) {
+                    return Err(TcError::InstructionForbidden(*prim));
+                }
+                args.iter().try_for_each(|arg| self.check(arg))
+            }
+            Micheline::Seq(args) => args.iter().try_for_each(|arg| self.check(arg)),
+            Micheline::Int(..) | Micheline::String(..) | Micheline::Bytes(..) => Ok(()),
+        }
+    }
+}
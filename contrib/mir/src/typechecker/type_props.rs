@@ -9,11 +9,12 @@
 
 use super::TcError;
 use crate::ast::Type;
-use crate::gas::{tc_cost, Gas};
+use crate::gas::Gas;
 
 /// Type properties, as described in
 /// <https://tezos.gitlab.io/michelson-reference/#types>
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[allow(missing_docs)]
 pub enum TypeProperty {
     Comparable,
@@ -49,7 +50,7 @@ impl Type {
     /// run out of gas, in which case it will return [TcError::OutOfGas].
     pub fn ensure_prop(&self, gas: &mut Gas, prop: TypeProperty) -> Result<(), TcError> {
         use Type::*;
-        gas.consume(tc_cost::TYPE_PROP_STEP)?;
+        gas.consume(gas.costs().tc.type_prop_step)?;
         let invalid_type_prop = || Err(TcError::InvalidTypeProperty(prop, self.clone()));
         match self {
             Nat | Int | Bool | Mutez | String | Unit | Never | Address | ChainId | Bytes | Key
@@ -106,7 +106,7 @@ fn populate_ctx_with_known_contracts(
 ) {
     // If other_contracts is not provided, then initialize with empty map,
     // or else initialize with the provided list of known contracts.
-    let mut known_contracts = m_other_contracts.unwrap_or(HashMap::new());
+    let mut known_contracts = m_other_contracts.unwrap_or_default();
 
     // If self address is provided, include that to the list of known contracts as well.
     // Use a default type of Unit, if parameter type is not provided.
@@ -143,9 +143,13 @@ fn typecheck_stack<'a>(
 
 impl<'a> Parser<'a> {
     /// Parse top-level definition of a TZT test.
+    ///
+    /// Unlike [Parser::parse], `DUP 0` is accepted, and left to the
+    /// typechecker to reject: TZT tests expect it to fail with a static
+    /// error.
     pub fn parse_tzt_test(&'a self, src: &'a str) -> Result<TztTest, Box<dyn Error + '_>> {
         tztTestEntitiesParser::new()
-            .parse(&self.arena, spanned_lexer(src))?
+            .parse(&self.arena, &self.spans, false, spanned_lexer(src))?
             .try_into()
     }
 }
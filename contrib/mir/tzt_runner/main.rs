@@ -104,6 +104,13 @@ mod tztrunner_tests {
         assert!(matches!(run_tzt_test(tzt_test), Ok(())));
     }
 
+    #[test]
+    fn test_runner_dup0() {
+        // rejected by the parser outside of TZT tests
+        let tzt_test = parse_tzt_test(TZT_SAMPLE_DUP0).unwrap();
+        assert_eq!(run_tzt_test(tzt_test), Ok(()));
+    }
+
     #[should_panic(expected = "Duplicate field 'input' in test")]
     #[test]
     fn test_duplicate_field() {
@@ -272,6 +279,10 @@ mod tztrunner_tests {
         input { Stack_elt mutez 5 ; Stack_elt int 5 } ;
         output(StaticError _)";
 
+    const TZT_SAMPLE_DUP0: &str = "code { DUP 0 } ;
+        input { Stack_elt nat 1 } ;
+        output (StaticError _)";
+
     const TZT_SAMPLE_TC_FAIL_SPECIFIC: &str = r#"code { ADD } ;
         input { Stack_elt mutez 5 ; Stack_elt int 5 } ;
         output(StaticError "no matching overload for ADD on stack Stack([Int, Mutez])")"#;
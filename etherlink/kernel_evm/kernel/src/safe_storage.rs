@@ -233,6 +233,14 @@ impl<Host: Runtime, InternalHost> Runtime for SafeStorage<&mut Host, &mut Intern
         self.host.store_value_size(&path)
     }
 
+    fn store_value_size_unchecked(
+        &self,
+        path: &impl Path,
+    ) -> Result<usize, RuntimeError> {
+        let path = safe_path(path)?;
+        self.host.store_value_size_unchecked(&path)
+    }
+
     fn mark_for_reboot(&mut self) -> Result<(), RuntimeError> {
         self.host.mark_for_reboot()
     }
@@ -28,6 +28,9 @@ pub const MAX_FILE_CHUNK_SIZE: usize = 2048;
 /// The size of a preimage *Reveal_hash* hash in bytes.
 pub const PREIMAGE_HASH_SIZE: usize = 33;
 
+/// The maximum size of a preimage revealed by `reveal_preimage`, in bytes.
+pub const MAX_PREIMAGE_SIZE: usize = 4096;
+
 /// The store key submitted as an argument of a host function exceeds the
 /// authorized limit.
 pub const STORE_KEY_TOO_LARGE: i32 = -1;
@@ -357,6 +357,53 @@ pub fn reveal_loop<Host: Runtime>(
     Ok(())
 }
 
+/// Reveals the full payload of the Merkle tree of DAC pages with root `root_hash`.
+///
+/// Contents pages are concatenated in order. Fails with [RuntimeError::DecodingError]
+/// if a page cannot be decoded, or if the tree is more than `max_dac_levels` deep -
+/// which also guards against cycles of hashes pages.
+///
+/// The payload is held in memory: for payloads of more than a few MB, consider using
+/// `DacCertificate::reveal_to_store` instead.
+#[cfg(feature = "alloc")]
+pub fn reveal_dac_root<Host: Runtime>(
+    host: &Host,
+    root_hash: &[u8; PREIMAGE_HASH_SIZE],
+    max_dac_levels: usize,
+) -> Result<Vec<u8>, RuntimeError> {
+    fn reveal_level<Host: Runtime>(
+        host: &Host,
+        level: usize,
+        hash: &[u8; PREIMAGE_HASH_SIZE],
+        max_dac_levels: usize,
+        payload: &mut Vec<u8>,
+    ) -> Result<(), RuntimeError> {
+        if level >= max_dac_levels {
+            return Err(RuntimeError::DecodingError);
+        }
+
+        let mut buffer = [0; MAX_PAGE_SIZE];
+        let (page, _) = fetch_page_raw(host, hash, &mut buffer)?;
+
+        match SlicePage::try_from(page).map_err(|_| RuntimeError::DecodingError)? {
+            SlicePage::V0HashPage(hashes) => {
+                for hash in hashes.hashes() {
+                    reveal_level(host, level + 1, hash, max_dac_levels, payload)?;
+                }
+            }
+            SlicePage::V0ContentPage(content) => {
+                payload.extend_from_slice(content.as_ref())
+            }
+        }
+
+        Ok(())
+    }
+
+    let mut payload = Vec::new();
+    reveal_level(host, 0, root_hash, max_dac_levels, &mut payload)?;
+    Ok(payload)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,190 @@
+// SPDX-FileCopyrightText: 2023 TriliTech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! A log of values in durable storage, which can only be appended to.
+//!
+//! *N.B.* Only available when the `alloc` feature is enabled.
+#![cfg(feature = "alloc")]
+
+use alloc::vec::Vec;
+
+use crate::path::{concat, OwnedPath, Path, PathError, RefPath};
+use crate::runtime::{Runtime, RuntimeError};
+
+const LENGTH_PATH: RefPath = RefPath::assert_from(b"/length");
+
+/// Log of values stored under a prefix.
+///
+/// The `n`th value appended is stored at `<prefix>/<n>`, starting from 0, while the
+/// number of values is stored at `<prefix>/length`, as a little-endian `u64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppendLog {
+    prefix: OwnedPath,
+    length_path: OwnedPath,
+}
+
+impl AppendLog {
+    /// Create a log stored under `prefix`.
+    ///
+    /// Returns [`PathError::PathTooLong`] if the paths of some values wouldn't fit
+    /// in [`PATH_MAX_SIZE`](crate::path::PATH_MAX_SIZE).
+    pub fn new(prefix: &impl Path) -> Result<Self, PathError> {
+        OwnedPath::from_indexed(prefix, u64::MAX)?;
+
+        Ok(Self {
+            prefix: OwnedPath::from(prefix),
+            length_path: concat(prefix, &LENGTH_PATH)?,
+        })
+    }
+
+    /// Number of values in the log - 0 if nothing has been appended yet.
+    pub fn len(&self, host: &impl Runtime) -> Result<u64, RuntimeError> {
+        if host.store_has(&self.length_path)?.is_none() {
+            return Ok(0);
+        }
+
+        let mut bytes = [0; core::mem::size_of::<u64>()];
+        let size = host.store_read_slice(&self.length_path, 0, &mut bytes)?;
+
+        if size == bytes.len() {
+            Ok(u64::from_le_bytes(bytes))
+        } else {
+            Err(RuntimeError::DecodingError)
+        }
+    }
+
+    /// Append a value to the log, returning its index.
+    ///
+    /// The length is only updated once the value is written, so a failed write
+    /// leaves the log unchanged.
+    pub fn push(
+        &mut self,
+        host: &mut impl Runtime,
+        bytes: &[u8],
+    ) -> Result<u64, RuntimeError> {
+        let index = self.len(host)?;
+
+        host.store_write_all(&self.entry_path(index), bytes)?;
+        host.store_write_all(&self.length_path, &(index + 1).to_le_bytes())?;
+
+        Ok(index)
+    }
+
+    /// Read the value at `index`.
+    ///
+    /// Returns [`RuntimeError::StoreListIndexOutOfBounds`] if `index` isn't less than
+    /// the length of the log.
+    pub fn get(&self, host: &impl Runtime, index: u64) -> Result<Vec<u8>, RuntimeError> {
+        if index >= self.len(host)? {
+            return Err(RuntimeError::StoreListIndexOutOfBounds);
+        }
+
+        host.store_read_all(&self.entry_path(index))
+    }
+
+    fn entry_path(&self, index: u64) -> OwnedPath {
+        OwnedPath::from_indexed(&self.prefix, index)
+            .expect("Checked that any index fits when creating the log")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AppendLog;
+    use crate::path::{OwnedPath, PathError, RefPath, PATH_MAX_SIZE, PATH_SEPARATOR};
+    use crate::runtime::RuntimeError;
+    use std::collections::BTreeMap;
+    use std::slice::{from_raw_parts, from_raw_parts_mut};
+    use std::sync::{Arc, Mutex};
+    use tezos_smart_rollup_core::smart_rollup_core::MockSmartRollupCore;
+
+    /// Mock whose durable storage is a map from paths to values.
+    fn mock_store() -> MockSmartRollupCore {
+        let store = Arc::new(Mutex::new(BTreeMap::<Vec<u8>, Vec<u8>>::new()));
+        let mut mock = MockSmartRollupCore::new();
+
+        let has = store.clone();
+        mock.expect_store_has().returning(move |ptr, size| {
+            let path = unsafe { from_raw_parts(ptr, size) };
+            if has.lock().unwrap().contains_key(path) {
+                tezos_smart_rollup_core::VALUE_TYPE_VALUE
+            } else {
+                tezos_smart_rollup_core::VALUE_TYPE_NONE
+            }
+        });
+
+        let value_size = store.clone();
+        mock.expect_store_value_size().returning(move |ptr, size| {
+            let path = unsafe { from_raw_parts(ptr, size) };
+            value_size.lock().unwrap()[path].len() as i32
+        });
+
+        let read = store.clone();
+        mock.expect_store_read()
+            .returning(move |ptr, size, offset, dst, max_bytes| {
+                let path = unsafe { from_raw_parts(ptr, size) };
+                let store = read.lock().unwrap();
+                let value = &store[path][offset..];
+                let len = usize::min(value.len(), max_bytes);
+                let buffer = unsafe { from_raw_parts_mut(dst, len) };
+                buffer.copy_from_slice(&value[..len]);
+                len as i32
+            });
+
+        let delete = store.clone();
+        mock.expect_store_delete_value()
+            .returning(move |ptr, size| {
+                let path = unsafe { from_raw_parts(ptr, size) };
+                delete.lock().unwrap().remove(path);
+                0
+            });
+
+        mock.expect_store_write()
+            .returning(move |ptr, size, offset, src, num_bytes| {
+                let path = unsafe { from_raw_parts(ptr, size) };
+                let bytes = unsafe { from_raw_parts(src, num_bytes) };
+                let mut store = store.lock().unwrap();
+                let value = store.entry(path.to_vec()).or_default();
+                let len = usize::max(value.len(), offset + num_bytes);
+                value.resize(len, 0);
+                value[offset..offset + num_bytes].copy_from_slice(bytes);
+                0
+            });
+
+        mock
+    }
+
+    #[test]
+    fn push_and_get() {
+        // Arrange
+        let mut mock = mock_store();
+        let mut log = AppendLog::new(&RefPath::assert_from(b"/log")).unwrap();
+
+        // Act
+        let empty_len = log.len(&mock);
+        let first = log.push(&mut mock, b"first");
+        let second = log.push(&mut mock, b"second");
+
+        // Assert
+        assert_eq!(Ok(0), empty_len);
+        assert_eq!(Ok(0), first);
+        assert_eq!(Ok(1), second);
+        assert_eq!(Ok(2), log.len(&mock));
+        assert_eq!(Ok(b"first".to_vec()), log.get(&mock, 0));
+        assert_eq!(Ok(b"second".to_vec()), log.get(&mock, 1));
+        assert_eq!(
+            Err(RuntimeError::StoreListIndexOutOfBounds),
+            log.get(&mock, 2)
+        );
+    }
+
+    #[test]
+    fn prefix_too_long() {
+        let mut bytes = vec![PATH_SEPARATOR];
+        bytes.extend_from_slice(&[b'i'; PATH_MAX_SIZE - 20]);
+        let prefix = OwnedPath::try_from(bytes).unwrap();
+
+        assert_eq!(Err(PathError::PathTooLong), AppendLog::new(&prefix));
+    }
+}
@@ -0,0 +1,64 @@
+// SPDX-FileCopyrightText: 2023 TriliTech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Record the debug output of a kernel, for testing.
+//!
+//! *N.B.* Only available when the `testing` feature is enabled.
+#![cfg(feature = "testing")]
+
+use std::cell::RefCell;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::observed::{HostObserver, Observed};
+
+/// Wraps a host, recording every message written with [`Runtime::write_debug`]
+/// before passing it on.
+///
+/// All other host functions are forwarded to the inner host unchanged.
+///
+/// [`Runtime::write_debug`]: crate::runtime::Runtime::write_debug
+pub type DebugCapture<Host> = Observed<Host, DebugLog>;
+
+/// Messages recorded by a [`DebugCapture`].
+#[derive(Debug, Default)]
+pub struct DebugLog(RefCell<Vec<String>>);
+
+impl HostObserver for DebugLog {
+    fn debug_message(&self, msg: &[u8]) {
+        self.0
+            .borrow_mut()
+            .push(String::from_utf8_lossy(msg).into_owned());
+    }
+}
+
+impl<Host> DebugCapture<Host> {
+    /// The messages written so far, in order.
+    pub fn captured(&self) -> Vec<String> {
+        self.observer().0.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DebugCapture;
+    use crate::runtime::Runtime;
+    use tezos_smart_rollup_core::smart_rollup_core::MockSmartRollupCore;
+
+    #[test]
+    fn write_debug_captured_in_order() {
+        // Arrange
+        let mut mock = MockSmartRollupCore::new();
+        mock.expect_write_debug().times(2).return_const(());
+
+        let host = DebugCapture::new(mock);
+
+        // Act
+        host.write_debug("first\n");
+        host.write_debug("second\n");
+
+        // Assert
+        assert_eq!(vec!["first\n", "second\n"], host.captured());
+    }
+}
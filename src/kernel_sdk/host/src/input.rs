@@ -10,6 +10,8 @@
 
 use alloc::vec::Vec;
 
+use crate::runtime::{Runtime, RuntimeError};
+
 /// An input from Layer 1 contains the inbox level, message number, and message payload.
 #[derive(Debug, PartialEq, Eq)]
 pub struct Message {
@@ -32,3 +34,113 @@ impl AsRef<[u8]> for Message {
         self.payload.as_ref()
     }
 }
+
+/// Iterator over the remaining messages of the inbox at the current level, see
+/// [`Runtime::drain_inbox`].
+///
+/// Ends once the host has no more input, or after yielding an error.
+#[derive(Debug)]
+pub struct Inbox<'a, Host: ?Sized> {
+    host: &'a mut Host,
+    done: bool,
+}
+
+impl<'a, Host: Runtime + ?Sized> Inbox<'a, Host> {
+    /// Iterate over the messages read from `host`.
+    pub fn new(host: &'a mut Host) -> Self {
+        Self { host, done: false }
+    }
+}
+
+impl<Host: Runtime + ?Sized> Iterator for Inbox<'_, Host> {
+    type Item = Result<Message, RuntimeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.host.read_input().transpose();
+        self.done = !matches!(result, Some(Ok(_)));
+        result
+    }
+}
+
+impl<Host: Runtime + ?Sized> core::iter::FusedIterator for Inbox<'_, Host> {}
+
+#[cfg(test)]
+mod tests {
+    use super::Message;
+    use crate::runtime::{Runtime, RuntimeError};
+    use crate::Error;
+    use std::slice::from_raw_parts_mut;
+    use tezos_smart_rollup_core::smart_rollup_core::{
+        MockSmartRollupCore, ReadInputMessageInfo,
+    };
+
+    fn mock_inbox(mut results: Vec<Result<&'static [u8], Error>>) -> MockSmartRollupCore {
+        let mut mock = MockSmartRollupCore::new();
+        results.reverse();
+        let mut id = 0;
+
+        mock.expect_read_input()
+            .returning(move |message_info, buffer, _| match results.pop() {
+                None => 0,
+                Some(Err(e)) => e.code(),
+                Some(Ok(payload)) => {
+                    unsafe {
+                        std::ptr::write(
+                            message_info,
+                            ReadInputMessageInfo { level: 5, id },
+                        );
+                        from_raw_parts_mut(buffer, payload.len())
+                            .copy_from_slice(payload);
+                    }
+                    id += 1;
+                    payload.len().try_into().unwrap()
+                }
+            });
+
+        mock
+    }
+
+    #[test]
+    fn drain_inbox_until_no_input() {
+        // Arrange
+        let mut mock = mock_inbox(vec![Ok(b"one"), Ok(b"two"), Ok(b"three")]);
+
+        // Act
+        let messages: Vec<_> = mock.drain_inbox().collect();
+
+        // Assert
+        assert_eq!(
+            vec![
+                Ok(Message::new(5, 0, b"one".to_vec())),
+                Ok(Message::new(5, 1, b"two".to_vec())),
+                Ok(Message::new(5, 2, b"three".to_vec())),
+            ],
+            messages
+        );
+    }
+
+    #[test]
+    fn drain_inbox_stops_after_error() {
+        // Arrange
+        let mut mock = mock_inbox(vec![
+            Ok(b"one"),
+            Err(Error::MemoryInvalidAccess),
+            Ok(b"two"),
+        ]);
+
+        // Act
+        let messages: Vec<_> = mock.drain_inbox().collect();
+
+        // Assert
+        assert_eq!(
+            vec![
+                Ok(Message::new(5, 0, b"one".to_vec())),
+                Err(RuntimeError::HostErr(Error::MemoryInvalidAccess)),
+            ],
+            messages
+        );
+    }
+}
@@ -13,9 +13,14 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+pub mod append_log;
 pub mod dal_parameters;
+pub mod debug_capture;
 pub mod input;
 pub mod metadata;
+pub mod metered;
+pub mod observed;
+pub mod output;
 pub mod path;
 pub mod runtime;
 
@@ -33,6 +33,12 @@ impl RollupMetadata {
     pub fn address(&self) -> SmartRollupHash {
         SmartRollupHash(self.raw_rollup_address.to_vec())
     }
+
+    /// The address of the smart rollup, in its `sr1..` base58check form.
+    #[cfg(all(feature = "crypto", feature = "alloc"))]
+    pub fn rollup_address_string(&self) -> alloc::string::String {
+        self.address().to_base58_check()
+    }
 }
 
 impl From<[u8; METADATA_SIZE]> for RollupMetadata {
@@ -59,3 +65,26 @@ impl From<RollupMetadata> for [u8; METADATA_SIZE] {
         data
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{RollupMetadata, METADATA_SIZE};
+
+    #[test]
+    fn metadata_from_bytes() {
+        let metadata_bytes: [u8; METADATA_SIZE] = [
+            // sr1 as 20 bytes
+            b'M', 165, 28, b']', 231, 161, 205, 212, 148, 193, b'[', b'S', 129, b'^', 31,
+            170, b'L', 26, 150, 202, // origination level as 4 bytes
+            0, 0, 0, 42,
+        ];
+
+        let metadata = RollupMetadata::from(metadata_bytes);
+
+        assert_eq!(42, metadata.origination_level);
+        assert_eq!(
+            "sr1D7tjtYDmnugNWh8sGW91WJ3TPpCpqX1Dg",
+            metadata.rollup_address_string()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+// SPDX-FileCopyrightText: 2023 TriliTech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Count the host calls made by a kernel, for testing.
+//!
+//! *N.B.* Only available when the `testing` feature is enabled.
+#![cfg(feature = "testing")]
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use crate::observed::{HostObserver, Observed};
+
+/// Host calls recorded by a [`MeteredRuntime`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HostCallStats {
+    /// Number of calls to each host function, by name.
+    pub calls: BTreeMap<&'static str, usize>,
+    /// Total number of bytes read from durable storage by `store_read`.
+    pub bytes_read: usize,
+    /// Total number of bytes written to durable storage by `store_write`.
+    pub bytes_written: usize,
+}
+
+impl HostCallStats {
+    /// Number of calls to the host function `name`.
+    pub fn calls_to(&self, name: &str) -> usize {
+        self.calls.get(name).copied().unwrap_or_default()
+    }
+}
+
+/// Wraps a host, counting the calls made to each host function, and the bytes
+/// read from and written to durable storage.
+///
+/// All host functions are forwarded to the inner host unchanged.
+pub type MeteredRuntime<Host> = Observed<Host, CallMeter>;
+
+/// Host calls recorded by a [`MeteredRuntime`], as they are made.
+#[derive(Debug, Default)]
+pub struct CallMeter(RefCell<HostCallStats>);
+
+impl HostObserver for CallMeter {
+    fn host_call(&self, name: &'static str) {
+        *self.0.borrow_mut().calls.entry(name).or_default() += 1;
+    }
+
+    fn bytes_read(&self, size: usize) {
+        self.0.borrow_mut().bytes_read += size;
+    }
+
+    fn bytes_written(&self, size: usize) {
+        self.0.borrow_mut().bytes_written += size;
+    }
+}
+
+impl<Host> MeteredRuntime<Host> {
+    /// The host calls made so far.
+    pub fn stats(&self) -> HostCallStats {
+        self.observer().0.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MeteredRuntime;
+    use crate::path::RefPath;
+    use crate::runtime::Runtime;
+    use std::slice::from_raw_parts_mut;
+    use tezos_smart_rollup_core::smart_rollup_core::MockSmartRollupCore;
+
+    #[test]
+    fn host_calls_counted() {
+        // Arrange
+        const PATH: RefPath<'static> = RefPath::assert_from(b"/a/value");
+        let mut mock = MockSmartRollupCore::new();
+        mock.expect_store_delete_value().return_const(0);
+        mock.expect_store_write().return_const(0);
+        mock.expect_store_read()
+            .returning(|_, _, _, buf_ptr, max_bytes| {
+                let buffer = unsafe { from_raw_parts_mut(buf_ptr, max_bytes) };
+                buffer.fill(b'a');
+                max_bytes as i32
+            });
+
+        let mut host = MeteredRuntime::new(mock);
+
+        // Act
+        host.store_write_all(&PATH, &[b'a'; 10]).unwrap();
+        host.store_write(&PATH, &[b'b'; 5], 10).unwrap();
+        host.store_read_slice(&PATH, 0, &mut [0; 8]).unwrap();
+
+        // Assert
+        let stats = host.stats();
+        assert_eq!(1, stats.calls_to("store_delete_value"));
+        assert_eq!(2, stats.calls_to("store_write"));
+        assert_eq!(1, stats.calls_to("store_read"));
+        assert_eq!(0, stats.calls_to("store_has"));
+        assert_eq!(15, stats.bytes_written);
+        assert_eq!(8, stats.bytes_read);
+    }
+}
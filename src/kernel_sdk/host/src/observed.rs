@@ -0,0 +1,203 @@
+// SPDX-FileCopyrightText: 2023 TriliTech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Observe the host calls made by a kernel, for testing.
+//!
+//! *N.B.* Only available when the `testing` feature is enabled.
+#![cfg(feature = "testing")]
+
+use tezos_smart_rollup_core::smart_rollup_core::ReadInputMessageInfo;
+use tezos_smart_rollup_core::SmartRollupCore;
+
+/// Notified of the host calls made through an [`Observed`] host.
+///
+/// All methods do nothing by default.
+pub trait HostObserver {
+    /// Called before each host function, with its name.
+    fn host_call(&self, _name: &'static str) {}
+
+    /// Called with each message written with `write_debug`.
+    fn debug_message(&self, _msg: &[u8]) {}
+
+    /// Called with the number of bytes read by each successful `store_read`.
+    fn bytes_read(&self, _size: usize) {}
+
+    /// Called with the number of bytes written by each successful `store_write`.
+    fn bytes_written(&self, _size: usize) {}
+}
+
+/// Wraps a host, notifying an observer of the calls made to it.
+///
+/// All host functions are forwarded to the inner host unchanged. Like any
+/// [`SmartRollupCore`], `Observed` implements [`Runtime`].
+///
+/// [`Runtime`]: crate::runtime::Runtime
+#[derive(Debug, Default)]
+pub struct Observed<Host, Observer> {
+    inner: Host,
+    observer: Observer,
+}
+
+impl<Host, Observer: Default> Observed<Host, Observer> {
+    /// Wrap `inner`, with nothing observed yet.
+    pub fn new(inner: Host) -> Self {
+        Self {
+            inner,
+            observer: Observer::default(),
+        }
+    }
+}
+
+impl<Host, Observer> Observed<Host, Observer> {
+    /// The observer notified of host calls.
+    pub fn observer(&self) -> &Observer {
+        &self.observer
+    }
+
+    /// Unwrap the inner host.
+    pub fn into_inner(self) -> Host {
+        self.inner
+    }
+}
+
+unsafe impl<Host: SmartRollupCore, Observer: HostObserver> SmartRollupCore
+    for Observed<Host, Observer>
+{
+    unsafe fn read_input(
+        &self,
+        message_info: *mut ReadInputMessageInfo,
+        dst: *mut u8,
+        max_bytes: usize,
+    ) -> i32 {
+        self.observer.host_call("read_input");
+        self.inner.read_input(message_info, dst, max_bytes)
+    }
+
+    unsafe fn write_output(&self, src: *const u8, num_bytes: usize) -> i32 {
+        self.observer.host_call("write_output");
+        self.inner.write_output(src, num_bytes)
+    }
+
+    unsafe fn write_debug(&self, src: *const u8, num_bytes: usize) {
+        self.observer.host_call("write_debug");
+        self.observer
+            .debug_message(core::slice::from_raw_parts(src, num_bytes));
+        self.inner.write_debug(src, num_bytes)
+    }
+
+    unsafe fn store_has(&self, path: *const u8, path_len: usize) -> i32 {
+        self.observer.host_call("store_has");
+        self.inner.store_has(path, path_len)
+    }
+
+    unsafe fn store_read(
+        &self,
+        path: *const u8,
+        path_len: usize,
+        offset: usize,
+        dst: *mut u8,
+        max_bytes: usize,
+    ) -> i32 {
+        self.observer.host_call("store_read");
+        let res = self
+            .inner
+            .store_read(path, path_len, offset, dst, max_bytes);
+        if let Ok(size) = usize::try_from(res) {
+            self.observer.bytes_read(size);
+        }
+        res
+    }
+
+    unsafe fn store_write(
+        &self,
+        path: *const u8,
+        path_len: usize,
+        offset: usize,
+        src: *const u8,
+        num_bytes: usize,
+    ) -> i32 {
+        self.observer.host_call("store_write");
+        let res = self
+            .inner
+            .store_write(path, path_len, offset, src, num_bytes);
+        if res == 0 {
+            self.observer.bytes_written(num_bytes);
+        }
+        res
+    }
+
+    unsafe fn store_delete(&self, path: *const u8, len: usize) -> i32 {
+        self.observer.host_call("store_delete");
+        self.inner.store_delete(path, len)
+    }
+
+    unsafe fn store_delete_value(&self, path: *const u8, len: usize) -> i32 {
+        self.observer.host_call("store_delete_value");
+        self.inner.store_delete_value(path, len)
+    }
+
+    unsafe fn store_list_size(&self, path: *const u8, path_len: usize) -> i64 {
+        self.observer.host_call("store_list_size");
+        self.inner.store_list_size(path, path_len)
+    }
+
+    unsafe fn store_move(
+        &self,
+        from_path: *const u8,
+        from_path_len: usize,
+        to_path: *const u8,
+        to_path_len: usize,
+    ) -> i32 {
+        self.observer.host_call("store_move");
+        self.inner
+            .store_move(from_path, from_path_len, to_path, to_path_len)
+    }
+
+    unsafe fn store_copy(
+        &self,
+        from_path: *const u8,
+        from_path_len: usize,
+        to_path: *const u8,
+        to_path_len: usize,
+    ) -> i32 {
+        self.observer.host_call("store_copy");
+        self.inner
+            .store_copy(from_path, from_path_len, to_path, to_path_len)
+    }
+
+    unsafe fn reveal_preimage(
+        &self,
+        hash_addr: *const u8,
+        hash_len: usize,
+        destination_addr: *mut u8,
+        max_bytes: usize,
+    ) -> i32 {
+        self.observer.host_call("reveal_preimage");
+        self.inner
+            .reveal_preimage(hash_addr, hash_len, destination_addr, max_bytes)
+    }
+
+    #[cfg(feature = "proto-alpha")]
+    unsafe fn reveal(
+        &self,
+        payload_addr: *const u8,
+        payload_len: usize,
+        destination_addr: *mut u8,
+        max_bytes: usize,
+    ) -> i32 {
+        self.observer.host_call("reveal");
+        self.inner
+            .reveal(payload_addr, payload_len, destination_addr, max_bytes)
+    }
+
+    unsafe fn store_value_size(&self, path: *const u8, path_len: usize) -> i32 {
+        self.observer.host_call("store_value_size");
+        self.inner.store_value_size(path, path_len)
+    }
+
+    unsafe fn reveal_metadata(&self, destination_addr: *mut u8, max_bytes: usize) -> i32 {
+        self.observer.host_call("reveal_metadata");
+        self.inner.reveal_metadata(destination_addr, max_bytes)
+    }
+}
@@ -0,0 +1,123 @@
+// SPDX-FileCopyrightText: 2023 TriliTech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Accumulate output in memory, before writing it with [Runtime::write_output].
+//!
+//! *N.B.* Only available when the `alloc` feature is enabled.
+#![cfg(feature = "alloc")]
+
+use alloc::vec::Vec;
+use tezos_smart_rollup_core::MAX_OUTPUT_SIZE;
+
+use crate::runtime::{Runtime, RuntimeError};
+
+/// Builds an output incrementally, avoiding a host call for every piece.
+///
+/// The accumulated bytes are written on [`OutputBuilder::flush`] - in one call to
+/// [`Runtime::write_output`], or in chunks of at most [`MAX_OUTPUT_SIZE`] bytes
+/// when they exceed that limit.
+#[derive(Debug, Default)]
+pub struct OutputBuilder {
+    buffer: Vec<u8>,
+    written: usize,
+}
+
+impl OutputBuilder {
+    /// Create an empty output builder.
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            written: 0,
+        }
+    }
+
+    /// Append bytes to the pending output.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Number of bytes pushed, but not yet flushed.
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Write the pending output, leaving the builder empty.
+    ///
+    /// If writing a chunk fails, the chunks not yet written are kept pending.
+    pub fn flush(&mut self, host: &mut impl Runtime) -> Result<(), RuntimeError> {
+        let mut flushed = 0;
+        let result = self.buffer.chunks(MAX_OUTPUT_SIZE).try_for_each(|chunk| {
+            host.write_output(chunk)?;
+            flushed += chunk.len();
+            Ok(())
+        });
+
+        self.buffer.drain(..flushed);
+        self.written += flushed;
+        result
+    }
+
+    /// Flush the pending output, returning the total number of bytes written by
+    /// this builder.
+    pub fn finish(mut self, host: &mut impl Runtime) -> Result<usize, RuntimeError> {
+        self.flush(host)?;
+        Ok(self.written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OutputBuilder;
+    use std::slice::from_raw_parts;
+    use tezos_smart_rollup_core::{
+        smart_rollup_core::MockSmartRollupCore, MAX_OUTPUT_SIZE,
+    };
+
+    #[test]
+    fn pushed_output_flushed_in_one_write() {
+        // Arrange
+        let mut mock = MockSmartRollupCore::new();
+
+        mock.expect_write_output()
+            .times(1)
+            .withf(|ptr, len| {
+                let slice = unsafe { from_raw_parts(*ptr, *len) };
+
+                slice == b"hello, world"
+            })
+            .return_const(0);
+
+        let mut builder = OutputBuilder::new();
+
+        // Act
+        builder.push(b"hello");
+        builder.push(b", ");
+        builder.push(b"world");
+        let result = builder.finish(&mut mock);
+
+        // Assert
+        assert_eq!(Ok(12), result);
+    }
+
+    #[test]
+    fn large_output_flushed_in_chunks() {
+        // Arrange
+        let mut mock = MockSmartRollupCore::new();
+
+        mock.expect_write_output()
+            .times(2)
+            .withf(|_, len| *len <= MAX_OUTPUT_SIZE)
+            .return_const(0);
+
+        let mut builder = OutputBuilder::new();
+
+        // Act
+        builder.push(&[b'a'; MAX_OUTPUT_SIZE]);
+        builder.push(b"b");
+        let result = builder.finish(&mut mock);
+
+        // Assert
+        assert_eq!(Ok(MAX_OUTPUT_SIZE + 1), result);
+    }
+}
@@ -158,6 +158,23 @@ impl<'a> RefPath<'a> {
         }
     }
 
+    /// Constructs a [`RefPath`] from a byte slice, returning an error if the byte
+    /// slice does not represent a valid path-encoding.
+    ///
+    /// Prefer this to [`assert_from`] for paths built at runtime.
+    ///
+    /// [`assert_from`]: RefPath::assert_from
+    pub const fn try_from_bytes(path: &[u8]) -> Result<RefPath, PathError> {
+        match validate_path(path) {
+            // SAFETY: we've validated that every byte is either alphanumeric or
+            // SEPARATOR and it is not pointing to a read-only store
+            Ok(()) => Ok(RefPath {
+                inner: unsafe { core::str::from_utf8_unchecked(path) },
+            }),
+            Err(e) => Err(e),
+        }
+    }
+
     /// similar to [`assert_from`] but does not verify that the path
     /// is writable, i.e. not prefixed with `/readonly`. This function
     /// is to be used only internally to create [`RefPath`] for the
@@ -241,15 +258,28 @@ const fn validate_path_internal(path: &[u8]) -> Result<(), PathError> {
     }
 }
 
+/// check whether `path` is `prefix`, or a path under it.
+pub(crate) fn is_path_prefix(prefix: &[u8], path: &[u8]) -> bool {
+    match path.strip_prefix(prefix) {
+        Some(rest) => matches!(rest, [] | [PATH_SEPARATOR, ..]),
+        None => false,
+    }
+}
+
+/// check whether the given path is `/readonly`, or a path under it.
+pub(crate) const fn is_readonly(path: &[u8]) -> bool {
+    match path {
+        [PATH_SEPARATOR, b'r', b'e', b'a', b'd', b'o', b'n', b'l', b'y', rest @ ..] => {
+            matches!(rest, [] | [PATH_SEPARATOR, ..])
+        }
+        _ => false,
+    }
+}
+
 const fn validate_path(path: &[u8]) -> Result<(), PathError> {
     match validate_path_internal(path) {
-        Ok(()) => match path {
-            [PATH_SEPARATOR, b'r', b'e', b'a', b'd', b'o', b'n', b'l', b'y']
-            | [PATH_SEPARATOR, b'r', b'e', b'a', b'd', b'o', b'n', b'l', b'y', PATH_SEPARATOR, ..] => {
-                Err(PathError::ReadOnly)
-            }
-            _ => Ok(()),
-        },
+        Ok(()) if is_readonly(path) => Err(PathError::ReadOnly),
+        Ok(()) => Ok(()),
         Err(e) => Err(e),
     }
 }
@@ -266,24 +296,20 @@ impl<'a> TryFrom<&'a [u8]> for RefPath<'a> {
     type Error = PathError;
 
     fn try_from(slice: &'a [u8]) -> Result<RefPath, PathError> {
-        validate_path(slice)?;
-
-        // SAFETY: we've validated that every byte is either alphanumeric or SEPARATOR
-        // and it is not pointing to a read-only store
-        let inner = unsafe { core::str::from_utf8_unchecked(slice) };
-
-        Ok(RefPath { inner })
+        RefPath::try_from_bytes(slice)
     }
 }
 
 #[cfg(feature = "alloc")]
 pub use owned::*;
+
 #[cfg(feature = "alloc")]
 mod owned {
-    use super::{validate_path, Path, PathError, RefPath};
+    use super::{is_allowed_step_byte, validate_path, Path, PathError, RefPath};
     use crate::path::PATH_MAX_SIZE;
     use alloc::string::{String, ToString};
     use alloc::vec::Vec;
+    use core::fmt::{Display, Write};
     use tezos_data_encoding::enc::{put_bytes, BinResult, BinWriter};
 
     /// Representation of a [`Path`] which *owns* its underlying path-encoded byte sequence.
@@ -306,6 +332,61 @@ mod owned {
                 inner: String::from_utf8_unchecked(bytes),
             }
         }
+
+        /// Constructs an [`OwnedPath`] from a copy of a byte slice, returning an
+        /// error if the byte slice does not represent a valid path-encoding.
+        pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, PathError> {
+            RefPath::try_from_bytes(bytes).map(OwnedPath::from)
+        }
+
+        /// Constructs an [`OwnedPath`] from its steps, as rendered by [`Display`].
+        ///
+        /// Fails if a step is empty or contains a byte not allowed in steps -
+        /// including the path separator - or if the path is too long.
+        ///
+        /// See also the [`path!`](crate::path!) macro.
+        pub fn from_segments(segments: &[&dyn Display]) -> Result<Self, PathError> {
+            let mut inner = String::new();
+
+            for segment in segments {
+                let start = inner.len() + 1;
+                write!(inner, "/{segment}").expect("Writing to a String cannot fail");
+
+                let step = &inner.as_bytes()[start..];
+                if step.is_empty() {
+                    return Err(PathError::InvalidEmptyStep);
+                }
+                if !step.iter().all(|byte| is_allowed_step_byte(*byte)) {
+                    return Err(PathError::InvalidByteInStep);
+                }
+                if inner.len() > PATH_MAX_SIZE {
+                    return Err(PathError::PathTooLong);
+                }
+            }
+
+            Self::try_from(inner)
+        }
+
+        /// Creates a new path, by appending the step `/<index>` to `prefix`.
+        ///
+        /// Returns error in case the resulting path is too long.
+        pub fn from_indexed(prefix: &impl Path, index: u64) -> Result<Self, PathError> {
+            let mut inner = OwnedPath::from(prefix).inner;
+            write!(inner, "/{index}").expect("Writing to a String cannot fail");
+
+            if inner.len() <= PATH_MAX_SIZE {
+                Ok(OwnedPath { inner })
+            } else {
+                Err(PathError::PathTooLong)
+            }
+        }
+
+        /// Creates a new path, by appending `segment` to this path.
+        ///
+        /// Returns error in case the resulting path is too long.
+        pub fn join(&self, segment: &impl Path) -> Result<OwnedPath, PathError> {
+            concat(self, segment)
+        }
     }
 
     unsafe impl Path for OwnedPath {
@@ -401,6 +482,31 @@ mod owned {
     }
 }
 
+/// Constructs an [`OwnedPath`] from its steps, which may be any value implementing
+/// [`Display`](core::fmt::Display).
+///
+/// Returns a [`PathError`] if a step isn't valid, or the path is too long - see
+/// [`OwnedPath::from_segments`].
+///
+/// ```
+/// use tezos_smart_rollup_host::path;
+/// use tezos_smart_rollup_host::path::Path;
+///
+/// let index = 42;
+/// let path = path!["accounts", "alice", index].unwrap();
+///
+/// assert_eq!(b"/accounts/alice/42", path.as_bytes());
+/// ```
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! path {
+    ($($segment: expr),+ $(,)?) => {
+        $crate::path::OwnedPath::from_segments(
+            &[$(&$segment as &dyn ::core::fmt::Display),+]
+        )
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -461,6 +567,42 @@ mod tests {
         assert_eq!(Err(PathError::PathTooLong), result);
     }
 
+    #[test]
+    fn try_from_bytes() {
+        let valid = b"/valid/path";
+        let mut overlong = vec![PATH_SEPARATOR];
+        overlong.extend_from_slice(&[b'i'; PATH_MAX_SIZE]);
+
+        assert_eq!(
+            Ok(RefPath {
+                inner: "/valid/path"
+            }),
+            RefPath::try_from_bytes(valid)
+        );
+        assert_eq!(
+            Ok(b"/valid/path".as_slice()),
+            OwnedPath::try_from_bytes(valid)
+                .as_ref()
+                .map(Path::as_bytes)
+        );
+        assert_eq!(
+            Err(PathError::InvalidStart),
+            RefPath::try_from_bytes(b"missing/slash")
+        );
+        assert_eq!(
+            Err(PathError::InvalidStart),
+            OwnedPath::try_from_bytes(b"missing/slash")
+        );
+        assert_eq!(
+            Err(PathError::PathTooLong),
+            RefPath::try_from_bytes(&overlong)
+        );
+        assert_eq!(
+            Err(PathError::PathTooLong),
+            OwnedPath::try_from_bytes(&overlong)
+        );
+    }
+
     #[test]
     fn store_path_readonly() {
         let path = "/readonly/this/path/is/read/only";
@@ -504,6 +646,78 @@ mod tests {
         assert_eq!(b"/a/b/c/d", p3.as_bytes());
     }
 
+    #[test]
+    fn test_join() {
+        let p1 = OwnedPath::from(RefPath::assert_from(b"/a"));
+        let p2 = RefPath::assert_from(b"/b");
+        let p3 = RefPath::assert_from(b"/c/d");
+
+        let joined = p1.join(&p2).and_then(|path| path.join(&p3)).unwrap();
+
+        assert_eq!(b"/a/b/c/d", joined.as_bytes());
+    }
+
+    #[test]
+    fn test_join_too_long() {
+        let mut bytes = vec![PATH_SEPARATOR];
+        bytes.extend_from_slice(&[b'i'; PATH_MAX_SIZE - 3]);
+        let p1 = OwnedPath::try_from(bytes).unwrap();
+        let p2 = RefPath::assert_from(b"/ab");
+
+        let result = p1.join(&p2);
+
+        assert_eq!(Err(PathError::PathTooLong), result);
+    }
+
+    #[test]
+    fn test_from_indexed() {
+        let prefix = RefPath::assert_from(b"/accounts");
+
+        let first = OwnedPath::from_indexed(&prefix, 0).unwrap();
+        let last = OwnedPath::from_indexed(&prefix, u64::MAX).unwrap();
+
+        assert_eq!(b"/accounts/0", first.as_bytes());
+        assert_eq!(b"/accounts/18446744073709551615", last.as_bytes());
+    }
+
+    #[test]
+    fn test_from_indexed_too_long() {
+        let mut bytes = vec![PATH_SEPARATOR];
+        bytes.extend_from_slice(&[b'i'; PATH_MAX_SIZE - 3]);
+        let prefix = OwnedPath::try_from(bytes).unwrap();
+
+        assert!(OwnedPath::from_indexed(&prefix, 1).is_ok());
+        assert_eq!(
+            Err(PathError::PathTooLong),
+            OwnedPath::from_indexed(&prefix, 10)
+        );
+    }
+
+    #[test]
+    fn test_path_macro() {
+        let index = 42_u64;
+
+        let path = crate::path!["accounts", "tz1", index].unwrap();
+
+        assert_eq!(b"/accounts/tz1/42", path.as_bytes());
+    }
+
+    #[test]
+    fn test_path_macro_invalid_step() {
+        assert_eq!(Err(PathError::InvalidByteInStep), crate::path!["a/b"]);
+        assert_eq!(Err(PathError::InvalidEmptyStep), crate::path!["a", ""]);
+        assert_eq!(Err(PathError::ReadOnly), crate::path!["readonly", "a"]);
+    }
+
+    #[test]
+    fn test_path_macro_too_long() {
+        let step = "i".repeat(PATH_MAX_SIZE / 2);
+
+        let result = crate::path![step, step];
+
+        assert_eq!(Err(PathError::PathTooLong), result);
+    }
+
     #[test]
     fn test_ownedpath_display_roundtrip() {
         let p1 = OwnedPath::try_from("/hello".to_string()).unwrap();
@@ -15,8 +15,9 @@ use tezos_smart_rollup_core::{SmartRollupCore, PREIMAGE_HASH_SIZE};
 #[cfg(feature = "proto-alpha")]
 use crate::dal_parameters::RollupDalParameters;
 #[cfg(feature = "alloc")]
-use crate::input::Message;
+use crate::input::{Inbox, Message};
 use crate::metadata::RollupMetadata;
+use crate::path::{is_path_prefix, is_readonly};
 #[cfg(feature = "alloc")]
 use crate::path::{Path, RefPath};
 #[cfg(not(feature = "alloc"))]
@@ -41,6 +42,27 @@ pub enum RuntimeError {
     HostErr(Error),
     /// Failed parsing
     DecodingError,
+    /// Failed encoding
+    EncodingError,
+    /// Attempted to modify a value under `/readonly`.
+    ReadonlyPath,
+    /// Attempted to read from an offset past the end of a value.
+    StoreOffsetOutOfBounds,
+    /// Attempted to swap two paths, one of which is a prefix of the other.
+    OverlappingPaths,
+    /// The temporary path of [`Runtime::store_swap`] is already in use.
+    TemporaryPathInUse,
+    /// Failed decoding data read from the host.
+    Decode(DecodeError),
+}
+
+#[derive(Copy, Eq, PartialEq, Clone, Debug)]
+/// Reason a [`RuntimeError::Decode`] failed.
+pub enum DecodeError {
+    /// Bytes expected to be a string aren't valid UTF-8.
+    InvalidUtf8,
+    /// Bytes don't encode a value of the expected type.
+    InvalidValue,
 }
 
 // TODO: use `core:error::Error` once `error_in_core` stabilised.
@@ -58,9 +80,17 @@ impl core::fmt::Display for RuntimeError {
             Self::PathNotFound => write!(f, "RuntimeError::PathNotFound"),
             Self::HostErr(e) => e.fmt(f),
             Self::DecodingError => write!(f, "RuntimeError::DecodingError"),
+            Self::EncodingError => write!(f, "RuntimeError::EncodingError"),
+            Self::ReadonlyPath => write!(f, "RuntimeError::ReadonlyPath"),
+            Self::StoreOffsetOutOfBounds => {
+                write!(f, "RuntimeError::StoreOffsetOutOfBounds")
+            }
             Self::StoreListIndexOutOfBounds => {
                 write!(f, "RuntimeError::StoreListIndexOutOfBounds")
             }
+            Self::OverlappingPaths => write!(f, "RuntimeError::OverlappingPaths"),
+            Self::TemporaryPathInUse => write!(f, "RuntimeError::TemporaryPathInUse"),
+            Self::Decode(e) => write!(f, "RuntimeError::Decode({e:?})"),
         }
     }
 }
@@ -86,6 +116,29 @@ pub trait Runtime {
     /// Write contents of the given slice to output.
     fn write_output(&mut self, from: &[u8]) -> Result<(), RuntimeError>;
 
+    /// Encode `value` and write it to output.
+    ///
+    /// Values whose encoding exceeds [`MAX_OUTPUT_SIZE`] are rejected without
+    /// calling the host.
+    ///
+    /// [`MAX_OUTPUT_SIZE`]: tezos_smart_rollup_core::MAX_OUTPUT_SIZE
+    #[cfg(feature = "serde")]
+    fn write_output_typed<T: serde::Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), RuntimeError> {
+        use tezos_smart_rollup_core::MAX_OUTPUT_SIZE;
+
+        let output =
+            postcard::to_allocvec(value).map_err(|_| RuntimeError::EncodingError)?;
+
+        if output.len() > MAX_OUTPUT_SIZE {
+            return Err(RuntimeError::HostErr(Error::InputOutputTooLarge));
+        }
+
+        self.write_output(&output)
+    }
+
     /// Write message to debug log.
     fn write_debug(&self, msg: &str);
 
@@ -98,9 +151,33 @@ pub trait Runtime {
     #[cfg(feature = "alloc")]
     fn read_input(&mut self) -> Result<Option<Message>, RuntimeError>;
 
+    /// Iterate over the remaining inputs of the global inbox at the current
+    /// level, by repeatedly calling [`Runtime::read_input`].
+    ///
+    /// The iterator ends once no message is available, or after yielding an
+    /// error.
+    #[cfg(feature = "alloc")]
+    fn drain_inbox(&mut self) -> Inbox<'_, Self> {
+        Inbox::new(self)
+    }
+
     /// Returns whether a given path exists in storage.
     fn store_has<T: Path>(&self, path: &T) -> Result<Option<ValueType>, RuntimeError>;
 
+    /// Returns whether a given path has a value, subkeys, or both in storage.
+    fn store_exists<T: Path>(&self, path: &T) -> Result<bool, RuntimeError> {
+        Ok(self.store_has(path)?.is_some())
+    }
+
+    /// Returns whether a given path has subkeys in storage, whether or not it
+    /// has a value.
+    fn store_has_subtree<T: Path>(&self, path: &T) -> Result<bool, RuntimeError> {
+        Ok(matches!(
+            self.store_has(path)?,
+            Some(ValueType::Subtree | ValueType::ValueWithSubtree)
+        ))
+    }
+
     /// Read up to `max_bytes` from the given path in storage, starting `from_offset`.
     #[cfg(feature = "alloc")]
     fn store_read<T: Path>(
@@ -124,10 +201,58 @@ pub trait Runtime {
         buffer: &mut [u8],
     ) -> Result<usize, RuntimeError>;
 
+    /// Same as [`Runtime::store_read_slice`], but first checks `from_offset`
+    /// against the size of the value, without calling the host to read if it
+    /// is out of bounds.
+    ///
+    /// Fails with [`RuntimeError::PathNotFound`] if there is no value at
+    /// `path`, and [`RuntimeError::StoreOffsetOutOfBounds`] if `from_offset`
+    /// is past the end of the value. Reads 0 bytes at the end of the value.
+    fn store_read_slice_checked<T: Path>(
+        &self,
+        path: &T,
+        from_offset: usize,
+        buffer: &mut [u8],
+    ) -> Result<usize, RuntimeError> {
+        let size = self.store_value_size(path)?;
+        match from_offset.cmp(&size) {
+            core::cmp::Ordering::Greater => Err(RuntimeError::StoreOffsetOutOfBounds),
+            core::cmp::Ordering::Equal => Ok(0),
+            core::cmp::Ordering::Less => self.store_read_slice(path, from_offset, buffer),
+        }
+    }
+
     /// Read an entire value from the given path in storage.
     #[cfg(feature = "alloc")]
     fn store_read_all(&self, path: &impl Path) -> Result<Vec<u8>, RuntimeError>;
 
+    /// Read the value at `path` in storage, and decode it as written by
+    /// [`Runtime::store_write_typed`].
+    ///
+    /// Fails with [`DecodeError::InvalidValue`] if the value doesn't decode.
+    #[cfg(feature = "serde")]
+    fn store_read_typed<T: serde::de::DeserializeOwned, P: Path>(
+        &self,
+        path: &P,
+    ) -> Result<T, RuntimeError> {
+        let bytes = self.store_read_all(path)?;
+        postcard::from_bytes(&bytes)
+            .map_err(|_| RuntimeError::Decode(DecodeError::InvalidValue))
+    }
+
+    /// Encode `value` and write it to storage at `path`, replacing the value (if
+    /// any) previously stored there.
+    #[cfg(feature = "serde")]
+    fn store_write_typed<T: serde::Serialize, P: Path>(
+        &mut self,
+        path: &P,
+        value: &T,
+    ) -> Result<(), RuntimeError> {
+        let bytes =
+            postcard::to_allocvec(value).map_err(|_| RuntimeError::EncodingError)?;
+        self.store_write_all(path, &bytes)
+    }
+
     /// Write the bytes given by `src` to storage at `path`, starting `at_offset`.
     ///
     /// Contrary to `store_write_all`, this does not replace the value (if any)
@@ -150,6 +275,23 @@ pub trait Runtime {
         src: &[u8],
     ) -> Result<(), RuntimeError>;
 
+    /// Write each value to storage at its path, as with
+    /// [`Runtime::store_write_all`], in order.
+    ///
+    /// Stops at the first failing write, returning its index in `writes`
+    /// together with the error. Writes before it have been performed.
+    fn store_write_many<'a, T: Path + 'a>(
+        &mut self,
+        writes: impl IntoIterator<Item = (&'a T, &'a [u8])>,
+    ) -> Result<(), (usize, RuntimeError)> {
+        writes
+            .into_iter()
+            .enumerate()
+            .try_for_each(|(index, (path, src))| {
+                self.store_write_all(path, src).map_err(|e| (index, e))
+            })
+    }
+
     /// Delete `path` from storage.
     fn store_delete<T: Path>(&mut self, path: &T) -> Result<(), RuntimeError>;
 
@@ -158,6 +300,14 @@ pub trait Runtime {
 
     /// Count the number of subkeys under `prefix`.
     ///
+    /// Subkeys are counted whether they hold a value or only further subkeys.
+    /// Telling them apart requires their names, to query them with
+    /// [Runtime::store_has].
+    ///
+    /// The SDK doesn't expose subkeys by index, so they can't be enumerated:
+    /// kernels needing to iterate over entries should keep their own index in
+    /// storage, e.g. under consecutive numeric subkeys.
+    ///
     /// See [SmartRollupCore::store_list_size].
     fn store_count_subkeys<T: Path>(&self, prefix: &T) -> Result<u64, RuntimeError>;
 
@@ -170,6 +320,51 @@ pub trait Runtime {
         to_path: &impl Path,
     ) -> Result<(), RuntimeError>;
 
+    /// Exchange the values and subkeys stored at two paths, using
+    /// `/tmp/store_swap` as a temporary location.
+    ///
+    /// Fails, without modifying storage, with:
+    /// - [`RuntimeError::OverlappingPaths`] if one path is a prefix of the
+    ///   other;
+    /// - [`RuntimeError::PathNotFound`] unless there is something stored at
+    ///   both paths;
+    /// - [`RuntimeError::TemporaryPathInUse`] if there is something stored at
+    ///   `/tmp/store_swap`.
+    ///
+    /// If a move fails midway, the moves already done are undone on a
+    /// best-effort basis, and the error is returned.
+    fn store_swap(&mut self, a: &impl Path, b: &impl Path) -> Result<(), RuntimeError> {
+        const TMP_PATH: RefPath = RefPath::assert_from(b"/tmp/store_swap");
+
+        if a.as_bytes() != b.as_bytes()
+            && (is_path_prefix(a.as_bytes(), b.as_bytes())
+                || is_path_prefix(b.as_bytes(), a.as_bytes()))
+        {
+            return Err(RuntimeError::OverlappingPaths);
+        }
+        if !self.store_exists(a)? || !self.store_exists(b)? {
+            return Err(RuntimeError::PathNotFound);
+        }
+        if a.as_bytes() == b.as_bytes() {
+            return Ok(());
+        }
+        if self.store_exists(&TMP_PATH)? {
+            return Err(RuntimeError::TemporaryPathInUse);
+        }
+
+        self.store_move(a, &TMP_PATH)?;
+        if let Err(e) = self.store_move(b, a) {
+            let _ = self.store_move(&TMP_PATH, a);
+            return Err(e);
+        }
+        if let Err(e) = self.store_move(&TMP_PATH, b) {
+            let _ = self.store_move(a, b);
+            let _ = self.store_move(&TMP_PATH, a);
+            return Err(e);
+        }
+        Ok(())
+    }
+
     /// Copy one part of durable storage to a different location
     ///
     /// See [SmartRollupCore::store_copy].
@@ -190,6 +385,42 @@ pub trait Runtime {
         destination: &mut [u8],
     ) -> Result<usize, RuntimeError>;
 
+    /// Reveal pre-image from a hash of size `PREIMAGE_HASH_SIZE` in bytes,
+    /// into a buffer of the exact size of the pre-image.
+    ///
+    /// Pre-images are at most [`MAX_PREIMAGE_SIZE`] bytes long.
+    ///
+    /// [`MAX_PREIMAGE_SIZE`]: tezos_smart_rollup_core::MAX_PREIMAGE_SIZE
+    #[cfg(feature = "alloc")]
+    fn reveal_preimage_owned(
+        &self,
+        hash: &[u8; PREIMAGE_HASH_SIZE],
+    ) -> Result<Vec<u8>, RuntimeError> {
+        use tezos_smart_rollup_core::MAX_PREIMAGE_SIZE;
+
+        let mut buffer = alloc::vec![0; MAX_PREIMAGE_SIZE];
+        let size = self.reveal_preimage(hash, &mut buffer)?;
+        buffer.truncate(size);
+        Ok(buffer)
+    }
+
+    /// Reveal the pre-images of several hashes, as with
+    /// [`Runtime::reveal_preimage_owned`], in order.
+    ///
+    /// Stops at the first failing reveal, returning its index in `hashes`
+    /// together with the error.
+    #[cfg(feature = "alloc")]
+    fn reveal_preimages(
+        &self,
+        hashes: &[[u8; PREIMAGE_HASH_SIZE]],
+    ) -> Result<Vec<Vec<u8>>, (usize, RuntimeError)> {
+        hashes
+            .iter()
+            .enumerate()
+            .map(|(index, hash)| self.reveal_preimage_owned(hash).map_err(|e| (index, e)))
+            .collect()
+    }
+
     /// Reveal a DAL page.
     #[cfg(all(feature = "alloc", feature = "proto-alpha"))]
     fn reveal_dal_page(
@@ -207,6 +438,19 @@ pub trait Runtime {
     /// Return the size of value stored at `path`
     fn store_value_size(&self, path: &impl Path) -> Result<usize, RuntimeError>;
 
+    /// Return the size of value stored at `path`, without first checking that
+    /// `path` exists.
+    ///
+    /// Saves a host call compared to [`Runtime::store_value_size`]. Returns
+    /// [`RuntimeError::PathNotFound`] if there is no value at `path`, including
+    /// when `path` only has subkeys.
+    fn store_value_size_unchecked(
+        &self,
+        path: &impl Path,
+    ) -> Result<usize, RuntimeError> {
+        self.store_value_size(path)
+    }
+
     /// Mark the kernel for reboot.
     ///
     /// If the kernel is marked for reboot, it will continue
@@ -241,6 +485,9 @@ pub trait Runtime {
     fn reboot_left(&self) -> Result<u32, RuntimeError>;
 
     /// The runtime_version the kernel is using.
+    ///
+    /// Fails with [`DecodeError::InvalidUtf8`] if the stored version isn't
+    /// valid UTF-8.
     #[cfg(feature = "alloc")]
     fn runtime_version(&self) -> Result<String, RuntimeError>;
 }
@@ -427,6 +674,8 @@ where
     ) -> Result<(), RuntimeError> {
         use tezos_smart_rollup_core::MAX_FILE_CHUNK_SIZE;
 
+        check_path_writable(path)?;
+
         let write = |bytes: &[u8], offset| {
             let result_code = unsafe {
                 SmartRollupCore::store_write(
@@ -485,6 +734,7 @@ where
     }
 
     fn store_delete<T: Path>(&mut self, path: &T) -> Result<(), RuntimeError> {
+        check_path_writable(path)?;
         check_path_exists(self, path)?;
 
         let res =
@@ -496,6 +746,8 @@ where
     }
 
     fn store_delete_value<T: Path>(&mut self, path: &T) -> Result<(), RuntimeError> {
+        check_path_writable(path)?;
+
         let res = unsafe {
             SmartRollupCore::store_delete_value(self, path.as_ptr(), path.size())
         };
@@ -521,21 +773,7 @@ where
         from_path: &impl Path,
         to_path: &impl Path,
     ) -> Result<(), RuntimeError> {
-        check_path_exists(self, from_path)?;
-
-        let res = unsafe {
-            SmartRollupCore::store_move(
-                self,
-                from_path.as_ptr(),
-                from_path.size(),
-                to_path.as_ptr(),
-                to_path.size(),
-            )
-        };
-        match Error::wrap(res) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(RuntimeError::HostErr(e)),
-        }
+        store_transfer(self, from_path, to_path, TransferKind::Move)
     }
 
     fn store_copy(
@@ -543,21 +781,7 @@ where
         from_path: &impl Path,
         to_path: &impl Path,
     ) -> Result<(), RuntimeError> {
-        check_path_exists(self, from_path)?;
-
-        let res = unsafe {
-            SmartRollupCore::store_copy(
-                self,
-                from_path.as_ptr(),
-                from_path.size(),
-                to_path.as_ptr(),
-                to_path.size(),
-            )
-        };
-        match Error::wrap(res) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(RuntimeError::HostErr(e)),
-        }
+        store_transfer(self, from_path, to_path, TransferKind::Copy)
     }
 
     fn reveal_preimage(
@@ -676,6 +900,20 @@ where
         }
     }
 
+    fn store_value_size_unchecked(
+        &self,
+        path: &impl Path,
+    ) -> Result<usize, RuntimeError> {
+        let res = unsafe {
+            SmartRollupCore::store_value_size(self, path.as_ptr(), path.size())
+        };
+        match Error::wrap(res) {
+            Ok(size) => Ok(size),
+            Err(Error::StoreNotAValue) => Err(RuntimeError::PathNotFound),
+            Err(e) => Err(RuntimeError::HostErr(e)),
+        }
+    }
+
     fn mark_for_reboot(&mut self) -> Result<(), RuntimeError> {
         self.store_write(&REBOOT_PATH, &[0_u8], 0)
     }
@@ -720,9 +958,48 @@ where
         const PATH_VERSION: RefPath =
             RefPath::assert_from_readonly(b"/readonly/wasm_version");
         let bytes = Runtime::store_read(self, &PATH_VERSION, 0, 9)?;
-        // SAFETY: This storage can only contains valid version string which are utf8 safe.
-        let version = unsafe { alloc::string::String::from_utf8_unchecked(bytes) };
-        Ok(version)
+        alloc::string::String::from_utf8(bytes)
+            .map_err(|_| RuntimeError::Decode(DecodeError::InvalidUtf8))
+    }
+}
+
+/// Whether [store_transfer] keeps the source of the transfer.
+#[derive(Clone, Copy)]
+enum TransferKind {
+    Move,
+    Copy,
+}
+
+/// Move or copy `from_path` to `to_path`, after checking that the paths
+/// modified are writable, and that `from_path` exists.
+fn store_transfer<Host: SmartRollupCore>(
+    host: &mut Host,
+    from_path: &impl Path,
+    to_path: &impl Path,
+    kind: TransferKind,
+) -> Result<(), RuntimeError> {
+    if let TransferKind::Move = kind {
+        check_path_writable(from_path)?;
+    }
+    check_path_writable(to_path)?;
+    check_path_exists(host, from_path)?;
+
+    let transfer = match kind {
+        TransferKind::Move => <Host as SmartRollupCore>::store_move,
+        TransferKind::Copy => <Host as SmartRollupCore>::store_copy,
+    };
+    let res = unsafe {
+        transfer(
+            host,
+            from_path.as_ptr(),
+            from_path.size(),
+            to_path.as_ptr(),
+            to_path.size(),
+        )
+    };
+    match Error::wrap(res) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(RuntimeError::HostErr(e)),
     }
 }
 
@@ -740,6 +1017,14 @@ fn check_path_has_value<T: Path>(
     }
 }
 
+fn check_path_writable(path: &impl Path) -> Result<(), RuntimeError> {
+    if is_readonly(path.as_bytes()) {
+        Err(RuntimeError::ReadonlyPath)
+    } else {
+        Ok(())
+    }
+}
+
 fn check_path_exists<T: Path>(
     runtime: &impl Runtime,
     path: &T,
@@ -753,7 +1038,7 @@ fn check_path_exists<T: Path>(
 
 #[cfg(test)]
 mod tests {
-    use super::{Runtime, RuntimeError, PREIMAGE_HASH_SIZE};
+    use super::{DecodeError, Runtime, RuntimeError, PREIMAGE_HASH_SIZE};
     #[cfg(feature = "proto-alpha")]
     use crate::{dal_parameters::RollupDalParameters, DAL_PARAMETERS_SIZE};
     use crate::{
@@ -766,7 +1051,7 @@ mod tests {
     use test_helpers::*;
     use tezos_smart_rollup_core::{
         smart_rollup_core::MockSmartRollupCore, MAX_FILE_CHUNK_SIZE,
-        MAX_INPUT_MESSAGE_SIZE, MAX_OUTPUT_SIZE,
+        MAX_INPUT_MESSAGE_SIZE, MAX_OUTPUT_SIZE, MAX_PREIMAGE_SIZE,
     };
 
     const READ_SIZE: usize = 80;
@@ -818,6 +1103,63 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn given_typed_output_written_then_decodes() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Outbox {
+            level: u32,
+            payload: Vec<u8>,
+        }
+
+        // Arrange
+        let mut mock = MockSmartRollupCore::new();
+        let value = Outbox {
+            level: 5,
+            payload: b"transfer".to_vec(),
+        };
+
+        mock.expect_write_output().return_once(|ptr, len| {
+            let slice = unsafe { from_raw_parts(ptr, len) };
+            let decoded: Outbox = postcard::from_bytes(slice).unwrap();
+
+            assert_eq!(
+                Outbox {
+                    level: 5,
+                    payload: b"transfer".to_vec()
+                },
+                decoded
+            );
+
+            0
+        });
+
+        // Act
+        let result = mock.write_output_typed(&value);
+
+        // Assert
+        assert_eq!(Ok(()), result);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn given_typed_output_too_large_then_err() {
+        // Arrange
+        let mut mock = MockSmartRollupCore::new();
+        let value = vec![0_u8; MAX_OUTPUT_SIZE];
+
+        mock.expect_write_output().never();
+
+        // Act
+        let result = mock.write_output_typed(&value);
+
+        // Assert
+        assert_eq!(
+            Err(RuntimeError::HostErr(Error::InputOutputTooLarge)),
+            result
+        );
+    }
+
     #[test]
     fn read_input_returns_none_when_nothing_read() {
         // Arrange
@@ -901,6 +1243,31 @@ mod tests {
         assert!(matches!(result, Ok(None)));
     }
 
+    #[test]
+    fn store_exists_and_has_subtree() {
+        use tezos_smart_rollup_core::{
+            VALUE_TYPE_NONE, VALUE_TYPE_SUBTREE, VALUE_TYPE_VALUE,
+            VALUE_TYPE_VALUE_WITH_SUBTREE,
+        };
+
+        const PATH: RefPath<'static> = RefPath::assert_from("/a/path".as_bytes());
+
+        for (value_type, exists, has_subtree) in [
+            (VALUE_TYPE_NONE, false, false),
+            (VALUE_TYPE_VALUE, true, false),
+            (VALUE_TYPE_SUBTREE, true, true),
+            (VALUE_TYPE_VALUE_WITH_SUBTREE, true, true),
+        ] {
+            // Arrange
+            let mut mock = MockSmartRollupCore::new();
+            mock.expect_store_has().return_const(value_type);
+
+            // Act & Assert
+            assert_eq!(Ok(exists), mock.store_exists(&PATH));
+            assert_eq!(Ok(has_subtree), mock.store_has_subtree(&PATH));
+        }
+    }
+
     #[test]
     fn store_read_max_bytes() {
         // Arrange
@@ -981,6 +1348,53 @@ mod tests {
         assert_eq!(Err(RuntimeError::PathNotFound), result);
     }
 
+    #[test]
+    fn store_read_slice_checked_at_end() {
+        // Arrange
+        const PATH: RefPath<'static> = RefPath::assert_from("/a/simple/path".as_bytes());
+        let mut mock = mock_path_exists(PATH.as_bytes());
+        mock.expect_store_value_size().return_const(10);
+        mock.expect_store_read().never();
+        let mut buffer = [0; 4];
+
+        // Act
+        let result = mock.store_read_slice_checked(&PATH, 10, &mut buffer);
+
+        // Assert
+        assert_eq!(Ok(0), result);
+    }
+
+    #[test]
+    fn store_read_slice_checked_out_of_bounds() {
+        // Arrange
+        const PATH: RefPath<'static> = RefPath::assert_from("/a/simple/path".as_bytes());
+        let mut mock = mock_path_exists(PATH.as_bytes());
+        mock.expect_store_value_size().return_const(10);
+        mock.expect_store_read().never();
+        let mut buffer = [0; 4];
+
+        // Act
+        let result = mock.store_read_slice_checked(&PATH, 11, &mut buffer);
+
+        // Assert
+        assert_eq!(Err(RuntimeError::StoreOffsetOutOfBounds), result);
+    }
+
+    #[test]
+    fn store_read_slice_checked_path_not_found() {
+        // Arrange
+        let bytes = "/a/2nd/PATH.which/doesnt/exist".as_bytes().to_vec();
+        let path: OwnedPath = RefPath::assert_from(&bytes).into();
+        let mock = mock_path_not_existing(bytes);
+        let mut buffer = [0; 4];
+
+        // Act
+        let result = mock.store_read_slice_checked(&path, 0, &mut buffer);
+
+        // Assert
+        assert_eq!(Err(RuntimeError::PathNotFound), result);
+    }
+
     #[test]
     fn store_read_all_above_max_file_chunk_size() {
         // Arrange
@@ -1041,6 +1455,21 @@ mod tests {
         assert_eq!(Ok(expected), result);
     }
 
+    #[test]
+    fn store_read_all_path_not_found() {
+        // Arrange
+        let bytes = "/a/2nd/PATH.which/doesnt/exist".as_bytes().to_vec();
+        let path: OwnedPath = RefPath::assert_from(&bytes).into();
+
+        let mock = mock_path_not_existing(bytes);
+
+        // Act
+        let result = Runtime::store_read_all(&mock, &path);
+
+        // Assert
+        assert_eq!(Err(RuntimeError::PathNotFound), result);
+    }
+
     #[test]
     fn store_write_ok() {
         // Arrange
@@ -1171,6 +1600,189 @@ mod tests {
         assert_eq!(value, result_read);
     }
 
+    #[test]
+    fn store_write_all_replaces_longer_value() {
+        // Arrange
+        const PATH: RefPath<'static> = RefPath::assert_from("/a/simple/path".as_bytes());
+        const OLD_VALUE: &[u8] = b"0123456789";
+        const NEW_VALUE: &[u8] = b"abc";
+
+        let mut mock = MockSmartRollupCore::new();
+        let mut sequence = mockall::Sequence::new();
+        let stored = std::sync::Arc::new(std::sync::Mutex::new(OLD_VALUE.to_vec()));
+
+        let deleted = stored.clone();
+        mock.expect_store_delete_value()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .withf(|path_ptr, path_size| {
+                let path = unsafe { from_raw_parts(*path_ptr, *path_size) };
+                path == PATH.as_bytes()
+            })
+            .returning(move |_, _| {
+                deleted.lock().unwrap().clear();
+                0
+            });
+
+        let written = stored.clone();
+        mock.expect_store_write()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .withf(|path_ptr, path_size, at_offset, _, _| {
+                let path = unsafe { from_raw_parts(*path_ptr, *path_size) };
+                path == PATH.as_bytes() && *at_offset == 0
+            })
+            .returning(move |_, _, at_offset, src_ptr, src_size| {
+                let src = unsafe { from_raw_parts(src_ptr, src_size) };
+                let mut value = written.lock().unwrap();
+                let len = usize::max(value.len(), at_offset + src_size);
+                value.resize(len, 0);
+                value[at_offset..at_offset + src_size].copy_from_slice(src);
+                0
+            });
+
+        // Act
+        let result = mock.store_write_all(&PATH, NEW_VALUE);
+
+        // Assert
+        assert_eq!(Ok(()), result);
+        assert_eq!(NEW_VALUE, stored.lock().unwrap().as_slice());
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Account {
+        nonce: u64,
+        name: String,
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn store_typed_value_roundtrip() {
+        // Arrange
+        const PATH: RefPath<'static> = RefPath::assert_from("/accounts/alice".as_bytes());
+
+        let mut mock = MockSmartRollupCore::new();
+        let stored = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        mock.expect_store_delete_value().return_const(0);
+
+        let written = stored.clone();
+        mock.expect_store_write()
+            .returning(move |_, _, at_offset, src_ptr, src_size| {
+                let src = unsafe { from_raw_parts(src_ptr, src_size) };
+                let mut value = written.lock().unwrap();
+                value.truncate(at_offset);
+                value.extend_from_slice(src);
+                0
+            });
+
+        let read = stored.clone();
+        mock.expect_store_value_size()
+            .returning(move |_, _| read.lock().unwrap().len().try_into().unwrap());
+
+        let read = stored.clone();
+        mock.expect_store_read()
+            .returning(move |_, _, offset, buf_ptr, max_bytes| {
+                let value = read.lock().unwrap();
+                let chunk = &value[offset..usize::min(value.len(), offset + max_bytes)];
+                let buffer = unsafe { from_raw_parts_mut(buf_ptr, chunk.len()) };
+                buffer.copy_from_slice(chunk);
+                chunk.len().try_into().unwrap()
+            });
+
+        mock.expect_store_has().return_const(1_i32);
+
+        let account = Account {
+            nonce: 42,
+            name: "alice".to_string(),
+        };
+
+        // Act
+        let written = mock.store_write_typed(&PATH, &account);
+        let read: Result<Account, _> = mock.store_read_typed(&PATH);
+
+        // Assert
+        assert_eq!(Ok(()), written);
+        assert_eq!(Ok(account), read);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn store_read_typed_corrupted_value() {
+        // Arrange
+        const PATH: RefPath<'static> = RefPath::assert_from("/accounts/alice".as_bytes());
+        // a nonce, then a name length exceeding the remaining bytes
+        const VALUE: &[u8] = &[42, 10, b'a'];
+
+        let mut mock = mock_path_exists(PATH.as_bytes());
+        mock.expect_store_value_size()
+            .return_const(i32::try_from(VALUE.len()).unwrap());
+        mock.expect_store_read().returning(|_, _, _, buf_ptr, _| {
+            let buffer = unsafe { from_raw_parts_mut(buf_ptr, VALUE.len()) };
+            buffer.copy_from_slice(VALUE);
+            VALUE.len().try_into().unwrap()
+        });
+
+        // Act
+        let result: Result<Account, _> = mock.store_read_typed(&PATH);
+
+        // Assert
+        assert_eq!(Err(RuntimeError::Decode(DecodeError::InvalidValue)), result);
+    }
+
+    #[test]
+    fn store_write_many_stops_at_first_error() {
+        // Arrange
+        const PATHS: [RefPath<'static>; 3] = [
+            RefPath::assert_from(b"/a"),
+            RefPath::assert_from(b"/b"),
+            RefPath::assert_from(b"/c"),
+        ];
+
+        let mut mock = MockSmartRollupCore::new();
+        let mut sequence = mockall::Sequence::new();
+        let path_is = |expected: &'static [u8]| {
+            move |path_ptr: &*const u8, path_size: &usize| {
+                let path = unsafe { from_raw_parts(*path_ptr, *path_size) };
+                path == expected
+            }
+        };
+
+        mock.expect_store_delete_value()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .withf(path_is(b"/a"))
+            .return_const(0);
+        mock.expect_store_write()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .withf(|path_ptr, path_size, _, src_ptr, src_size| {
+                let path = unsafe { from_raw_parts(*path_ptr, *path_size) };
+                let src = unsafe { from_raw_parts(*src_ptr, *src_size) };
+                path == b"/a" && src == b"first"
+            })
+            .return_const(0);
+        mock.expect_store_delete_value()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .withf(path_is(b"/b"))
+            .return_const(Error::StoreNotAValue.code());
+
+        // Act
+        let result = mock.store_write_many([
+            (&PATHS[0], b"first".as_slice()),
+            (&PATHS[1], b"second".as_slice()),
+            (&PATHS[2], b"third".as_slice()),
+        ]);
+
+        // Assert
+        assert_eq!(
+            Err((1, RuntimeError::HostErr(Error::StoreNotAValue))),
+            result
+        );
+    }
+
     #[test]
     fn store_delete() {
         // Arrange
@@ -1208,6 +1820,69 @@ mod tests {
         assert_eq!(Err(RuntimeError::PathNotFound), result);
     }
 
+    #[test]
+    fn store_modify_readonly_path() {
+        // Arrange
+        const READONLY: RefPath<'static> =
+            RefPath::assert_from_readonly(b"/readonly/kernel/env/reboot_counter");
+        const WRITABLE: RefPath<'static> = RefPath::assert_from(b"/a/writable/path");
+
+        // no expectations: the host must not be called
+        let mut mock = MockSmartRollupCore::new();
+
+        // Act & Assert
+        assert_eq!(
+            Err(RuntimeError::ReadonlyPath),
+            mock.store_write(&READONLY, b"value", 0)
+        );
+        assert_eq!(
+            Err(RuntimeError::ReadonlyPath),
+            mock.store_write_all(&READONLY, b"value")
+        );
+        assert_eq!(
+            Err(RuntimeError::ReadonlyPath),
+            mock.store_delete(&READONLY)
+        );
+        assert_eq!(
+            Err(RuntimeError::ReadonlyPath),
+            mock.store_delete_value(&READONLY)
+        );
+        assert_eq!(
+            Err(RuntimeError::ReadonlyPath),
+            mock.store_move(&READONLY, &WRITABLE)
+        );
+        assert_eq!(
+            Err(RuntimeError::ReadonlyPath),
+            mock.store_move(&WRITABLE, &READONLY)
+        );
+        assert_eq!(
+            Err(RuntimeError::ReadonlyPath),
+            mock.store_copy(&WRITABLE, &READONLY)
+        );
+    }
+
+    #[test]
+    fn store_write_writable_path() {
+        // Arrange
+        const PATH: RefPath<'static> = RefPath::assert_from(b"/readonly.is/writable");
+
+        let mut mock = MockSmartRollupCore::new();
+        mock.expect_store_write()
+            .withf(|path_ptr, path_size, offset, src_ptr, src_size| {
+                let path = unsafe { from_raw_parts(*path_ptr, *path_size) };
+                let src = unsafe { from_raw_parts(*src_ptr, *src_size) };
+
+                PATH.as_bytes() == path && *offset == 0 && src == b"value"
+            })
+            .return_const(0);
+
+        // Act
+        let result = mock.store_write(&PATH, b"value", 0);
+
+        // Assert
+        assert_eq!(Ok(()), result);
+    }
+
     #[test]
     fn store_delete_value() {
         // Arrange
@@ -1266,6 +1941,182 @@ mod tests {
         assert_eq!(Ok(subkey_count.try_into().unwrap()), result);
     }
 
+    type MockStore =
+        std::sync::Arc<std::sync::Mutex<std::collections::BTreeMap<Vec<u8>, Vec<u8>>>>;
+
+    /// Mock whose durable storage is a map from paths to values, supporting
+    /// `store_has` and `store_move`. The map is returned along with the mock.
+    fn mock_store_with_moves(
+        values: &[(&[u8], &[u8])],
+    ) -> (MockSmartRollupCore, MockStore) {
+        let store = std::sync::Arc::new(std::sync::Mutex::new(
+            values
+                .iter()
+                .map(|(path, value)| (path.to_vec(), value.to_vec()))
+                .collect::<std::collections::BTreeMap<_, _>>(),
+        ));
+        let mut mock = MockSmartRollupCore::new();
+
+        let has = store.clone();
+        mock.expect_store_has().returning(move |ptr, size| {
+            let path = unsafe { from_raw_parts(ptr, size) };
+            if has.lock().unwrap().contains_key(path) {
+                tezos_smart_rollup_core::VALUE_TYPE_VALUE
+            } else {
+                tezos_smart_rollup_core::VALUE_TYPE_NONE
+            }
+        });
+
+        let moves = store.clone();
+        mock.expect_store_move().returning(
+            move |from_ptr, from_size, to_ptr, to_size| {
+                let from = unsafe { from_raw_parts(from_ptr, from_size) };
+                let to = unsafe { from_raw_parts(to_ptr, to_size) };
+                let mut store = moves.lock().unwrap();
+                let value = store.remove(from).unwrap();
+                store.insert(to.to_vec(), value);
+                0
+            },
+        );
+
+        (mock, store)
+    }
+
+    #[test]
+    fn store_swap() {
+        // Arrange
+        const A: RefPath<'static> = RefPath::assert_from(b"/state/a");
+        const B: RefPath<'static> = RefPath::assert_from(b"/state/b");
+        let (mut mock, store) =
+            mock_store_with_moves(&[(b"/state/a", b"first"), (b"/state/b", b"second")]);
+
+        // Act
+        let result = mock.store_swap(&A, &B);
+
+        // Assert
+        assert_eq!(Ok(()), result);
+        assert_eq!(
+            vec![
+                (b"/state/a".to_vec(), b"second".to_vec()),
+                (b"/state/b".to_vec(), b"first".to_vec())
+            ],
+            store
+                .lock()
+                .unwrap()
+                .clone()
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Ok(None),
+            mock.store_has(&RefPath::assert_from(b"/tmp/store_swap"))
+        );
+    }
+
+    #[test]
+    fn store_swap_overlapping_paths() {
+        // Arrange
+        const A: RefPath<'static> = RefPath::assert_from(b"/x/y");
+        const B: RefPath<'static> = RefPath::assert_from(b"/x");
+        let values: [(&[u8], &[u8]); 2] = [(b"/x", b"first"), (b"/x/y", b"second")];
+        let (mut mock, store) = mock_store_with_moves(&values);
+
+        // Act
+        let results = [mock.store_swap(&A, &B), mock.store_swap(&B, &A)];
+
+        // Assert
+        assert_eq!([Err(RuntimeError::OverlappingPaths); 2], results);
+        assert_eq!(
+            values
+                .iter()
+                .map(|(path, value)| (path.to_vec(), value.to_vec()))
+                .collect::<Vec<_>>(),
+            store
+                .lock()
+                .unwrap()
+                .clone()
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn store_swap_temporary_path_in_use() {
+        // Arrange
+        const A: RefPath<'static> = RefPath::assert_from(b"/state/a");
+        const B: RefPath<'static> = RefPath::assert_from(b"/state/ab");
+        let values: [(&[u8], &[u8]); 3] = [
+            (b"/state/a", b"first"),
+            (b"/state/ab", b"second"),
+            (b"/tmp/store_swap", b"kept"),
+        ];
+        let (mut mock, store) = mock_store_with_moves(&values);
+
+        // Act
+        let result = mock.store_swap(&A, &B);
+
+        // Assert
+        assert_eq!(Err(RuntimeError::TemporaryPathInUse), result);
+        assert_eq!(
+            values
+                .iter()
+                .map(|(path, value)| (path.to_vec(), value.to_vec()))
+                .collect::<Vec<_>>(),
+            store
+                .lock()
+                .unwrap()
+                .clone()
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn store_copy_keeps_source() {
+        // Arrange
+        const FROM: RefPath<'static> = RefPath::assert_from(b"/state/from");
+        const TO: RefPath<'static> = RefPath::assert_from(b"/state/to");
+        let mut mock = mock_path_exists(b"/state/from");
+        mock.expect_store_move().never();
+        mock.expect_store_copy()
+            .times(1)
+            .withf(|from_ptr, from_size, to_ptr, to_size| {
+                let from = unsafe { from_raw_parts(*from_ptr, *from_size) };
+                let to = unsafe { from_raw_parts(*to_ptr, *to_size) };
+                from == b"/state/from" && to == b"/state/to"
+            })
+            .return_const(0);
+
+        // Act
+        let result = mock.store_copy(&FROM, &TO);
+
+        // Assert
+        assert_eq!(Ok(()), result);
+    }
+
+    #[test]
+    fn store_swap_path_not_found() {
+        // Arrange
+        const A: RefPath<'static> = RefPath::assert_from(b"/state/a");
+        const B: RefPath<'static> = RefPath::assert_from(b"/state/b");
+        let (mut mock, store) = mock_store_with_moves(&[(b"/state/a", b"first")]);
+
+        // Act
+        let result = mock.store_swap(&A, &B);
+
+        // Assert
+        assert_eq!(Err(RuntimeError::PathNotFound), result);
+        assert_eq!(
+            vec![(b"/state/a".to_vec(), b"first".to_vec())],
+            store
+                .lock()
+                .unwrap()
+                .clone()
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn reveal_preimage_ok() {
         let mut mock = MockSmartRollupCore::new();
@@ -1292,6 +2143,80 @@ mod tests {
         assert_eq!(Ok(50), result);
     }
 
+    #[test]
+    fn reveal_preimage_owned_truncated() {
+        let mut mock = MockSmartRollupCore::new();
+
+        mock.expect_reveal_preimage()
+            .withf(|hash_addr, hash_len, _dest_addr, max_bytes| {
+                let hash = unsafe { from_raw_parts(*hash_addr, *hash_len) };
+                hash == [5; PREIMAGE_HASH_SIZE] && *max_bytes == MAX_PREIMAGE_SIZE
+            })
+            .return_once(|_, _, destination_address, _| {
+                let revealed_bytes = [b'!'; 50];
+                let buffer = unsafe { from_raw_parts_mut(destination_address, 50) };
+                buffer.copy_from_slice(&revealed_bytes);
+                50
+            });
+
+        // Act
+        let result = mock.reveal_preimage_owned(&[5; PREIMAGE_HASH_SIZE]);
+
+        // Assert
+        assert_eq!(Ok(vec![b'!'; 50]), result);
+    }
+
+    #[test]
+    fn reveal_preimages() {
+        // Arrange
+        let mut mock = MockSmartRollupCore::new();
+        mock.expect_reveal_preimage().times(2).returning(
+            |hash_addr, hash_len, destination_address, _| {
+                let hash = unsafe { from_raw_parts(hash_addr, hash_len) };
+                let revealed_bytes = [hash[0]; 10];
+                let buffer = unsafe { from_raw_parts_mut(destination_address, 10) };
+                buffer.copy_from_slice(&revealed_bytes);
+                10
+            },
+        );
+
+        // Act
+        let result =
+            mock.reveal_preimages(&[[1; PREIMAGE_HASH_SIZE], [2; PREIMAGE_HASH_SIZE]]);
+
+        // Assert
+        assert_eq!(Ok(vec![vec![1; 10], vec![2; 10]]), result);
+    }
+
+    #[test]
+    fn reveal_preimages_stops_at_first_error() {
+        // Arrange
+        let mut mock = MockSmartRollupCore::new();
+        mock.expect_reveal_preimage()
+            .times(2)
+            .returning(|hash_addr, hash_len, _, _| {
+                let hash = unsafe { from_raw_parts(hash_addr, hash_len) };
+                if hash[0] == 1 {
+                    0
+                } else {
+                    tezos_smart_rollup_core::GENERIC_INVALID_ACCESS
+                }
+            });
+
+        // Act
+        let result = mock.reveal_preimages(&[
+            [1; PREIMAGE_HASH_SIZE],
+            [2; PREIMAGE_HASH_SIZE],
+            [3; PREIMAGE_HASH_SIZE],
+        ]);
+
+        // Assert
+        assert_eq!(
+            Err((1, RuntimeError::HostErr(Error::GenericInvalidAccess))),
+            result
+        );
+    }
+
     #[test]
     fn store_value_size() {
         let mut mock = MockSmartRollupCore::new();
@@ -1318,6 +2243,116 @@ mod tests {
         );
     }
 
+    #[test]
+    fn store_value_size_unchecked() {
+        // Arrange
+        const PATH: RefPath<'static> = RefPath::assert_from(b"/a/value");
+        let mut mock = MockSmartRollupCore::new();
+        mock.expect_store_has().never();
+        mock.expect_store_value_size()
+            .times(1)
+            .withf(|ptr, size| {
+                let bytes = unsafe { from_raw_parts(*ptr, *size) };
+                bytes == b"/a/value"
+            })
+            .return_const(256);
+
+        // Act
+        let result = mock.store_value_size_unchecked(&PATH);
+
+        // Assert
+        assert_eq!(Ok(256), result);
+    }
+
+    #[test]
+    fn store_value_size_unchecked_path_not_found() {
+        // Arrange
+        const PATH: RefPath<'static> = RefPath::assert_from(b"/a/missing");
+        let mut mock = MockSmartRollupCore::new();
+        mock.expect_store_has().never();
+        mock.expect_store_value_size()
+            .times(1)
+            .return_const(tezos_smart_rollup_core::STORE_NOT_A_VALUE);
+
+        // Act
+        let result = mock.store_value_size_unchecked(&PATH);
+
+        // Assert
+        assert_eq!(Err(RuntimeError::PathNotFound), result);
+    }
+
+    #[test]
+    fn reboot_left_reads_counter() {
+        // Arrange
+        let mut mock = mock_path_exists(b"/readonly/kernel/env/reboot_counter");
+        mock.expect_store_read()
+            .withf(|_, _, offset, _, max_bytes| *offset == 0 && *max_bytes == 4)
+            .return_once(|_, _, _, buf_ptr, _| {
+                let buffer = unsafe { from_raw_parts_mut(buf_ptr, 4) };
+                buffer.copy_from_slice(&997_i32.to_le_bytes());
+                4
+            });
+
+        // Act
+        let result = mock.reboot_left();
+
+        // Assert
+        assert_eq!(Ok(997), result);
+    }
+
+    #[test]
+    fn restart_forced_reads_flag() {
+        // Arrange
+        let flagged = mock_path_exists(b"/readonly/kernel/env/too_many_reboot");
+        let not_flagged =
+            mock_path_not_existing(b"/readonly/kernel/env/too_many_reboot".to_vec());
+
+        // Act & Assert
+        assert_eq!(Ok(true), flagged.restart_forced());
+        assert_eq!(Ok(false), not_flagged.restart_forced());
+    }
+
+    #[test]
+    fn runtime_version_invalid_utf8() {
+        // Arrange
+        let mut mock = mock_path_exists(b"/readonly/wasm_version");
+        mock.expect_store_read().return_once(|_, _, _, buf_ptr, _| {
+            let buffer = unsafe { from_raw_parts_mut(buf_ptr, 2) };
+            buffer.copy_from_slice(&[0xc3, 0x28]);
+            2
+        });
+
+        // Act
+        let result = mock.runtime_version();
+
+        // Assert
+        assert_eq!(Err(RuntimeError::Decode(DecodeError::InvalidUtf8)), result);
+    }
+
+    #[test]
+    fn runtime_error_display() {
+        // Arrange
+        let not_found = RuntimeError::PathNotFound;
+        let host_err = RuntimeError::HostErr(Error::StoreNotAValue);
+
+        // Act & Assert
+        assert_eq!("RuntimeError::PathNotFound", not_found.to_string());
+        assert_eq!("StoreNotAValue", host_err.to_string());
+    }
+
+    #[test]
+    fn runtime_error_decode() {
+        // Arrange
+        let error = RuntimeError::Decode(DecodeError::InvalidUtf8);
+
+        // Act & Assert
+        assert!(matches!(
+            error,
+            RuntimeError::Decode(DecodeError::InvalidUtf8)
+        ));
+        assert_eq!("RuntimeError::Decode(InvalidUtf8)", error.to_string());
+    }
+
     #[test]
     fn reveal_metadata_ok() {
         let mut mock = MockSmartRollupCore::new();
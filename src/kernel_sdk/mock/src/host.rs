@@ -211,7 +211,9 @@ mod tests {
     use super::MockHost;
 
     use crate::state::HostState;
-    use tezos_smart_rollup_core::{MAX_FILE_CHUNK_SIZE, MAX_INPUT_MESSAGE_SIZE};
+    use tezos_smart_rollup_core::{
+        MAX_FILE_CHUNK_SIZE, MAX_INPUT_MESSAGE_SIZE, MAX_OUTPUT_SIZE,
+    };
     use tezos_smart_rollup_host::input::Message;
     use tezos_smart_rollup_host::{
         metadata::RollupMetadata,
@@ -241,6 +243,88 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn read_input_drains_inbox() {
+        // Arrange
+        let mut mock_host = MockHost::default();
+        mock_host.as_mut().add_input(vec![1; 10]);
+        mock_host.as_mut().add_input(vec![2; 20]);
+        let level = mock_host.level();
+
+        // Act
+        let first = mock_host.read_input();
+        let second = mock_host.read_input();
+        let drained = mock_host.read_input();
+
+        // Assert
+        assert_eq!(Ok(Some(Message::new(level, 0, vec![1; 10]))), first);
+        assert_eq!(Ok(Some(Message::new(level, 1, vec![2; 20]))), second);
+        assert_eq!(Ok(None), drained);
+    }
+
+    #[test]
+    fn store_delete_and_count_subkeys() {
+        // Arrange
+        let mut mock = MockHost::default();
+        const PREFIX: RefPath = RefPath::assert_from(b"/prefix");
+        const PATH_A: RefPath = RefPath::assert_from(b"/prefix/a");
+        const PATH_B: RefPath = RefPath::assert_from(b"/prefix/b");
+
+        mock.store_write_all(&PATH_A, b"a").unwrap();
+        mock.store_write_all(&PATH_B, b"b").unwrap();
+
+        // Act
+        let count_before = mock.store_count_subkeys(&PREFIX);
+        let deleted = mock.store_delete(&PATH_A);
+        let count_after = mock.store_count_subkeys(&PREFIX);
+
+        // Assert
+        assert_eq!(Ok(2), count_before);
+        assert_eq!(Ok(()), deleted);
+        assert_eq!(Ok(1), count_after);
+        assert_eq!(
+            Err(RuntimeError::PathNotFound),
+            mock.store_read_all(&PATH_A)
+        );
+        assert_eq!(Ok(b"b".to_vec()), mock.store_read_all(&PATH_B));
+    }
+
+    #[test]
+    fn write_output_added_to_outbox() {
+        // Arrange
+        let mut mock_host = MockHost::default();
+
+        // Act
+        let first = mock_host.write_output(b"first");
+        let second = mock_host.write_output(b"second");
+
+        // Assert
+        assert_eq!(Ok(()), first);
+        assert_eq!(Ok(()), second);
+        assert_eq!(
+            vec![b"first".to_vec(), b"second".to_vec()],
+            mock_host.outbox_at(mock_host.level())
+        );
+    }
+
+    #[test]
+    fn write_output_too_large() {
+        // Arrange
+        let mut mock_host = MockHost::default();
+
+        // Act
+        let result = mock_host.write_output(&[0; MAX_OUTPUT_SIZE + 1]);
+
+        // Assert
+        assert_eq!(
+            Err(RuntimeError::HostErr(
+                tezos_smart_rollup_host::Error::InputOutputTooLarge
+            )),
+            result
+        );
+        assert!(mock_host.outbox_at(mock_host.level()).is_empty());
+    }
+
     #[test]
     fn test_reveal_preimage() {
         // Arrange
@@ -5,7 +5,7 @@ use tezos_smart_rollup_encoding::dac::certificate::CertificateError;
 use tezos_smart_rollup_encoding::dac::certificate::*;
 use tezos_smart_rollup_encoding::dac::pages::*;
 use tezos_smart_rollup_host::path::RefPath;
-use tezos_smart_rollup_host::runtime::Runtime;
+use tezos_smart_rollup_host::runtime::{Runtime, RuntimeError};
 
 const MAX_DAC_ONE_SHOT_SIZE: usize = 10063860;
 
@@ -156,6 +156,47 @@ fn encode_decode_preimages() {
     assert_eq!(data, revealed, "Revealed different contents to original")
 }
 
+#[test]
+fn reveal_dac_root_single_page() {
+    // Arrange
+    let data = b"a payload fitting in one contents page".to_vec();
+    let mut host = tezos_smart_rollup_mock::MockHost::default();
+    let root_hash = prepare_preimages(&data, |_hash, page| {
+        host.set_preimage(page);
+    })
+    .unwrap();
+
+    // Act
+    let revealed = reveal_dac_root(&host, root_hash.as_ref(), 1);
+
+    // Assert
+    assert_eq!(Ok(data), revealed);
+}
+
+#[test]
+fn reveal_dac_root_two_levels() {
+    // Arrange
+    let mut data = Vec::with_capacity(3 * MAX_PAGE_SIZE);
+    (0u32..)
+        .take(3 * MAX_PAGE_SIZE / 4)
+        .map(u32::to_le_bytes)
+        .for_each(|bytes| data.extend_from_slice(&bytes));
+
+    let mut host = tezos_smart_rollup_mock::MockHost::default();
+    let root_hash = prepare_preimages(&data, |_hash, page| {
+        host.set_preimage(page);
+    })
+    .unwrap();
+
+    // Act
+    let revealed = reveal_dac_root(&host, root_hash.as_ref(), 2);
+    let too_deep = reveal_dac_root(&host, root_hash.as_ref(), 1);
+
+    // Assert
+    assert_eq!(Ok(data), revealed);
+    assert_eq!(Err(RuntimeError::DecodingError), too_deep);
+}
+
 fn save_content<Host: Runtime>(
     buffer: &mut Vec<u8>,
 ) -> impl FnMut(&mut Host, V0SliceContentPage) -> Result<(), &'static str> + '_ {
@@ -25,7 +25,7 @@ pub mod host {
     //!
     //! [core_unsafe]: crate::core_unsafe
 
-    pub use tezos_smart_rollup_host::runtime::{Runtime, RuntimeError};
+    pub use tezos_smart_rollup_host::runtime::{DecodeError, Runtime, RuntimeError};
     #[doc(inline)]
     pub use tezos_smart_rollup_host::Error as HostError;
 }